@@ -0,0 +1,380 @@
+//! Frame accurate animation of scene items.
+//!
+//! An [`Animation`] interpolates between two [`SceneItemTransform`]s and sends all keyframes as a
+//! single [`ExecutionType::SerialFrame`] batch, sleeping a single frame between each of them. That
+//! way, the motion is driven by the render loop of OBS and doesn't depend on the timing of the
+//! connection.
+//!
+//! ```no_run
+//! use obws::{
+//!     animation::{Animation, Easing},
+//!     requests::scene_items::{Position, SceneItemTransform},
+//!     Client,
+//! };
+//!
+//! # async fn run(client: &Client) -> obws::Result<()> {
+//! let start = client.scene_items().transform("Scene", 1).await?;
+//!
+//! Animation {
+//!     scene: "Scene",
+//!     item_id: 1,
+//!     from: start.into(),
+//!     to: SceneItemTransform {
+//!         position: Some(Position {
+//!             x: Some(960.0),
+//!             y: Some(540.0),
+//!         }),
+//!         rotation: Some(90.0),
+//!         ..SceneItemTransform::default()
+//!     },
+//!     frames: 60,
+//!     easing: Easing::EaseInOut,
+//! }
+//! .play(client)
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    requests::{
+        general::Sleep,
+        scene_items::{Bounds, Crop, Position, Scale, SceneItemTransform, SetTransform},
+        ExecutionType,
+    },
+    Client, Result,
+};
+
+/// Animation of a single scene item from one transform to another.
+///
+/// Numeric values (position, rotation, scale, bounds size and crop) are interpolated, if they are
+/// set in both transforms. If a value is only set in one of them, that value is used for all
+/// keyframes. Values that can't be interpolated (alignment and bounds type) are taken from the
+/// target transform, if set there, and applied from the first keyframe on.
+#[derive(Clone, Copy, Debug)]
+pub struct Animation<'a> {
+    /// Name of the scene the item is in.
+    pub scene: &'a str,
+    /// Numeric ID of the scene item.
+    pub item_id: i64,
+    /// Transform at the start of the animation.
+    pub from: SceneItemTransform,
+    /// Transform at the end of the animation.
+    pub to: SceneItemTransform,
+    /// Duration of the animation, in frames of OBS's render loop.
+    pub frames: u32,
+    /// Curve that describes the progress of the animation over time.
+    pub easing: Easing,
+}
+
+impl<'a> Animation<'a> {
+    /// Generate the transforms for each frame of the animation, including the start and end
+    /// transform. For an animation of `n` frames, this results in `n + 1` keyframes.
+    pub fn keyframes(&self) -> Vec<SceneItemTransform> {
+        (0..=self.frames)
+            .map(|frame| {
+                let progress = if self.frames == 0 {
+                    1.0
+                } else {
+                    frame as f32 / self.frames as f32
+                };
+
+                interpolate(&self.from, &self.to, self.easing.apply(progress))
+            })
+            .collect()
+    }
+
+    /// Play the animation, by sending all keyframes as a single batch to obs-websocket.
+    ///
+    /// The batch is halted at the first failing request, in which case its error is returned.
+    pub async fn play(&self, client: &Client) -> Result<()> {
+        let scene_items = client.scene_items();
+        let mut batch = client
+            .batch()
+            .execution_type(ExecutionType::SerialFrame)
+            .halt_on_failure(true);
+        let mut items = Vec::new();
+
+        for (i, transform) in self.keyframes().into_iter().enumerate() {
+            if i > 0 {
+                items.push(batch.sleep(Sleep::Frames(1)));
+            }
+
+            items.push(batch.add(scene_items.set_transform(SetTransform {
+                scene: self.scene,
+                item_id: self.item_id,
+                transform,
+            })));
+        }
+
+        let mut results = batch.send().await?;
+        items.into_iter().try_for_each(|item| results.take(item))
+    }
+}
+
+/// Easing curve, that maps the linear progress of an animation to the actual progress.
+///
+/// The predefined curves are the same as the ones in CSS.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    /// Constant speed from start to end.
+    Linear,
+    /// Start slow and speed up towards the end.
+    EaseIn,
+    /// Start fast and slow down towards the end.
+    EaseOut,
+    /// Start slow, speed up and slow down again towards the end.
+    EaseInOut,
+    /// Custom cubic Bézier curve, with the control points `(x1, y1)` and `(x2, y2)`. The `x`
+    /// values are limited to the range `0.0..=1.0`.
+    CubicBezier {
+        /// Horizontal position of the first control point.
+        x1: f32,
+        /// Vertical position of the first control point.
+        y1: f32,
+        /// Horizontal position of the second control point.
+        x2: f32,
+        /// Vertical position of the second control point.
+        y2: f32,
+    },
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl Easing {
+    /// Map the linear progress (in the range `0.0..=1.0`) to the eased progress.
+    pub fn apply(self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+
+        match self {
+            Self::Linear => progress,
+            Self::EaseIn => cubic_bezier(0.42, 0.0, 1.0, 1.0, progress),
+            Self::EaseOut => cubic_bezier(0.0, 0.0, 0.58, 1.0, progress),
+            Self::EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, progress),
+            Self::CubicBezier { x1, y1, x2, y2 } => cubic_bezier(x1, y1, x2, y2, progress),
+        }
+    }
+}
+
+/// Evaluate a cubic Bézier curve from `(0, 0)` to `(1, 1)` at the horizontal position `x`.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let curve = |p1: f32, p2: f32, t: f32| {
+        let inv = 1.0 - t;
+        3.0 * inv * inv * t * p1 + 3.0 * inv * t * t * p2 + t * t * t
+    };
+
+    // With both x values in range, the curve is monotonic on the x axis and the curve parameter
+    // for `x` can be found with a simple bisection.
+    let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+    let (mut low, mut high) = (0.0, 1.0);
+
+    for _ in 0..32 {
+        let mid = (low + high) / 2.0;
+        if curve(x1, x2, mid) < x {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    curve(y1, y2, (low + high) / 2.0)
+}
+
+fn interpolate(
+    from: &SceneItemTransform,
+    to: &SceneItemTransform,
+    progress: f32,
+) -> SceneItemTransform {
+    let lerp = |from: Option<f32>, to: Option<f32>| match (from, to) {
+        (Some(from), Some(to)) => Some(from + (to - from) * progress),
+        (from, to) => to.or(from),
+    };
+    let lerp_u32 = |from: Option<u32>, to: Option<u32>| {
+        lerp(from.map(|v| v as f32), to.map(|v| v as f32)).map(|v| v.round().max(0.0) as u32)
+    };
+
+    SceneItemTransform {
+        position: merge(from.position, to.position, |from, to| Position {
+            x: lerp(from.x, to.x),
+            y: lerp(from.y, to.y),
+        }),
+        rotation: lerp(from.rotation, to.rotation),
+        scale: merge(from.scale, to.scale, |from, to| Scale {
+            x: lerp(from.x, to.x),
+            y: lerp(from.y, to.y),
+        }),
+        alignment: to.alignment.or(from.alignment),
+        bounds: merge(from.bounds, to.bounds, |from, to| Bounds {
+            r#type: to.r#type.or(from.r#type),
+            alignment: to.alignment.or(from.alignment),
+            width: lerp(from.width, to.width),
+            height: lerp(from.height, to.height),
+        }),
+        crop: merge(from.crop, to.crop, |from, to| Crop {
+            left: lerp_u32(from.left, to.left),
+            right: lerp_u32(from.right, to.right),
+            top: lerp_u32(from.top, to.top),
+            bottom: lerp_u32(from.bottom, to.bottom),
+        }),
+    }
+}
+
+/// Combine two optional groups of values, treating a missing group like one with all values
+/// unset.
+fn merge<T: Default>(from: Option<T>, to: Option<T>, f: impl FnOnce(T, T) -> T) -> Option<T> {
+    if from.is_none() && to.is_none() {
+        return None;
+    }
+
+    Some(f(from.unwrap_or_default(), to.unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{Alignment, BoundsType};
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 1e-4,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn easing_curves() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::CubicBezier {
+                x1: 0.1,
+                y1: 0.7,
+                x2: 1.0,
+                y2: 0.1,
+            },
+        ] {
+            assert_close(0.0, easing.apply(0.0));
+            assert_close(1.0, easing.apply(1.0));
+            assert_close(0.0, easing.apply(-1.0));
+            assert_close(1.0, easing.apply(2.0));
+        }
+
+        assert_close(0.25, Easing::Linear.apply(0.25));
+        assert_close(0.5, Easing::EaseInOut.apply(0.5));
+        assert!(Easing::EaseIn.apply(0.25) < 0.25);
+        assert!(Easing::EaseOut.apply(0.25) > 0.25);
+        assert_close(
+            0.3,
+            Easing::CubicBezier {
+                x1: 0.0,
+                y1: 0.0,
+                x2: 1.0,
+                y2: 1.0,
+            }
+            .apply(0.3),
+        );
+    }
+
+    #[test]
+    fn keyframes() {
+        let animation = Animation {
+            scene: "Scene",
+            item_id: 1,
+            from: SceneItemTransform {
+                position: Some(Position {
+                    x: Some(0.0),
+                    y: Some(100.0),
+                }),
+                rotation: Some(0.0),
+                crop: Some(Crop {
+                    left: Some(0),
+                    ..Crop::default()
+                }),
+                ..SceneItemTransform::default()
+            },
+            to: SceneItemTransform {
+                position: Some(Position {
+                    x: Some(100.0),
+                    y: None,
+                }),
+                rotation: Some(-90.0),
+                alignment: Some(Alignment::TOP),
+                bounds: Some(Bounds {
+                    r#type: Some(BoundsType::Stretch),
+                    ..Bounds::default()
+                }),
+                crop: Some(Crop {
+                    left: Some(9),
+                    ..Crop::default()
+                }),
+                ..SceneItemTransform::default()
+            },
+            frames: 4,
+            easing: Easing::Linear,
+        };
+
+        let keyframes = animation.keyframes();
+        assert_eq!(5, keyframes.len());
+
+        let positions = keyframes
+            .iter()
+            .map(|t| t.position.unwrap().x.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![0.0, 25.0, 50.0, 75.0, 100.0], positions);
+
+        let crops = keyframes
+            .iter()
+            .map(|t| t.crop.unwrap().left.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![0, 2, 5, 7, 9], crops);
+
+        let middle = keyframes[2];
+        assert_eq!(Some(100.0), middle.position.unwrap().y);
+        assert_eq!(Some(-45.0), middle.rotation);
+        assert_eq!(None, middle.scale);
+        assert_eq!(Some(Alignment::TOP), middle.alignment);
+        assert_eq!(Some(BoundsType::Stretch), middle.bounds.unwrap().r#type);
+        assert_eq!(
+            Some(Position {
+                x: Some(100.0),
+                y: Some(100.0),
+            }),
+            keyframes[4].position
+        );
+    }
+
+    #[test]
+    fn zero_frames() {
+        let animation = Animation {
+            scene: "Scene",
+            item_id: 1,
+            from: SceneItemTransform {
+                rotation: Some(0.0),
+                ..SceneItemTransform::default()
+            },
+            to: SceneItemTransform {
+                rotation: Some(180.0),
+                ..SceneItemTransform::default()
+            },
+            frames: 0,
+            easing: Easing::EaseIn,
+        };
+
+        let keyframes = animation.keyframes();
+        assert_eq!(1, keyframes.len());
+        assert_eq!(Some(180.0), keyframes[0].rotation);
+    }
+}
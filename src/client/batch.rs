@@ -0,0 +1,459 @@
+use std::{
+    any::Any,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    task::Poll,
+};
+
+use futures_util::future;
+use tokio::sync::oneshot;
+
+use super::Client;
+use crate::{
+    requests::{general::Sleep, ExecutionType, RawRequest},
+    responses::Status,
+    Error, Result,
+};
+
+tokio::task_local! {
+    /// Queue that collects requests, while the steps of a [`Batch`] are polled for the first
+    /// time.
+    pub(super) static BATCH_QUEUE: Arc<BatchQueue>;
+}
+
+/// A request that was collected from a step of a batch, together with the channel to send the
+/// response back to the waiting step.
+type QueuedRequest = (RawRequest, oneshot::Sender<(Status, serde_json::Value)>);
+
+/// Requests that were collected from the steps of a batch. Once they were taken out to be sent,
+/// the queue is closed and refuses any further requests.
+pub(super) struct BatchQueue(Mutex<Option<Vec<QueuedRequest>>>);
+
+impl BatchQueue {
+    fn new() -> Self {
+        Self(Mutex::new(Some(Vec::new())))
+    }
+
+    /// Add a request to the queue, returning a channel to await the response on.
+    ///
+    /// Fails with [`Error::InvalidBatchItem`] if the queue is already closed, as the request was
+    /// made too late to be part of the batch.
+    pub fn push(
+        &self,
+        request: RawRequest,
+    ) -> Result<oneshot::Receiver<(Status, serde_json::Value)>> {
+        let (tx, rx) = oneshot::channel();
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+            .ok_or(Error::InvalidBatchItem)?
+            .push((request, tx));
+        Ok(rx)
+    }
+
+    /// Amount of requests in the queue.
+    fn len(&self) -> usize {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map_or(0, Vec::len)
+    }
+
+    /// Take out all requests and close the queue.
+    fn take(&self) -> Vec<QueuedRequest> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+            .unwrap_or_default()
+    }
+}
+
+/// Source of the unique IDs of all batches, that tie the [`BatchItem`]s to their batch.
+static BATCH_IDS: AtomicU64 = AtomicU64::new(0);
+
+type BoxedResult = Box<dyn Any + Send>;
+type Step<'a> = Pin<Box<dyn Future<Output = BoxedResult> + Send + 'a>>;
+
+/// A batch of requests, that are sent to obs-websocket as a single message and processed
+/// together. Created through [`Client::batch`].
+///
+/// Any of the regular API calls can be added to a batch, by passing its (not yet awaited) future
+/// to [`Self::add`]. Each API call must only make a single request, which is true for all calls
+/// of this crate.
+pub struct Batch<'a> {
+    client: &'a Client,
+    id: u64,
+    halt_on_failure: Option<bool>,
+    execution_type: Option<ExecutionType>,
+    steps: Vec<Step<'a>>,
+}
+
+impl<'a> Batch<'a> {
+    pub(super) fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            id: BATCH_IDS.fetch_add(1, Ordering::Relaxed),
+            halt_on_failure: None,
+            execution_type: None,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Stop processing any further requests of the batch, once one of them failed. All requests
+    /// after the failed one result in [`Error::BatchHalted`].
+    pub fn halt_on_failure(mut self, halt_on_failure: bool) -> Self {
+        self.halt_on_failure = Some(halt_on_failure);
+        self
+    }
+
+    /// Define how obs-websocket executes the requests. The default is
+    /// [`ExecutionType::SerialRealtime`].
+    pub fn execution_type(mut self, execution_type: ExecutionType) -> Self {
+        self.execution_type = Some(execution_type);
+        self
+    }
+
+    /// Add a request to the batch, returning an item that allows to get its result from the
+    /// [`BatchResults`] once the batch was sent.
+    ///
+    /// The future must make its request right away, without awaiting anything else before, and
+    /// must not make any further requests. Otherwise, the request would not become part of the
+    /// batch, so sending the batch fails with [`Error::InvalidBatchItem`], or the item itself
+    /// fails with it, if it makes another request after the batch was sent.
+    pub fn add<F, T>(&mut self, request: F) -> BatchItem<T>
+    where
+        F: Future<Output = Result<T>> + Send + 'a,
+        T: Send + 'static,
+    {
+        self.steps.push(Box::pin(
+            async move { Box::new(request.await) as BoxedResult },
+        ));
+
+        BatchItem {
+            batch: self.id,
+            index: self.steps.len() - 1,
+            _result: PhantomData,
+        }
+    }
+
+    /// Add a [`Sleep`] to the batch, which pauses the processing of the following requests.
+    ///
+    /// Sleeping by duration is only allowed for [`ExecutionType::SerialRealtime`] and sleeping by
+    /// frames only for [`ExecutionType::SerialFrame`]. Otherwise, sending the batch fails with
+    /// [`Error::SleepExecutionType`].
+    pub fn sleep(&mut self, sleep: Sleep) -> BatchItem<()> {
+        let client = self.client;
+        self.add(async move { client.general().sleep(sleep).await })
+    }
+
+    /// Amount of requests in this batch.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Whether this batch doesn't contain any requests.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Send all requests of the batch to obs-websocket and wait for their responses.
+    ///
+    /// This only fails if the batch as a whole couldn't be sent, contains requests that are
+    /// invalid for its execution type, or contains items that don't make exactly one request
+    /// right away. Failures of single requests are reported through the [`BatchResults`].
+    pub async fn send(self) -> Result<BatchResults> {
+        let Self {
+            client,
+            id,
+            halt_on_failure,
+            execution_type,
+            mut steps,
+        } = self;
+
+        let queue = Arc::new(BatchQueue::new());
+        let mut results = steps.iter().map(|_| None).collect::<Vec<_>>();
+
+        // Poll each step once, which makes them put their request into the queue and then wait
+        // for the response. Steps that fail early, before making a request, are already done.
+        // Any step that is still waiting for something else, would send its request on its own
+        // later, outside of the batch.
+        future::poll_fn(|cx| {
+            for (step, result) in steps.iter_mut().zip(&mut results) {
+                let queued = queue.len();
+                let poll = BATCH_QUEUE.sync_scope(Arc::clone(&queue), || step.as_mut().poll(cx));
+                let expected = match poll {
+                    Poll::Ready(value) => {
+                        *result = Some(value);
+                        queued
+                    }
+                    Poll::Pending => queued + 1,
+                };
+
+                if queue.len() != expected {
+                    return Poll::Ready(Err(Error::InvalidBatchItem));
+                }
+            }
+
+            Poll::Ready(Ok(()))
+        })
+        .await?;
+
+        let (requests, mut senders) = queue
+            .take()
+            .into_iter()
+            .map(|(request, tx)| (request, Some(tx)))
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+
+        validate_sleeps(
+            &requests,
+            execution_type.unwrap_or(ExecutionType::SerialRealtime),
+        )?;
+
+        if !requests.is_empty() {
+            let responses = client
+                .send_batch(&requests, halt_on_failure, execution_type)
+                .await?;
+
+            for response in responses {
+                let tx = response
+                    .id
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| senders.get_mut(index))
+                    .and_then(Option::take);
+
+                if let Some(tx) = tx {
+                    tx.send((response.status, response.data)).ok();
+                }
+            }
+        }
+
+        // Any request without a response was not processed, which lets its step fail.
+        drop(senders);
+
+        // Steps keep access to the now closed queue, so that any further requests fail instead
+        // of being sent outside of the batch.
+        for (step, result) in steps.into_iter().zip(&mut results) {
+            if result.is_none() {
+                *result = Some(BATCH_QUEUE.scope(Arc::clone(&queue), step).await);
+            }
+        }
+
+        Ok(BatchResults { id, results })
+    }
+}
+
+/// Ensure that all sleep requests match the kind of sleep, that is allowed for the execution type.
+fn validate_sleeps(requests: &[RawRequest], execution_type: ExecutionType) -> Result<()> {
+    let allowed = match execution_type {
+        ExecutionType::SerialRealtime => Some("sleepMillis"),
+        ExecutionType::SerialFrame => Some("sleepFrames"),
+        ExecutionType::Parallel => None,
+    };
+
+    let valid = requests
+        .iter()
+        .filter(|req| req.request_type == "Sleep")
+        .all(|req| {
+            allowed
+                .zip(req.request_data.as_ref())
+                .map_or(false, |(field, data)| data.get(field).is_some())
+        });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::SleepExecutionType(execution_type))
+    }
+}
+
+/// Handle for a single request within a [`Batch`], to get its result from the [`BatchResults`].
+pub struct BatchItem<T> {
+    batch: u64,
+    index: usize,
+    _result: PhantomData<fn() -> T>,
+}
+
+/// Results of all requests of a sent [`Batch`].
+pub struct BatchResults {
+    id: u64,
+    results: Vec<Option<BoxedResult>>,
+}
+
+impl BatchResults {
+    /// Take out the result of a single request.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::ForeignBatchItem`] if the item belongs to a different batch, besides
+    /// the error of the request itself.
+    pub fn take<T>(&mut self, item: BatchItem<T>) -> Result<T>
+    where
+        T: 'static,
+    {
+        if item.batch != self.id {
+            return Err(Error::ForeignBatchItem);
+        }
+
+        // Items are only created together with a result of the same type, and taking the result
+        // consumes the item, so the result is always there.
+        let result = self.results[item.index]
+            .take()
+            .and_then(|result| result.downcast::<Result<T>>().ok())
+            .ok_or(Error::ForeignBatchItem)?;
+
+        *result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version() -> RawRequest {
+        RawRequest {
+            request_type: "GetVersion".to_owned(),
+            request_data: None,
+        }
+    }
+
+    #[test]
+    fn collect_requests() {
+        let queue = Arc::new(BatchQueue::new());
+
+        BATCH_QUEUE.sync_scope(Arc::clone(&queue), || {
+            let queue = BATCH_QUEUE.with(Arc::clone);
+            queue.push(version()).unwrap();
+        });
+
+        assert_eq!(1, queue.len());
+        let requests = queue.take();
+        assert_eq!(1, requests.len());
+        assert_eq!("GetVersion", requests[0].0.request_type);
+        assert!(queue.take().is_empty());
+        assert!(matches!(
+            queue.push(version()),
+            Err(Error::InvalidBatchItem)
+        ));
+        assert!(BATCH_QUEUE.try_with(|_| ()).is_err());
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn round_trip() {
+        use serde_json::json;
+
+        use crate::{responses::general::Version, testing::MockServer};
+
+        let server = MockServer::start().await.unwrap();
+        server.respond("GetSceneItemEnabled", json!({ "sceneItemEnabled": true }));
+        let client = server.connect().await.unwrap();
+        server.clear_requests();
+
+        let (general, recording, scene_items, streaming) = (
+            client.general(),
+            client.recording(),
+            client.scene_items(),
+            client.streaming(),
+        );
+
+        let mut batch = client.batch().halt_on_failure(true);
+        let enabled = batch.add(scene_items.enabled("Main", 1));
+        let sleep = batch.sleep(Sleep::Millis(time::Duration::milliseconds(10)));
+        let version = batch.add(general.version());
+        let stream = batch.add(streaming.start());
+        let record = batch.add(recording.start());
+        let mut results = batch.send().await.unwrap();
+
+        let mut other = client.batch();
+        let foreign = other.add(general.version());
+        let mut other_results = other.send().await.unwrap();
+
+        assert!(results.take(enabled).unwrap());
+        assert!(results.take(sleep).is_ok());
+        assert!(results.take::<Version>(version).is_ok());
+        assert!(matches!(results.take(stream), Err(Error::Api { .. })));
+        assert!(matches!(results.take(record), Err(Error::BatchHalted)));
+        assert!(matches!(
+            results.take(foreign),
+            Err(Error::ForeignBatchItem)
+        ));
+        assert!(matches!(
+            other_results.take(client.batch().add(general.version())),
+            Err(Error::ForeignBatchItem)
+        ));
+        assert_eq!(5, server.requests().len());
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn invalid_items() {
+        use crate::testing::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let client = server.connect().await.unwrap();
+        let general = client.general();
+        server.clear_requests();
+
+        // Waiting for something else first would send the request on its own.
+        let mut batch = client.batch();
+        batch.add(general.version());
+        batch.add(async {
+            tokio::task::yield_now().await;
+            general.version().await
+        });
+        assert!(matches!(batch.send().await, Err(Error::InvalidBatchItem)));
+
+        // Several requests at once can't be told apart.
+        let mut batch = client.batch();
+        batch.add(async { future::try_join(general.version(), general.version()).await });
+        assert!(matches!(batch.send().await, Err(Error::InvalidBatchItem)));
+        assert!(server.requests().is_empty());
+
+        // Further requests after the first one are too late for the batch.
+        let mut batch = client.batch();
+        let twice = batch.add(async {
+            general.version().await?;
+            general.version().await
+        });
+        let mut results = batch.send().await.unwrap();
+        assert!(matches!(results.take(twice), Err(Error::InvalidBatchItem)));
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[test]
+    fn sleep_execution_type() {
+        let millis = RawRequest::new(
+            crate::requests::general::Request::Sleep(Sleep::Millis(time::Duration::milliseconds(
+                50,
+            )))
+            .into(),
+        )
+        .unwrap();
+        let frames =
+            RawRequest::new(crate::requests::general::Request::Sleep(Sleep::Frames(12)).into())
+                .unwrap();
+
+        assert!(validate_sleeps(&[], ExecutionType::Parallel).is_ok());
+        assert!(
+            validate_sleeps(std::slice::from_ref(&millis), ExecutionType::SerialRealtime).is_ok()
+        );
+        assert!(validate_sleeps(std::slice::from_ref(&frames), ExecutionType::SerialFrame).is_ok());
+        assert!(matches!(
+            validate_sleeps(&[millis], ExecutionType::SerialFrame),
+            Err(Error::SleepExecutionType(ExecutionType::SerialFrame))
+        ));
+        assert!(matches!(
+            validate_sleeps(&[frames], ExecutionType::Parallel),
+            Err(Error::SleepExecutionType(ExecutionType::Parallel))
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::requests;
+
+/// Category of requests, matching the API groups of the [`Client`](super::Client), like
+/// [`Client::scenes`](super::Client::scenes).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RequestCategory {
+    /// Requests of [`Config`](super::Config).
+    Config,
+    /// Requests of [`Filters`](super::Filters).
+    Filters,
+    /// Requests of [`General`](super::General).
+    General,
+    /// Requests of [`Hotkeys`](super::Hotkeys).
+    Hotkeys,
+    /// Requests of [`Inputs`](super::Inputs).
+    Inputs,
+    /// Requests of [`MediaInputs`](super::MediaInputs).
+    MediaInputs,
+    /// Requests of [`Outputs`](super::Outputs).
+    Outputs,
+    /// Requests of [`Profiles`](super::Profiles).
+    Profiles,
+    /// Requests of [`Recording`](super::Recording).
+    Recording,
+    /// Requests of [`ReplayBuffer`](super::ReplayBuffer).
+    ReplayBuffer,
+    /// Requests of [`SceneCollections`](super::SceneCollections).
+    SceneCollections,
+    /// Requests of [`SceneItems`](super::SceneItems).
+    SceneItems,
+    /// Requests of [`Scenes`](super::Scenes).
+    Scenes,
+    /// Requests of [`Sources`](super::Sources).
+    Sources,
+    /// Requests of [`Streaming`](super::Streaming).
+    Streaming,
+    /// Requests of [`Transitions`](super::Transitions).
+    Transitions,
+    /// Requests of [`Ui`](super::Ui).
+    Ui,
+    /// Requests of [`VirtualCam`](super::VirtualCam).
+    VirtualCam,
+}
+
+impl RequestCategory {
+    /// All categories, in alphabetical order.
+    pub const ALL: [Self; 18] = [
+        Self::Config,
+        Self::Filters,
+        Self::General,
+        Self::Hotkeys,
+        Self::Inputs,
+        Self::MediaInputs,
+        Self::Outputs,
+        Self::Profiles,
+        Self::Recording,
+        Self::ReplayBuffer,
+        Self::SceneCollections,
+        Self::SceneItems,
+        Self::Scenes,
+        Self::Sources,
+        Self::Streaming,
+        Self::Transitions,
+        Self::Ui,
+        Self::VirtualCam,
+    ];
+
+    /// All request types of this category, that are covered by this crate.
+    pub fn request_types(self) -> &'static [&'static str] {
+        match self {
+            Self::Config => requests::config::REQUEST_TYPES,
+            Self::Filters => requests::filters::REQUEST_TYPES,
+            Self::General => requests::general::REQUEST_TYPES,
+            Self::Hotkeys => requests::hotkeys::REQUEST_TYPES,
+            Self::Inputs => requests::inputs::REQUEST_TYPES,
+            Self::MediaInputs => requests::media_inputs::REQUEST_TYPES,
+            Self::Outputs => requests::outputs::REQUEST_TYPES,
+            Self::Profiles => requests::profiles::REQUEST_TYPES,
+            Self::Recording => requests::recording::REQUEST_TYPES,
+            Self::ReplayBuffer => requests::replay_buffer::REQUEST_TYPES,
+            Self::SceneCollections => requests::scene_collections::REQUEST_TYPES,
+            Self::SceneItems => requests::scene_items::REQUEST_TYPES,
+            Self::Scenes => requests::scenes::REQUEST_TYPES,
+            Self::Sources => requests::sources::REQUEST_TYPES,
+            Self::Streaming => requests::streaming::REQUEST_TYPES,
+            Self::Transitions => requests::transitions::REQUEST_TYPES,
+            Self::Ui => requests::ui::REQUEST_TYPES,
+            Self::VirtualCam => requests::virtual_cam::REQUEST_TYPES,
+        }
+    }
+}
+
+/// Report about the requests that the connected obs-websocket instance supports, as created by
+/// [`Client::capabilities`](super::Client::capabilities).
+#[derive(Clone, Debug)]
+pub struct Capabilities {
+    /// Supported and unsupported requests of each category, in the order of
+    /// [`RequestCategory::ALL`].
+    pub categories: Vec<CategoryCapabilities>,
+    /// Requests that obs-websocket supports, but aren't covered by this crate. They can still be
+    /// sent with [`Client::raw_request`](super::Client::raw_request).
+    pub uncovered: Vec<String>,
+}
+
+/// Supported and unsupported requests of a single [`RequestCategory`].
+#[derive(Clone, Debug)]
+pub struct CategoryCapabilities {
+    /// The category of the requests.
+    pub category: RequestCategory,
+    /// Requests that obs-websocket supports.
+    pub supported: Vec<&'static str>,
+    /// Requests that obs-websocket doesn't support, and fail with
+    /// [`Error::Unsupported`](crate::Error::Unsupported).
+    pub unsupported: Vec<&'static str>,
+}
+
+impl CategoryCapabilities {
+    /// Whether all requests of the category are supported.
+    pub fn is_complete(&self) -> bool {
+        self.unsupported.is_empty()
+    }
+}
+
+impl Capabilities {
+    /// Create the report from the list of requests that obs-websocket supports.
+    pub(super) fn new(available_requests: &[String]) -> Self {
+        let categories = RequestCategory::ALL
+            .iter()
+            .map(|&category| {
+                let (supported, unsupported) =
+                    category.request_types().iter().partition(|request_type| {
+                        available_requests
+                            .iter()
+                            .any(|available| available == *request_type)
+                    });
+
+                CategoryCapabilities {
+                    category,
+                    supported,
+                    unsupported,
+                }
+            })
+            .collect();
+
+        let uncovered = available_requests
+            .iter()
+            .filter(|available| {
+                !RequestCategory::ALL
+                    .iter()
+                    .any(|category| category.request_types().contains(&available.as_str()))
+            })
+            .cloned()
+            .collect();
+
+        Self {
+            categories,
+            uncovered,
+        }
+    }
+
+    /// Get the supported and unsupported requests of a single category.
+    pub fn category(&self, category: RequestCategory) -> &CategoryCapabilities {
+        self.categories
+            .iter()
+            .find(|capabilities| capabilities.category == category)
+            .expect("report covers all categories")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_types_are_unique() {
+        let mut all = RequestCategory::ALL
+            .iter()
+            .flat_map(|category| category.request_types())
+            .collect::<Vec<_>>();
+        let count = all.len();
+        all.sort_unstable();
+        all.dedup();
+        assert_eq!(
+            count,
+            all.len(),
+            "request types are in exactly one category"
+        );
+    }
+
+    #[test]
+    fn report() {
+        let available = ["GetVersion", "Sleep", "GetSceneList", "GetSceneItemSource"]
+            .iter()
+            .map(|&name| name.to_owned())
+            .collect::<Vec<_>>();
+        let capabilities = Capabilities::new(&available);
+
+        assert_eq!(RequestCategory::ALL.len(), capabilities.categories.len());
+        assert_eq!(vec!["GetSceneItemSource"], capabilities.uncovered);
+
+        let general = capabilities.category(RequestCategory::General);
+        assert_eq!(vec!["GetVersion", "Sleep"], general.supported);
+        assert!(general.unsupported.contains(&"GetStats"));
+        assert!(!general.is_complete());
+
+        let scenes = capabilities.category(RequestCategory::Scenes);
+        assert_eq!(vec!["GetSceneList"], scenes.supported);
+
+        let virtual_cam = capabilities.category(RequestCategory::VirtualCam);
+        assert!(virtual_cam.supported.is_empty());
+        assert_eq!(4, virtual_cam.unsupported.len());
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use tokio::sync::oneshot;
+
+use crate::requests::RawRequest;
+
+/// Setters that can be coalesced, together with the request fields that identify their target.
+/// Sending only the newest value of these has the same effect as sending all of them in order,
+/// which excludes setters that only update some fields, like `SetSceneItemTransform`.
+const COALESCED_SETTERS: &[(&str, &[&str])] = &[
+    ("SetInputVolume", &["inputName"]),
+    ("SetInputAudioBalance", &["inputName"]),
+    ("SetInputAudioSyncOffset", &["inputName"]),
+    ("SetTBarPosition", &[]),
+    ("SetCurrentSceneTransitionDuration", &[]),
+];
+
+/// Coalesces high-rate setters, so that at most one request per target is in flight and only the
+/// newest of the waiting values is sent afterwards.
+///
+/// Each target has a slot, that exists while a request for it is in flight and holds the channel
+/// of the caller waiting for its turn. A newer caller replaces the waiting one, which drops its
+/// channel and tells it that it was superseded.
+#[derive(Default)]
+pub(super) struct Coalescer {
+    slots: Mutex<HashMap<String, Option<oneshot::Sender<Turn>>>>,
+}
+
+/// Result of trying to send a request through the [`Coalescer`].
+pub(super) enum Acquired {
+    /// Nothing else is in flight for the same target, so the request can be sent right away.
+    Turn(Turn),
+    /// Another request for the same target is in flight. The receiver either gets the turn once
+    /// it's done, or fails if a newer request superseded this one.
+    Wait(oneshot::Receiver<Turn>),
+}
+
+/// Permission to send a request for a target. Once dropped, the turn is passed on to the newest
+/// waiting request for the same target.
+pub(super) struct Turn {
+    inner: Option<(Arc<Coalescer>, String)>,
+}
+
+impl Coalescer {
+    /// Acquire the turn for the given request, or [`None`] if it isn't a setter that can be
+    /// coalesced.
+    pub fn acquire(self: &Arc<Self>, request: &RawRequest) -> Option<Acquired> {
+        let key = coalesce_key(request)?;
+        let mut slots = self.slots.lock().unwrap_or_else(PoisonError::into_inner);
+
+        Some(match slots.entry(key) {
+            Entry::Vacant(entry) => {
+                let turn = Turn {
+                    inner: Some((Arc::clone(self), entry.key().clone())),
+                };
+                entry.insert(None);
+                Acquired::Turn(turn)
+            }
+            Entry::Occupied(mut entry) => {
+                let (tx, rx) = oneshot::channel();
+                entry.insert(Some(tx));
+                Acquired::Wait(rx)
+            }
+        })
+    }
+
+    /// Pass the turn for the target on to the waiting request, or free the slot if there is none.
+    fn release(self: Arc<Self>, key: String) {
+        loop {
+            let tx = {
+                let mut slots = self.slots.lock().unwrap_or_else(PoisonError::into_inner);
+                match slots.get_mut(&key).and_then(Option::take) {
+                    Some(tx) => tx,
+                    None => {
+                        slots.remove(&key);
+                        return;
+                    }
+                }
+            };
+
+            let turn = Turn {
+                inner: Some((Arc::clone(&self), key.clone())),
+            };
+
+            // The waiting request was cancelled in the meantime, so try the next one instead.
+            match tx.send(turn) {
+                Ok(()) => return,
+                Err(mut turn) => turn.inner = None,
+            }
+        }
+    }
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        if let Some((coalescer, key)) = self.inner.take() {
+            coalescer.release(key);
+        }
+    }
+}
+
+/// Build the key that identifies the target of a setter, made up of the request type and the
+/// values of all target fields.
+fn coalesce_key(request: &RawRequest) -> Option<String> {
+    let (_, fields) = COALESCED_SETTERS
+        .iter()
+        .find(|(request_type, _)| *request_type == request.request_type)?;
+
+    let targets = fields
+        .iter()
+        .map(|field| {
+            request
+                .request_data
+                .as_ref()
+                .and_then(|data| data.get(field))
+                .cloned()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    Some(format!(
+        "{}{}",
+        request.request_type,
+        serde_json::Value::Array(targets)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn volume(input: &str) -> RawRequest {
+        RawRequest {
+            request_type: "SetInputVolume".to_owned(),
+            request_data: Some(json!({ "inputName": input, "inputVolumeDb": -3.0 })),
+        }
+    }
+
+    fn turn(acquired: Option<Acquired>) -> Turn {
+        match acquired {
+            Some(Acquired::Turn(turn)) => turn,
+            _ => panic!("expected to get the turn"),
+        }
+    }
+
+    fn wait(acquired: Option<Acquired>) -> oneshot::Receiver<Turn> {
+        match acquired {
+            Some(Acquired::Wait(rx)) => rx,
+            _ => panic!("expected to wait for the turn"),
+        }
+    }
+
+    #[test]
+    fn keys() {
+        assert_eq!(
+            Some(r#"SetInputVolume["Mic"]"#),
+            coalesce_key(&volume("Mic")).as_deref()
+        );
+        assert_eq!(
+            Some("SetTBarPosition[]"),
+            coalesce_key(&RawRequest {
+                request_type: "SetTBarPosition".to_owned(),
+                request_data: Some(json!({ "position": 0.5 })),
+            })
+            .as_deref()
+        );
+        assert!(coalesce_key(&RawRequest {
+            request_type: "SetInputMute".to_owned(),
+            request_data: Some(json!({ "inputName": "Mic", "inputMuted": true })),
+        })
+        .is_none());
+    }
+
+    #[tokio::test]
+    async fn newest_wins() {
+        let coalescer = Arc::new(Coalescer::default());
+
+        let first = turn(coalescer.acquire(&volume("Mic")));
+        let other = turn(coalescer.acquire(&volume("Desktop")));
+        let superseded = wait(coalescer.acquire(&volume("Mic")));
+        let newest = wait(coalescer.acquire(&volume("Mic")));
+
+        assert!(superseded.await.is_err());
+        drop(first);
+        let second = newest.await.unwrap();
+
+        // A cancelled waiter is skipped, and the slot freed once nobody waits anymore.
+        drop(wait(coalescer.acquire(&volume("Mic"))));
+        drop(second);
+        drop(other);
+        assert!(coalescer.slots.lock().unwrap().is_empty());
+        turn(coalescer.acquire(&volume("Mic")));
+    }
+}
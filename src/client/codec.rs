@@ -0,0 +1,128 @@
+use serde::{de::DeserializeOwned, Serialize};
+use tokio_tungstenite::tungstenite::{self, Message};
+
+use super::{connection::HandshakeError, InnerError};
+
+/// Encoding of the messages that are exchanged with obs-websocket.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    /// Messages are encoded as JSON and sent as text frames. This is the default.
+    Json,
+    /// Messages are encoded as [MessagePack](https://msgpack.org) and sent as binary frames,
+    /// which is faster to decode, especially for high-volume events.
+    #[cfg(feature = "msgpack")]
+    MsgPack,
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Self::Json
+    }
+}
+
+impl Encoding {
+    /// Name of the web-socket sub-protocol, that tells obs-websocket which encoding to use.
+    pub(crate) fn protocol(self) -> &'static str {
+        match self {
+            Self::Json => "obswebsocket.json",
+            #[cfg(feature = "msgpack")]
+            Self::MsgPack => "obswebsocket.msgpack",
+        }
+    }
+
+    /// Find the encoding for the given web-socket sub-protocol name.
+    #[cfg_attr(not(feature = "testing"), allow(dead_code))]
+    pub(crate) fn from_protocol(protocol: &str) -> Option<Self> {
+        match protocol {
+            "obswebsocket.json" => Some(Self::Json),
+            #[cfg(feature = "msgpack")]
+            "obswebsocket.msgpack" => Some(Self::MsgPack),
+            _ => None,
+        }
+    }
+
+    /// Encode a message, ready to be sent to obs-websocket.
+    pub(crate) fn encode<T>(self, value: &T) -> Result<Message, serde_json::Error>
+    where
+        T: Serialize,
+    {
+        match self {
+            Self::Json => serde_json::to_string(value).map(Message::Text),
+            #[cfg(feature = "msgpack")]
+            Self::MsgPack => rmp_serde::to_vec_named(value)
+                .map(Message::Binary)
+                .map_err(serde::ser::Error::custom),
+        }
+    }
+
+    /// Decode a message that was received from obs-websocket.
+    pub(crate) fn decode<T>(self, message: Message) -> Result<T, DecodeError>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            Self::Json => {
+                let text = message
+                    .into_text()
+                    .map_err(|e| DecodeError::IntoText(Box::new(e)))?;
+                serde_json::from_str(&text).map_err(DecodeError::Deserialize)
+            }
+            #[cfg(feature = "msgpack")]
+            Self::MsgPack => rmp_serde::from_slice(&message.into_data())
+                .map_err(|e| DecodeError::Deserialize(serde::de::Error::custom(e))),
+        }
+    }
+}
+
+/// Failures while decoding a message, which are turned into the error type of the caller.
+pub(crate) enum DecodeError {
+    IntoText(Box<tungstenite::Error>),
+    Deserialize(serde_json::Error),
+}
+
+impl From<DecodeError> for InnerError {
+    fn from(error: DecodeError) -> Self {
+        match error {
+            DecodeError::IntoText(e) => Self::IntoText(e),
+            DecodeError::Deserialize(e) => Self::DeserializeMessage(e),
+        }
+    }
+}
+
+impl From<DecodeError> for HandshakeError {
+    fn from(error: DecodeError) -> Self {
+        match error {
+            DecodeError::IntoText(e) => Self::IntoText(e),
+            DecodeError::Deserialize(e) => Self::DeserializeMessage(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    #[test]
+    fn json() {
+        let value = json!({ "op": 6, "d": { "requestType": "GetVersion" } });
+        let message = Encoding::Json.encode(&value).unwrap();
+
+        assert!(message.is_text());
+        assert_eq!(value, Encoding::Json.decode::<Value>(message).ok().unwrap());
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack() {
+        let value = json!({ "op": 6, "d": { "requestType": "GetVersion", "values": [1, 2.5] } });
+        let message = Encoding::MsgPack.encode(&value).unwrap();
+
+        assert!(message.is_binary());
+        assert_eq!(
+            value,
+            Encoding::MsgPack.decode::<Value>(message).ok().unwrap()
+        );
+    }
+}
@@ -1,28 +1,46 @@
-use std::collections::{HashMap, VecDeque};
+#[cfg(feature = "events")]
+use std::sync::PoisonError;
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+};
 
 use futures_util::{Sink, SinkExt, Stream, StreamExt};
+#[cfg(feature = "events")]
+use tokio::sync::{broadcast, mpsc};
 use tokio::{
     sync::{oneshot, Mutex},
-    time::{self, Duration},
+    time::{self, Duration, Instant},
 };
 pub use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
-use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::{protocol::CloseFrame, Message};
 use tracing::debug;
 
-use super::InnerError;
+use super::{Encoding, InnerError};
+#[cfg(feature = "events")]
+use crate::events::Event;
 use crate::{
-    requests::{ClientRequest, EventSubscription, Identify},
-    responses::{Hello, Identified, RequestResponse, ServerMessage, Status},
+    requests::{ClientRequest, EventSubscription, Identify, Request},
+    responses::{
+        general::Version, Hello, Identified, ServerMessage, Status, StatusCode, WebSocketCloseCode,
+    },
 };
 
 /// Wrapper for the list of ongoing requests that wait for response.
-#[derive(Default)]
-pub(super) struct ReceiverList(Mutex<HashMap<u64, oneshot::Sender<(Status, serde_json::Value)>>>);
+pub(super) struct ReceiverList<T = (Status, serde_json::Value)>(
+    Mutex<HashMap<u64, oneshot::Sender<T>>>,
+);
+
+impl<T> Default for ReceiverList<T> {
+    fn default() -> Self {
+        Self(Mutex::default())
+    }
+}
 
-impl ReceiverList {
+impl<T> ReceiverList<T> {
     /// Add a new receiver to the wait list, that will be notified once a request with the given
     /// ID is received.
-    pub async fn add(&self, id: u64) -> oneshot::Receiver<(Status, serde_json::Value)> {
+    pub async fn add(&self, id: u64) -> oneshot::Receiver<T> {
         let (tx, rx) = oneshot::channel();
         self.0.lock().await.insert(id, tx);
         rx
@@ -35,25 +53,24 @@ impl ReceiverList {
     }
 
     /// Notify a waiting receiver with the response to a request.
-    pub async fn notify(&self, response: RequestResponse) -> Result<(), InnerError> {
-        let RequestResponse {
-            r#type: _,
-            id,
-            status,
-            data,
-        } = response;
-
+    pub async fn notify(&self, id: String, response: T) -> Result<(), InnerError> {
         let id = id
             .parse()
             .map_err(|e| InnerError::InvalidRequestId(e, id))?;
 
         if let Some(tx) = self.0.lock().await.remove(&id) {
-            tx.send((status, data)).ok();
+            tx.send(response).ok();
         }
 
         Ok(())
     }
 
+    /// Whether no receivers are waiting for a response.
+    #[cfg(all(test, feature = "testing"))]
+    pub async fn is_empty(&self) -> bool {
+        self.0.lock().await.is_empty()
+    }
+
     /// Reset the list, canceling any outstanding receivers.
     pub async fn reset(&self) {
         self.0.lock().await.clear();
@@ -85,6 +102,51 @@ impl ReidentifyReceiverList {
     }
 }
 
+/// Distributes received events to all current listeners.
+#[cfg(feature = "events")]
+pub(super) struct EventSender {
+    /// Listeners with a bounded queue, that lag behind if they don't keep up.
+    broadcast: broadcast::Sender<Event>,
+    /// Listeners with an unbounded queue, that never miss any event.
+    unbounded: std::sync::Mutex<Vec<mpsc::UnboundedSender<Event>>>,
+}
+
+#[cfg(feature = "events")]
+impl EventSender {
+    /// Create a new sender, with the given capacity for the queues of bounded listeners.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            broadcast: broadcast::channel(capacity).0,
+            unbounded: std::sync::Mutex::default(),
+        }
+    }
+
+    /// Add a new listener with a bounded queue.
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.broadcast.subscribe()
+    }
+
+    /// Add a new listener with an unbounded queue.
+    pub fn subscribe_unbounded(&self) -> mpsc::UnboundedReceiver<Event> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.unbounded
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(tx);
+        rx
+    }
+
+    /// Send an event to all listeners, removing any unbounded listeners that went away. Events
+    /// are dropped if nobody listens.
+    pub fn send(&self, event: Event) {
+        self.unbounded
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|tx| tx.send(event.clone()).is_ok());
+        self.broadcast.send(event).ok();
+    }
+}
+
 /// Errors that can occur while performing the initial handshake with obs-websocket.
 #[derive(Debug, thiserror::Error)]
 pub enum HandshakeError {
@@ -96,10 +158,10 @@ pub enum HandshakeError {
     ConnectionClosed(Option<CloseDetails>),
     /// Receiving a message did not succeed.
     #[error("failed reading websocket message")]
-    Receive(#[source] tokio_tungstenite::tungstenite::Error),
+    Receive(#[source] Box<tokio_tungstenite::tungstenite::Error>),
     /// The web-socket message was not convertible to text.
     #[error("websocket message not convertible to text")]
-    IntoText(#[source] tokio_tungstenite::tungstenite::Error),
+    IntoText(#[source] Box<tokio_tungstenite::tungstenite::Error>),
     /// A message from obs-websocket could not be deserialized.
     #[error("failed deserializing message")]
     DeserializeMessage(#[source] serde_json::Error),
@@ -108,13 +170,24 @@ pub enum HandshakeError {
     SerializeMessage(#[source] serde_json::Error),
     /// Sending a message to obs-websocket failed.
     #[error("failed to send message to obs-websocket")]
-    Send(#[source] tokio_tungstenite::tungstenite::Error),
+    Send(#[source] Box<tokio_tungstenite::tungstenite::Error>),
     /// Didn't receive the initial `Hello` message from obs-websocket after connecting.
     #[error("didn't receive a `Hello` message after connecting")]
     NoHello,
     /// Didn't receive a `Identified` message from obs-websocket after authentication.
     #[error("didn't receive a `Identified` message")]
     NoIdentified,
+    /// The handshake didn't finish within the configured timeout.
+    #[error("the handshake with obs-websocket timed out")]
+    Timeout,
+    /// Requesting the version information right after identifying failed.
+    #[error("failed requesting the version of obs-websocket ({code:?}): {}", .message.as_deref().unwrap_or_default())]
+    Version {
+        /// Status code that describes the kind of error.
+        code: StatusCode,
+        /// Optional message to provide additional details about the error.
+        message: Option<String>,
+    },
 }
 
 /// Description about the reason of why the web-socket connection was closed.
@@ -130,77 +203,261 @@ pub struct CloseDetails {
     pub reason: String,
 }
 
-pub(super) async fn handshake(
-    write: &mut (impl Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin),
-    read: &mut (impl Stream<Item = tokio_tungstenite::tungstenite::Result<Message>> + Unpin),
-    password: Option<&str>,
-    event_subscriptions: Option<EventSubscription>,
-) -> Result<(), HandshakeError> {
-    async fn read_message(
-        read: &mut (impl Stream<Item = tokio_tungstenite::tungstenite::Result<Message>> + Unpin),
-    ) -> Result<ServerMessage, HandshakeError> {
-        let mut message = read
-            .next()
-            .await
-            .ok_or(HandshakeError::ConnectionClosed(None))?
-            .map_err(HandshakeError::Receive)?;
-
-        if let Message::Close(info) = &mut message {
-            return Err(HandshakeError::ConnectionClosed(info.take().map(|i| {
-                CloseDetails {
-                    code: i.code,
-                    reason: i.reason.into_owned(),
-                }
-            })));
-        }
+/// Current state of the connection to obs-websocket.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConnectionState {
+    /// The connection is being (re-)established and the handshake performed.
+    Connecting,
+    /// The client is connected and identified, ready to send requests and receive events.
+    Identified,
+    /// obs-websocket started closing the connection.
+    Closing,
+    /// The connection is closed and won't be re-established anymore.
+    Closed {
+        /// Close code as defined by obs-websocket, if the connection was closed with one.
+        code: Option<WebSocketCloseCode>,
+        /// Textual reason for closing the connection, if any was provided.
+        reason: Option<String>,
+    },
+}
 
-        let message = message.into_text().map_err(HandshakeError::IntoText)?;
+impl ConnectionState {
+    /// Whether the connection is closed for good.
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Self::Closed { .. })
+    }
+}
+
+/// Details about the current session with obs-websocket, as negotiated during the handshake.
+#[derive(Clone, Debug)]
+pub struct Session {
+    /// Version of obs-websocket, as announced by the server when connecting.
+    pub obs_web_socket_version: semver::Version,
+    /// RPC version, that was negotiated with obs-websocket.
+    pub rpc_version: u32,
+    /// Whether obs-websocket required authentication for this connection.
+    pub authentication_required: bool,
+    /// Currently active event subscriptions. These are updated with each
+    /// [`Client::reidentify`](super::Client::reidentify).
+    pub event_subscriptions: EventSubscription,
+    /// Version information of OBS Studio and obs-websocket, that was requested right after
+    /// identifying. It's refreshed whenever the connection is re-established.
+    pub version: Version,
+}
 
-        serde_json::from_str::<ServerMessage>(&message).map_err(HandshakeError::DeserializeMessage)
+/// Details about an obs-websocket instance, as reported by [`Client::probe`](super::Client::probe)
+/// without fully connecting to it.
+#[derive(Clone, Debug)]
+pub struct Probe {
+    /// Version of obs-websocket.
+    pub obs_web_socket_version: semver::Version,
+    /// Latest RPC version, that obs-websocket supports.
+    pub rpc_version: u32,
+    /// Whether obs-websocket requires authentication, meaning a password is needed to connect.
+    pub authentication_required: bool,
+}
+
+impl From<Option<CloseDetails>> for ConnectionState {
+    fn from(details: Option<CloseDetails>) -> Self {
+        match details {
+            Some(details) => Self::Closed {
+                code: WebSocketCloseCode::try_from(u16::from(details.code)).ok(),
+                reason: (!details.reason.is_empty()).then(|| details.reason),
+            },
+            None => Self::Closed {
+                code: None,
+                reason: None,
+            },
+        }
     }
+}
 
-    let server_message = time::timeout(Duration::from_secs(5), read_message(read))
+/// Read the next message from obs-websocket, turning a close frame into an error.
+async fn read_message(
+    read: &mut (impl Stream<Item = tokio_tungstenite::tungstenite::Result<Message>> + Unpin),
+    encoding: Encoding,
+) -> Result<ServerMessage, HandshakeError> {
+    let mut message = read
+        .next()
         .await
-        .map_err(|_| HandshakeError::NoHello)?;
+        .ok_or(HandshakeError::ConnectionClosed(None))?
+        .map_err(|e| HandshakeError::Receive(Box::new(e)))?;
+
+    if let Message::Close(info) = &mut message {
+        return Err(HandshakeError::ConnectionClosed(info.take().map(|i| {
+            CloseDetails {
+                code: i.code,
+                reason: i.reason.into_owned(),
+            }
+        })));
+    }
+
+    encoding.decode(message).map_err(Into::into)
+}
+
+/// Run the future until the optional deadline, returning [`None`] if it elapsed first.
+async fn until<F: Future>(deadline: Option<Instant>, future: F) -> Option<F::Output> {
+    match deadline {
+        Some(deadline) => time::timeout_at(deadline, future).await.ok(),
+        None => Some(future.await),
+    }
+}
 
-    match server_message? {
-        ServerMessage::Hello(Hello {
-            obs_web_socket_version: _,
+/// Wait for the initial `Hello` message, that obs-websocket sends right after connecting.
+async fn read_hello(
+    read: &mut (impl Stream<Item = tokio_tungstenite::tungstenite::Result<Message>> + Unpin),
+    encoding: Encoding,
+    deadline: Option<Instant>,
+) -> Result<Hello, HandshakeError> {
+    match until(deadline, read_message(read, encoding)).await {
+        Some(Ok(ServerMessage::Hello(hello))) => Ok(hello),
+        Some(Err(e)) => Err(e),
+        Some(Ok(_)) | None => Err(HandshakeError::NoHello),
+    }
+}
+
+pub(super) async fn handshake(
+    write: &mut (impl Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin),
+    read: &mut (impl Stream<Item = tokio_tungstenite::tungstenite::Result<Message>> + Unpin),
+    password: Option<&str>,
+    event_subscriptions: Option<EventSubscription>,
+    encoding: Encoding,
+    timeout: Option<Duration>,
+) -> Result<Session, HandshakeError> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let hello = read_hello(read, encoding, deadline).await?;
+
+    until(
+        deadline,
+        identify(write, read, hello, password, event_subscriptions, encoding),
+    )
+    .await
+    .ok_or(HandshakeError::Timeout)?
+}
+
+/// Identify against obs-websocket after receiving the `Hello` message, and request the version
+/// information once identified.
+async fn identify(
+    write: &mut (impl Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin),
+    read: &mut (impl Stream<Item = tokio_tungstenite::tungstenite::Result<Message>> + Unpin),
+    hello: Hello,
+    password: Option<&str>,
+    event_subscriptions: Option<EventSubscription>,
+    encoding: Encoding,
+) -> Result<Session, HandshakeError> {
+    let Hello {
+        obs_web_socket_version,
+        rpc_version,
+        authentication,
+    } = hello;
+
+    let authentication_required = authentication.is_some();
+    let authentication = authentication
+        .zip(password)
+        .map(|(auth, password)| create_auth_response(&auth.challenge, &auth.salt, password));
+
+    let req = encoding
+        .encode(&ClientRequest::Identify(Identify {
             rpc_version,
             authentication,
-        }) => {
-            let authentication = authentication.zip(password).map(|(auth, password)| {
-                create_auth_response(&auth.challenge, &auth.salt, password)
-            });
-
-            let req = serde_json::to_string(&ClientRequest::Identify(Identify {
-                rpc_version,
-                authentication,
-                event_subscriptions,
-            }))
-            .map_err(HandshakeError::SerializeMessage)?;
-
-            write
-                .send(Message::Text(req))
-                .await
-                .map_err(HandshakeError::Send)?;
-        }
-        _ => return Err(HandshakeError::NoHello),
-    }
+            event_subscriptions,
+        }))
+        .map_err(HandshakeError::SerializeMessage)?;
+
+    write
+        .send(req)
+        .await
+        .map_err(|e| HandshakeError::Send(Box::new(e)))?;
 
-    match read_message(read).await? {
+    let rpc_version = match read_message(read, encoding).await? {
         ServerMessage::Identified(Identified {
             negotiated_rpc_version,
         }) => {
             debug!(rpc_version = %negotiated_rpc_version, "identified against obs-websocket");
+            negotiated_rpc_version
         }
         _ => return Err(HandshakeError::NoIdentified),
-    }
+    };
+
+    // The version is requested directly on the socket, as the client isn't running yet. The ID
+    // can't collide with regular requests, as the client's counter starts at 1.
+    let req = encoding
+        .encode(&ClientRequest::Request(Request {
+            request_id: "0",
+            request_type: "GetVersion",
+            request_data: None,
+        }))
+        .map_err(HandshakeError::SerializeMessage)?;
+
+    write
+        .send(req)
+        .await
+        .map_err(|e| HandshakeError::Send(Box::new(e)))?;
+
+    let version = loop {
+        match read_message(read, encoding).await? {
+            ServerMessage::RequestResponse(resp) if resp.id == "0" => {
+                if !resp.status.result {
+                    return Err(HandshakeError::Version {
+                        code: resp.status.code,
+                        message: resp.status.comment,
+                    });
+                }
+
+                break serde_json::from_value::<Version>(resp.data)
+                    .map_err(HandshakeError::DeserializeMessage)?;
+            }
+            // Events may already arrive before the response, but nobody can listen to them yet.
+            _ => continue,
+        }
+    };
+
+    Ok(Session {
+        obs_web_socket_version,
+        rpc_version,
+        authentication_required,
+        event_subscriptions: event_subscriptions.unwrap_or(EventSubscription::ALL),
+        version,
+    })
+}
 
-    Ok(())
+/// Read the `Hello` message of obs-websocket and close the connection again, without identifying.
+pub(super) async fn probe(
+    write: &mut (impl Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin),
+    read: &mut (impl Stream<Item = tokio_tungstenite::tungstenite::Result<Message>> + Unpin),
+    encoding: Encoding,
+    timeout: Option<Duration>,
+) -> Result<Probe, HandshakeError> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let hello = read_hello(read, encoding, deadline).await?;
+
+    write
+        .send(Message::Close(Some(CloseFrame {
+            code: CloseCode::Normal,
+            reason: "".into(),
+        })))
+        .await
+        .map_err(|e| HandshakeError::Send(Box::new(e)))?;
+
+    // Wait for obs-websocket to confirm the close, so the connection is shut down cleanly. Any
+    // failure is irrelevant at this point, as the details are already known.
+    until(deadline, async {
+        while let Some(Ok(message)) = read.next().await {
+            if message.is_close() {
+                break;
+            }
+        }
+    })
+    .await;
+
+    Ok(Probe {
+        obs_web_socket_version: hello.obs_web_socket_version,
+        rpc_version: hello.rpc_version,
+        authentication_required: hello.authentication.is_some(),
+    })
 }
 
-fn create_auth_response(challenge: &str, salt: &str, password: &str) -> String {
+pub(crate) fn create_auth_response(challenge: &str, salt: &str, password: &str) -> String {
     use sha2::{Digest, Sha256};
 
     let mut hasher = Sha256::new();
@@ -0,0 +1,342 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+use url::Url;
+
+use super::ConnectConfig;
+#[cfg(feature = "tls")]
+use super::TlsConfig;
+
+/// Port that obs-websocket listens on by default.
+const DEFAULT_PORT: u16 = 4455;
+
+/// Location of the obs-websocket settings, relative to the OBS configuration directory.
+const OBS_WEBSOCKET_CONFIG: &str = "plugin_config/obs-websocket/config.json";
+
+/// Errors that can occur while determining the connection details for obs-websocket.
+#[derive(Debug, thiserror::Error)]
+pub enum DiscoveryError {
+    /// The URL could not be parsed.
+    #[error("invalid URL")]
+    InvalidUrl(#[source] url::ParseError),
+    /// The URL scheme is not one of `ws` or `wss` (the latter requires the `tls` feature).
+    #[error("unsupported URL scheme `{0}`")]
+    UnsupportedScheme(String),
+    /// The URL doesn't contain a host.
+    #[error("URL doesn't contain a host")]
+    MissingHost,
+    /// The password in the URL is not valid UTF-8 after decoding it.
+    #[error("password in the URL is not valid UTF-8")]
+    InvalidPassword(#[source] std::str::Utf8Error),
+    /// An environment variable contains an invalid port.
+    #[error("environment variable `{name}` contains the invalid port `{value}`")]
+    InvalidPort {
+        /// Name of the environment variable.
+        name: &'static str,
+        /// The invalid content.
+        value: String,
+    },
+    /// None of the locations for the obs-websocket settings contained a configuration file.
+    #[error("obs-websocket configuration not found, searched in {0:?}")]
+    ConfigNotFound(Vec<PathBuf>),
+    /// The configuration file of obs-websocket could not be read.
+    #[error("failed reading obs-websocket configuration from `{path}`")]
+    ReadConfig {
+        /// Location of the configuration file.
+        path: PathBuf,
+        /// Underlying I/O error.
+        #[source]
+        source: io::Error,
+    },
+    /// The configuration file of obs-websocket has an unexpected format.
+    #[error("failed parsing obs-websocket configuration from `{path}`")]
+    ParseConfig {
+        /// Location of the configuration file.
+        path: PathBuf,
+        /// Underlying deserialization error.
+        #[source]
+        source: serde_json::Error,
+    },
+    /// The obs-websocket server is disabled in the OBS settings.
+    #[error("the obs-websocket server is disabled")]
+    ServerDisabled,
+}
+
+/// The settings of obs-websocket, as stored by OBS. Only the fields relevant for connecting are
+/// read.
+#[derive(Deserialize)]
+struct ObsWebsocketConfig {
+    #[serde(default = "default_true")]
+    server_enabled: bool,
+    #[serde(default = "default_port")]
+    server_port: u16,
+    #[serde(default)]
+    auth_required: bool,
+    #[serde(default)]
+    server_password: String,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_port() -> u16 {
+    DEFAULT_PORT
+}
+
+impl ConnectConfig<String, String> {
+    /// Create a configuration from a URL in the form of `ws://:password@host:port`.
+    ///
+    /// The password is optional and the port defaults to `4455`. With the `tls` feature, the
+    /// `wss` scheme enables TLS with the default `TlsConfig`.
+    pub fn from_url(url: &str) -> Result<Self, DiscoveryError> {
+        let url = Url::parse(url).map_err(DiscoveryError::InvalidUrl)?;
+
+        match url.scheme() {
+            "ws" => {}
+            #[cfg(feature = "tls")]
+            "wss" => {}
+            scheme => return Err(DiscoveryError::UnsupportedScheme(scheme.to_owned())),
+        }
+
+        let host = match url.host() {
+            Some(url::Host::Ipv6(addr)) => addr.to_string(),
+            Some(host) => host.to_string(),
+            None => return Err(DiscoveryError::MissingHost),
+        };
+        let password = url
+            .password()
+            .map(|password| {
+                percent_decode_str(password)
+                    .decode_utf8()
+                    .map(|password| password.into_owned())
+                    .map_err(DiscoveryError::InvalidPassword)
+            })
+            .transpose()?;
+
+        #[cfg_attr(not(feature = "tls"), allow(unused_mut))]
+        let mut config = Self::new(host, url.port().unwrap_or(DEFAULT_PORT), password);
+        #[cfg(feature = "tls")]
+        if url.scheme() == "wss" {
+            config.tls = Some(TlsConfig::default());
+        }
+
+        Ok(config)
+    }
+
+    /// Create a configuration from the environment variables `OBS_HOST`, `OBS_PORT` and
+    /// `OBS_PASSWORD`. All of them are optional, with the host defaulting to `localhost` and the
+    /// port to `4455`.
+    pub fn from_env() -> Result<Self, DiscoveryError> {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, DiscoveryError> {
+        let host = var("OBS_HOST").unwrap_or_else(|| "localhost".to_owned());
+        let port = match var("OBS_PORT") {
+            Some(value) => value.parse().map_err(|_| DiscoveryError::InvalidPort {
+                name: "OBS_PORT",
+                value,
+            })?,
+            None => DEFAULT_PORT,
+        };
+
+        Ok(Self::new(host, port, var("OBS_PASSWORD")))
+    }
+
+    /// Create a configuration for the OBS instance running on the local machine, by reading the
+    /// settings of obs-websocket from OBS's configuration directory.
+    ///
+    /// The standard locations for Linux (including the Flatpak version), macOS and Windows are
+    /// searched, and the first existing configuration is used.
+    pub fn from_obs_config() -> Result<Self, DiscoveryError> {
+        let candidates = obs_config_dirs()
+            .into_iter()
+            .map(|dir| dir.join(OBS_WEBSOCKET_CONFIG))
+            .collect::<Vec<_>>();
+
+        match candidates.iter().find(|path| path.is_file()) {
+            Some(path) => Self::from_obs_config_file(path),
+            None => Err(DiscoveryError::ConfigNotFound(candidates)),
+        }
+    }
+
+    /// Create a configuration for the OBS instance running on the local machine, by reading the
+    /// settings of obs-websocket from the given file. This is useful for portable installations
+    /// of OBS, that keep their configuration next to the executable.
+    pub fn from_obs_config_file(path: impl AsRef<Path>) -> Result<Self, DiscoveryError> {
+        let path = path.as_ref();
+        let content = fs::read(path).map_err(|source| DiscoveryError::ReadConfig {
+            path: path.to_owned(),
+            source,
+        })?;
+        let config = serde_json::from_slice::<ObsWebsocketConfig>(&content).map_err(|source| {
+            DiscoveryError::ParseConfig {
+                path: path.to_owned(),
+                source,
+            }
+        })?;
+
+        if !config.server_enabled {
+            return Err(DiscoveryError::ServerDisabled);
+        }
+
+        let password = config.auth_required.then(|| config.server_password);
+
+        Ok(Self::new(
+            "localhost".to_owned(),
+            config.server_port,
+            password,
+        ))
+    }
+}
+
+/// Standard locations of the OBS configuration directory, for the current platform.
+fn obs_config_dirs() -> Vec<PathBuf> {
+    let var = |name| env::var_os(name).map(PathBuf::from);
+    let mut dirs = Vec::new();
+
+    if cfg!(windows) {
+        dirs.extend(var("APPDATA").map(|dir| dir.join("obs-studio")));
+    } else if cfg!(target_os = "macos") {
+        dirs.extend(var("HOME").map(|dir| dir.join("Library/Application Support/obs-studio")));
+    } else {
+        dirs.extend(
+            var("XDG_CONFIG_HOME")
+                .or_else(|| var("HOME").map(|dir| dir.join(".config")))
+                .map(|dir| dir.join("obs-studio")),
+        );
+        dirs.extend(
+            var("HOME").map(|dir| dir.join(".var/app/com.obsproject.Studio/config/obs-studio")),
+        );
+    }
+
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_url() {
+        let config = ConnectConfig::from_url("ws://localhost").unwrap();
+        assert_eq!("localhost", config.host);
+        assert_eq!(4455, config.port);
+        assert_eq!(None, config.password);
+
+        let config = ConnectConfig::from_url("ws://:p%40ss@192.168.0.10:4456").unwrap();
+        assert_eq!("192.168.0.10", config.host);
+        assert_eq!(4456, config.port);
+        assert_eq!(Some("p@ss"), config.password.as_deref());
+
+        let config = ConnectConfig::from_url("ws://[::1]:4455").unwrap();
+        assert_eq!("::1", config.host);
+
+        assert!(matches!(
+            ConnectConfig::from_url("http://localhost"),
+            Err(DiscoveryError::UnsupportedScheme(scheme)) if scheme == "http"
+        ));
+        assert!(matches!(
+            ConnectConfig::from_url("localhost:4455"),
+            Err(DiscoveryError::UnsupportedScheme(_))
+        ));
+        assert!(matches!(
+            ConnectConfig::from_url("not a url"),
+            Err(DiscoveryError::InvalidUrl(_))
+        ));
+    }
+
+    #[cfg(feature = "tls")]
+    #[test]
+    fn from_url_tls() {
+        assert!(ConnectConfig::from_url("ws://localhost")
+            .unwrap()
+            .tls
+            .is_none());
+        assert!(ConnectConfig::from_url("wss://localhost")
+            .unwrap()
+            .tls
+            .is_some());
+    }
+
+    #[test]
+    fn from_vars() {
+        let config = ConnectConfig::from_vars(|_| None).unwrap();
+        assert_eq!("localhost", config.host);
+        assert_eq!(4455, config.port);
+        assert_eq!(None, config.password);
+
+        let config = ConnectConfig::from_vars(|name| match name {
+            "OBS_HOST" => Some("obs.lan".to_owned()),
+            "OBS_PORT" => Some("4456".to_owned()),
+            "OBS_PASSWORD" => Some("secret".to_owned()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!("obs.lan", config.host);
+        assert_eq!(4456, config.port);
+        assert_eq!(Some("secret"), config.password.as_deref());
+
+        assert!(matches!(
+            ConnectConfig::from_vars(|name| (name == "OBS_PORT").then(|| "obs".to_owned())),
+            Err(DiscoveryError::InvalidPort { name: "OBS_PORT", value }) if value == "obs"
+        ));
+    }
+
+    #[test]
+    fn from_obs_config_file() {
+        let path = env::temp_dir().join(format!(
+            "obws-{}-discovery-from-obs-config-file.json",
+            std::process::id()
+        ));
+
+        fs::write(
+            &path,
+            r#"{
+                "alerts_enabled": false,
+                "auth_required": true,
+                "first_load": false,
+                "server_enabled": true,
+                "server_password": "secret",
+                "server_port": 4456
+            }"#,
+        )
+        .unwrap();
+        let config = ConnectConfig::from_obs_config_file(&path).unwrap();
+        assert_eq!("localhost", config.host);
+        assert_eq!(4456, config.port);
+        assert_eq!(Some("secret"), config.password.as_deref());
+
+        fs::write(
+            &path,
+            r#"{"auth_required": false, "server_password": "secret"}"#,
+        )
+        .unwrap();
+        let config = ConnectConfig::from_obs_config_file(&path).unwrap();
+        assert_eq!(4455, config.port);
+        assert_eq!(None, config.password);
+
+        fs::write(&path, r#"{"server_enabled": false}"#).unwrap();
+        assert!(matches!(
+            ConnectConfig::from_obs_config_file(&path),
+            Err(DiscoveryError::ServerDisabled)
+        ));
+
+        fs::write(&path, "\"config\"").unwrap();
+        assert!(matches!(
+            ConnectConfig::from_obs_config_file(&path),
+            Err(DiscoveryError::ParseConfig { .. })
+        ));
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            ConnectConfig::from_obs_config_file(&path),
+            Err(DiscoveryError::ReadConfig { .. })
+        ));
+    }
+}
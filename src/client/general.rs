@@ -2,7 +2,7 @@ use serde::{de::DeserializeOwned, Serialize};
 
 use super::Client;
 use crate::{
-    requests::general::{CallVendorRequest, CallVendorRequestInternal, Request},
+    requests::general::{CallVendorRequest, CallVendorRequestInternal, Request, Sleep},
     responses::general as responses,
     Error, Result,
 };
@@ -56,9 +56,18 @@ impl<'a> General<'a> {
             .send_message(Request::CallVendorRequest(CallVendorRequestInternal {
                 vendor_name: request.vendor_name,
                 request_type: request.request_type,
-                request_data: serde_json::to_value(&request.request_data)
+                request_data: serde_json::to_value(request.request_data)
                     .map_err(Error::SerializeCustomData)?,
             }))
             .await
     }
+
+    /// Sleeps for a time duration or an amount of frames.
+    ///
+    /// **Note:** This request is only allowed within a [`Batch`](super::Batch), where the kind of
+    /// sleep must match the batch's [`ExecutionType`](crate::requests::ExecutionType). Calling it
+    /// outside of a batch fails with [`Error::SleepOutsideBatch`].
+    pub async fn sleep(&self, sleep: Sleep) -> Result<()> {
+        self.client.send_message(Request::Sleep(sleep)).await
+    }
 }
@@ -0,0 +1,325 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use tracing::{debug, debug_span, field, Span};
+
+use crate::{responses::Status, Error, Result};
+
+/// Hook into every request that the client sends, to log, measure or veto them in a central
+/// place. Interceptors are registered through the `interceptors` field of the
+/// [`ConnectConfig`](super::ConnectConfig).
+///
+/// Multiple interceptors are stacked in the order they were registered. [`Self::before_send`] is
+/// called from the first to the last interceptor, and [`Self::after_response`] in reverse order,
+/// so each interceptor wraps the ones that come after it.
+///
+/// Requests within a [`Batch`](super::Batch) are intercepted one by one, but the batch itself is
+/// not.
+pub trait RequestInterceptor: Send + Sync {
+    /// Called right before a request is sent. Returning an error vetoes the request, which then
+    /// fails with [`Error::Vetoed`] and the given reason, without being sent to obs-websocket.
+    ///
+    /// The default implementation lets all requests pass.
+    fn before_send(&self, request: &InterceptedRequest<'_>) -> Result<(), String> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Called once the request is done, either with the response from obs-websocket or the error
+    /// that kept it from arriving.
+    ///
+    /// This is called for every request that passed [`Self::before_send`] of this interceptor,
+    /// even if it was vetoed by a later interceptor.
+    ///
+    /// The default implementation does nothing.
+    fn after_response(
+        &self,
+        request: &InterceptedRequest<'_>,
+        outcome: &ResponseOutcome<'_>,
+        latency: Duration,
+    ) {
+        let _ = (request, outcome, latency);
+    }
+}
+
+/// A request, as seen by a [`RequestInterceptor`].
+#[derive(Clone, Copy, Debug)]
+pub struct InterceptedRequest<'a> {
+    /// Unique ID of the request within the client. Requests within a batch are identified by
+    /// their position by obs-websocket, so the ID is only used locally for them.
+    pub id: u64,
+    /// Name of the request, like `GetVersion`.
+    pub request_type: &'a str,
+    /// Serialized data of the request, if it takes any.
+    pub request_data: Option<&'a serde_json::Value>,
+}
+
+/// The result of a request, as seen by a [`RequestInterceptor`].
+#[derive(Clone, Copy, Debug)]
+pub enum ResponseOutcome<'a> {
+    /// obs-websocket responded to the request, which can still have failed as described by the
+    /// status.
+    Response {
+        /// Status of the request, as reported by obs-websocket.
+        status: &'a Status,
+        /// Raw data of the response. [`serde_json::Value::Null`] if the response doesn't carry
+        /// any data.
+        data: &'a serde_json::Value,
+    },
+    /// No response arrived, for example because the request was vetoed, timed out or the
+    /// connection was lost.
+    Failed(&'a Error),
+}
+
+/// The stack of interceptors of a client.
+#[derive(Clone, Default)]
+pub(super) struct Interceptors(Vec<Arc<dyn RequestInterceptor>>);
+
+impl From<Vec<Arc<dyn RequestInterceptor>>> for Interceptors {
+    fn from(interceptors: Vec<Arc<dyn RequestInterceptor>>) -> Self {
+        Self(interceptors)
+    }
+}
+
+impl Interceptors {
+    /// Run [`RequestInterceptor::before_send`] of all interceptors. If one of them vetoes the
+    /// request, the previous ones are notified about the failure.
+    pub fn before_send(&self, request: &InterceptedRequest<'_>) -> Result<()> {
+        for (index, interceptor) in self.0.iter().enumerate() {
+            if let Err(reason) = interceptor.before_send(request) {
+                let error = Error::Vetoed {
+                    request_type: request.request_type.to_owned(),
+                    reason,
+                };
+
+                for interceptor in self.0[..index].iter().rev() {
+                    interceptor.after_response(
+                        request,
+                        &ResponseOutcome::Failed(&error),
+                        Duration::ZERO,
+                    );
+                }
+
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run [`RequestInterceptor::after_response`] of all interceptors, in reverse order.
+    pub fn after_response(
+        &self,
+        request: &InterceptedRequest<'_>,
+        result: &Result<(Status, serde_json::Value)>,
+        latency: Duration,
+    ) {
+        let outcome = match result {
+            Ok((status, data)) => ResponseOutcome::Response { status, data },
+            Err(error) => ResponseOutcome::Failed(error),
+        };
+
+        for interceptor in self.0.iter().rev() {
+            interceptor.after_response(request, &outcome, latency);
+        }
+    }
+}
+
+/// Interceptor, that creates a `tracing` span named `obs_request` for each request.
+///
+/// The span carries the request `id` and `request_type`, and records the status `code` and the
+/// `latency_ms` once the request is done. It's only entered for the two events that are logged
+/// when the request is sent and when it completes, not while waiting for the response in between.
+#[derive(Debug, Default)]
+pub struct TracingInterceptor {
+    spans: Mutex<HashMap<u64, Span>>,
+}
+
+impl TracingInterceptor {
+    /// Create a new tracing interceptor.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RequestInterceptor for TracingInterceptor {
+    fn before_send(&self, request: &InterceptedRequest<'_>) -> Result<(), String> {
+        let span = debug_span!(
+            "obs_request",
+            id = request.id,
+            request_type = request.request_type,
+            code = field::Empty,
+            latency_ms = field::Empty,
+        );
+        span.in_scope(|| debug!(data = ?request.request_data, "sending request"));
+
+        self.spans
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(request.id, span);
+
+        Ok(())
+    }
+
+    fn after_response(
+        &self,
+        request: &InterceptedRequest<'_>,
+        outcome: &ResponseOutcome<'_>,
+        latency: Duration,
+    ) {
+        let span = self
+            .spans
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&request.id);
+        let span = match span {
+            Some(span) => span,
+            None => return,
+        };
+
+        span.record("latency_ms", latency.as_secs_f64() * 1000.0);
+        span.in_scope(|| match outcome {
+            ResponseOutcome::Response { status, .. } => {
+                span.record("code", field::debug(status.code));
+                debug!(comment = ?status.comment, "received response");
+            }
+            ResponseOutcome::Failed(error) => debug!(%error, "request failed"),
+        });
+    }
+}
+
+/// Interceptor, that collects a simple histogram of the response latencies for each request
+/// type.
+///
+/// Each histogram counts the requests whose latency is below or equal to each of the
+/// [bucket](Self::buckets) bounds, plus one last bucket for all requests that took longer.
+/// Requests that didn't get a response are not counted.
+#[derive(Debug)]
+pub struct LatencyHistogram {
+    buckets: Vec<Duration>,
+    counts: Mutex<HashMap<String, Vec<u64>>>,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::with_buckets(
+            [1, 2, 5, 10, 25, 50, 100, 250, 500, 1000]
+                .into_iter()
+                .map(Duration::from_millis)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl LatencyHistogram {
+    /// Create a new histogram, with bucket bounds from 1 millisecond up to 1 second.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new histogram with custom bucket bounds. The bounds are sorted and duplicates
+    /// removed.
+    pub fn with_buckets(buckets: impl Into<Vec<Duration>>) -> Self {
+        let mut buckets = buckets.into();
+        buckets.sort_unstable();
+        buckets.dedup();
+
+        Self {
+            buckets,
+            counts: Mutex::default(),
+        }
+    }
+
+    /// Upper bounds of the buckets.
+    pub fn buckets(&self) -> &[Duration] {
+        &self.buckets
+    }
+
+    /// Get the current counts for each request type. Each list has one entry more than there are
+    /// [`Self::buckets`], where the last one counts the requests that exceeded the largest bound.
+    pub fn snapshot(&self) -> HashMap<String, Vec<u64>> {
+        self.counts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Reset the counts of all request types.
+    pub fn reset(&self) {
+        self.counts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+impl RequestInterceptor for LatencyHistogram {
+    fn after_response(
+        &self,
+        request: &InterceptedRequest<'_>,
+        outcome: &ResponseOutcome<'_>,
+        latency: Duration,
+    ) {
+        if let ResponseOutcome::Failed(_) = outcome {
+            return;
+        }
+
+        let bucket = self.buckets.partition_point(|bound| *bound < latency);
+        let mut counts = self.counts.lock().unwrap_or_else(PoisonError::into_inner);
+        let counts = counts
+            .entry(request.request_type.to_owned())
+            .or_insert_with(|| vec![0; self.buckets.len() + 1]);
+
+        counts[bucket] += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_buckets() {
+        let histogram = LatencyHistogram::with_buckets(vec![
+            Duration::from_millis(10),
+            Duration::from_millis(1),
+            Duration::from_millis(10),
+        ]);
+        assert_eq!(
+            &[Duration::from_millis(1), Duration::from_millis(10)],
+            histogram.buckets()
+        );
+
+        let request = InterceptedRequest {
+            id: 1,
+            request_type: "GetVersion",
+            request_data: None,
+        };
+        let status = serde_json::from_value::<Status>(serde_json::json!({
+            "result": true,
+            "code": 100,
+        }))
+        .unwrap();
+        let outcome = ResponseOutcome::Response {
+            status: &status,
+            data: &serde_json::Value::Null,
+        };
+
+        for millis in [0, 1, 5, 10, 50] {
+            histogram.after_response(&request, &outcome, Duration::from_millis(millis));
+        }
+        histogram.after_response(
+            &request,
+            &ResponseOutcome::Failed(&Error::Disconnected),
+            Duration::ZERO,
+        );
+
+        assert_eq!(vec![2, 2, 1], histogram.snapshot()["GetVersion"]);
+
+        histogram.reset();
+        assert!(histogram.snapshot().is_empty());
+    }
+}
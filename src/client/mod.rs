@@ -4,61 +4,122 @@
 use std::sync::Weak;
 use std::{
     future::Future,
+    pin::Pin,
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
+    time::Duration,
 };
 
 #[cfg(feature = "events")]
-use futures_util::stream::Stream;
+use futures_util::future;
 use futures_util::{
-    sink::SinkExt,
-    stream::{SplitSink, StreamExt},
+    sink::{Sink, SinkExt},
+    stream::{Stream, StreamExt},
 };
 use semver::{Comparator, Op, Prerelease};
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Serialize};
 #[cfg(feature = "events")]
 use tokio::sync::broadcast;
-use tokio::{net::TcpStream, sync::Mutex, task::JoinHandle};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::{watch, Mutex},
+    task::JoinHandle,
+    time,
+};
 use tokio_tungstenite::{
-    tungstenite::{protocol::CloseFrame, Message},
-    MaybeTlsStream, WebSocketStream,
+    tungstenite::{
+        self,
+        client::IntoClientRequest,
+        http::{header::SEC_WEBSOCKET_PROTOCOL, HeaderValue},
+        protocol::{frame::coding::CloseCode, CloseFrame},
+        Message,
+    },
+    WebSocketStream,
 };
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
-use self::connection::{ReceiverList, ReidentifyReceiverList};
+#[cfg(feature = "events")]
+use self::connection::EventSender;
+#[cfg(feature = "tls")]
+pub use self::tls::{ClientIdentity, TlsConfig, TlsError};
+use self::{
+    batch::BATCH_QUEUE,
+    coalesce::{Acquired, Coalescer},
+    connection::{CloseDetails, ReceiverList, ReidentifyReceiverList},
+    interceptor::Interceptors,
+};
 pub use self::{
-    config::Config, connection::HandshakeError, filters::Filters, general::General,
-    hotkeys::Hotkeys, inputs::Inputs, media_inputs::MediaInputs, outputs::Outputs,
-    profiles::Profiles, recording::Recording, replay_buffer::ReplayBuffer,
-    scene_collections::SceneCollections, scene_items::SceneItems, scenes::Scenes, sources::Sources,
-    streaming::Streaming, transitions::Transitions, ui::Ui, virtual_cam::VirtualCam,
+    batch::{Batch, BatchItem, BatchResults},
+    capabilities::{Capabilities, CategoryCapabilities, RequestCategory},
+    codec::Encoding,
+    config::Config,
+    connection::{ConnectionState, HandshakeError, Probe, Session},
+    discovery::DiscoveryError,
+    filters::Filters,
+    general::General,
+    hotkeys::Hotkeys,
+    inputs::Inputs,
+    interceptor::{
+        InterceptedRequest, LatencyHistogram, RequestInterceptor, ResponseOutcome,
+        TracingInterceptor,
+    },
+    media_inputs::MediaInputs,
+    outputs::Outputs,
+    profiles::Profiles,
+    reconnect::{Backoff, ReconnectPolicy},
+    recording::Recording,
+    replay_buffer::ReplayBuffer,
+    retry::RetryPolicy,
+    scene_collections::SceneCollections,
+    scene_items::SceneItems,
+    scenes::Scenes,
+    sources::Sources,
+    streaming::Streaming,
+    transitions::Transitions,
+    ui::Ui,
+    virtual_cam::VirtualCam,
 };
 #[cfg(feature = "events")]
 use crate::events::Event;
+#[cfg(feature = "testing")]
+use crate::testing::Recorder;
 use crate::{
-    requests::{ClientRequest, EventSubscription, Reidentify, Request, RequestType},
-    responses::ServerMessage,
+    requests::{
+        ClientRequest, EventSubscription, ExecutionType, RawRequest, Reidentify, Request,
+        RequestBatch, RequestType,
+    },
+    responses::{general::Version, RequestResponse, ServerMessage, Status},
     Error, Result,
 };
 
+mod batch;
+mod capabilities;
+mod coalesce;
+pub(crate) mod codec;
 mod config;
-mod connection;
+pub(crate) mod connection;
+mod discovery;
 mod filters;
 mod general;
 mod hotkeys;
 mod inputs;
+mod interceptor;
 mod media_inputs;
 mod outputs;
 mod profiles;
+mod reconnect;
 mod recording;
 mod replay_buffer;
+mod retry;
 mod scene_collections;
 mod scene_items;
 mod scenes;
 mod sources;
 mod streaming;
+#[cfg(feature = "tls")]
+mod tls;
 mod transitions;
 mod ui;
 mod virtual_cam;
@@ -66,7 +127,7 @@ mod virtual_cam;
 #[derive(Debug, thiserror::Error)]
 enum InnerError {
     #[error("websocket message not convertible to text")]
-    IntoText(#[source] tokio_tungstenite::tungstenite::Error),
+    IntoText(#[source] Box<tokio_tungstenite::tungstenite::Error>),
     #[error("failed deserializing message")]
     DeserializeMessage(#[source] serde_json::Error),
     #[error("the request ID `{0}` is not an integer")]
@@ -79,23 +140,51 @@ enum InnerError {
 /// functions to remote control an OBS instance as well as to listen to events caused by the user
 /// by interacting with OBS.
 pub struct Client {
-    /// The writer handle to the web-socket stream.
-    write: Mutex<MessageWriter>,
+    /// The writer handle to the web-socket stream. It is shared with the background task, so it
+    /// can be replaced with a new one after reconnecting.
+    write: Arc<Mutex<MessageWriter>>,
     /// Global counter for requests that help to find out what response belongs to what previously
     /// sent request.
     id_counter: AtomicU64,
+    /// Encoding of the messages exchanged with obs-websocket.
+    encoding: Encoding,
+    /// Default timeout for responses to requests. Can be overwritten for single requests with
+    /// [`Self::with_timeout`].
+    request_timeout: Option<Duration>,
+    /// Interceptors that are called for every request.
+    interceptors: Interceptors,
+    /// Coalescer for high-rate setters, if enabled.
+    coalescer: Option<Arc<Coalescer>>,
+    /// Policy to retry requests after transient failures, if enabled.
+    retry: Option<RetryPolicy>,
     /// A list of currently waiting requests to get a response back. The key is the string version
     /// of a request ID and the value is a oneshot sender that allows to send the response back to
     /// the other end that waits for the response.
     receivers: Arc<ReceiverList>,
+    /// Same as [`Self::receivers`], but for request batches, that receive a list of responses.
+    batch_receivers: Arc<ReceiverList<Vec<RequestResponse>>>,
     /// A list of awaiting [`Self::reidentify`] requests, waiting for confirmation. As
     /// these requests don't carry any kind of ID, they're handled sequentially and must be tracked
     /// separate from normal requests.
     reidentify_receivers: Arc<ReidentifyReceiverList>,
-    /// Broadcast sender that distributes received events to all current listeners. Events are
-    /// dropped if nobody listens.
+    /// The latest event subscriptions, either as given when connecting or as updated by
+    /// [`Self::reidentify`]. These are used again when re-connecting.
+    event_subscriptions: Arc<Mutex<Option<EventSubscription>>>,
+    /// Details about the current session, shared with the background task that refreshes them
+    /// after re-connecting.
+    session: Arc<watch::Sender<Session>>,
+    /// Sender for the current connection state, shared with the background task.
+    state_tx: Arc<watch::Sender<ConnectionState>>,
+    /// Set once [`Self::disconnect`] was called, to tell the background task that the connection
+    /// is closed on purpose and must not be re-established.
+    disconnecting: Arc<AtomicBool>,
+    /// Receiver for the current connection state. Keeping it around ensures that updates to the
+    /// state are never lost, even if nobody else is listening.
+    state: watch::Receiver<ConnectionState>,
+    /// Sender that distributes received events to all current listeners. Events are dropped if
+    /// nobody listens.
     #[cfg(feature = "events")]
-    event_sender: Weak<broadcast::Sender<Event>>,
+    event_sender: Weak<EventSender>,
     /// Handle to the background task that receives messages and distributes them to waiting
     /// receivers and event listeners. It allows to shut down all the machinery once the client is
     /// no longer needed.
@@ -103,13 +192,36 @@ pub struct Client {
 }
 
 /// Shorthand for the writer side of a web-socket stream that has been split into reader and writer.
-type MessageWriter = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
+/// It's boxed, to support any kind of underlying transport.
+pub(crate) type MessageWriter =
+    Pin<Box<dyn Sink<Message, Error = tungstenite::Error> + Send + Sync>>;
+
+/// Shorthand for the reader side of a web-socket stream that has been split into reader and writer.
+/// It's boxed, to support any kind of underlying transport.
+pub(crate) type MessageReader =
+    Pin<Box<dyn Stream<Item = tungstenite::Result<Message>> + Send + Sync>>;
+
+tokio::task_local! {
+    /// Timeout for requests that overwrites the client's default, as set by
+    /// [`Client::with_timeout`].
+    static REQUEST_TIMEOUT: Duration;
+}
+
+/// Default timeout for the handshake with obs-websocket, as set by [`ConnectConfig::new`].
+const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum time to wait for obs-websocket to confirm closing the connection, when disconnecting.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Default broadcast capacity used when not overwritten by the user.
 #[cfg(feature = "events")]
 const DEFAULT_CAPACITY: usize = 100;
 
 /// Configuration for connecting to a obs-websocket instance.
+///
+/// Create it with [`ConnectConfig::new`] and adjust the remaining fields afterwards, as new
+/// settings may be added in the future.
+#[non_exhaustive]
 pub struct ConnectConfig<H, P>
 where
     H: AsRef<str>,
@@ -124,15 +236,69 @@ where
     /// Optional list of event subscriptions, controlling what events to receive. By default all
     /// events are listened to, with the exception of high volume events.
     pub event_subscriptions: Option<EventSubscription>,
-    /// Whether to use TLS when connecting. Only useful when OBS runs on a remote machine.
+    /// Optional TLS settings, to connect through an encrypted connection. Only useful when OBS
+    /// runs on a remote machine, usually behind a reverse proxy that terminates TLS. By default,
+    /// the connection is not encrypted.
     #[cfg(feature = "tls")]
-    pub tls: bool,
+    pub tls: Option<TlsConfig>,
+    /// Encoding of the messages exchanged with obs-websocket. The default is
+    /// [`Encoding::Json`].
+    #[cfg(feature = "msgpack")]
+    pub encoding: Encoding,
     /// Capacity of the broadcast channel for events. The default is `100` which should suffice.
     /// If the consumption of events takes a long time and the broadcast channel fills up faster
     /// than events are consumed, it will start dropping old messages from the queue and these will
-    /// not be send to listeners anymore.
+    /// not be send to listeners anymore. Instead, listeners receive an `Event::Lagged` marker.
+    ///
+    /// Streams from `Client::events_unbounded` are not affected by this limit.
     #[cfg_attr(not(feature = "events"), allow(dead_code))]
     pub broadcast_capacity: Option<usize>,
+    /// Optional policy to automatically reconnect, whenever the connection to obs-websocket is
+    /// lost. By default, the client doesn't reconnect and stays disconnected.
+    ///
+    /// Reconnecting is never attempted if obs-websocket closed the connection because the session
+    /// was invalidated or the authentication failed.
+    pub reconnect: Option<ReconnectPolicy>,
+    /// Maximum time to wait for the response to any request. By default, requests wait forever.
+    ///
+    /// The timeout can be overwritten for single requests with [`Client::with_timeout`]. Requests
+    /// that time out fail with [`Error::Timeout`].
+    pub request_timeout: Option<Duration>,
+    /// Maximum time to establish the connection, including the TLS and web-socket handshakes. By
+    /// default, there is no limit other than the operating system's timeouts.
+    ///
+    /// Connections that time out fail with [`Error::Connect`].
+    pub connect_timeout: Option<Duration>,
+    /// Maximum time for the handshake with obs-websocket, starting once the connection is
+    /// established. [`ConnectConfig::new`] sets it to 5 seconds, and [`None`] waits forever.
+    ///
+    /// Handshakes that time out fail with [`HandshakeError::NoHello`] if obs-websocket didn't
+    /// greet the client, or [`HandshakeError::Timeout`] otherwise.
+    pub handshake_timeout: Option<Duration>,
+    /// Interceptors that are called before each request is sent and after its response arrived,
+    /// to log, measure or veto requests. They're called in the given order before sending, and in
+    /// reverse order after the response. By default, there are none.
+    pub interceptors: Vec<Arc<dyn RequestInterceptor>>,
+    /// Coalesce high-rate setters, like [`Inputs::set_volume`] or
+    /// [`Transitions::set_tbar_position`]. By default, every request is sent.
+    ///
+    /// When enabled, at most one request per setter and target (like the input name or scene
+    /// item) is in flight. Any further calls for the same target wait until it's acknowledged, and
+    /// then only the newest of them is sent. Calls that were superseded by a newer value succeed
+    /// right away, without ever being sent. When sent through [`Client::raw_request`] instead,
+    /// they fail with [`Error::Superseded`].
+    ///
+    /// The coalesced setters are `SetInputVolume`, `SetInputAudioBalance`,
+    /// `SetInputAudioSyncOffset`, `SetTBarPosition` and `SetCurrentSceneTransitionDuration`.
+    /// Requests within a [`Batch`] are never coalesced.
+    pub coalesce_setters: bool,
+    /// Optional policy to retry idempotent requests, after they failed with a transient error. By
+    /// default, requests are never retried.
+    pub retry: Option<RetryPolicy>,
+    /// Optional recorder, that captures all messages exchanged with obs-websocket, to play them
+    /// back in tests later on. By default, nothing is recorded.
+    #[cfg(feature = "testing")]
+    pub recorder: Option<Recorder>,
 }
 
 const OBS_STUDIO_VERSION: Comparator = Comparator {
@@ -156,14 +322,53 @@ where
     H: AsRef<str>,
     P: AsRef<str>,
 {
-    #[cfg(feature = "tls")]
-    fn tls(&self) -> bool {
-        self.tls
+    /// Create a new configuration with the given connection details and the defaults for all other
+    /// settings.
+    pub fn new(host: H, port: u16, password: Option<P>) -> Self {
+        Self {
+            host,
+            port,
+            password,
+            event_subscriptions: None,
+            #[cfg(feature = "tls")]
+            tls: None,
+            #[cfg(feature = "msgpack")]
+            encoding: Encoding::Json,
+            broadcast_capacity: None,
+            reconnect: None,
+            request_timeout: None,
+            connect_timeout: None,
+            handshake_timeout: Some(DEFAULT_HANDSHAKE_TIMEOUT),
+            interceptors: Vec::new(),
+            coalesce_setters: false,
+            retry: None,
+            #[cfg(feature = "testing")]
+            recorder: None,
+        }
     }
 
-    #[cfg(not(feature = "tls"))]
-    fn tls(&self) -> bool {
-        false
+    #[cfg(feature = "msgpack")]
+    fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    #[cfg(not(feature = "msgpack"))]
+    fn encoding(&self) -> Encoding {
+        Encoding::Json
+    }
+
+    /// Start recording the messages of a freshly opened socket, if enabled.
+    #[cfg(feature = "testing")]
+    fn record(&self, socket: (MessageWriter, MessageReader)) -> (MessageWriter, MessageReader) {
+        match &self.recorder {
+            Some(recorder) => recorder.wrap(socket, self.encoding()),
+            None => socket,
+        }
+    }
+
+    #[cfg(not(feature = "testing"))]
+    fn record(&self, socket: (MessageWriter, MessageReader)) -> (MessageWriter, MessageReader) {
+        socket
     }
 }
 
@@ -174,16 +379,7 @@ impl Client {
         port: u16,
         password: Option<impl AsRef<str>>,
     ) -> Result<Self> {
-        Self::connect_with_config(ConnectConfig {
-            host,
-            port,
-            password,
-            event_subscriptions: None,
-            #[cfg(feature = "tls")]
-            tls: false,
-            broadcast_capacity: None,
-        })
-        .await
+        Self::connect_with_config(ConnectConfig::new(host, port, password)).await
     }
 
     /// Connect to a obs-websocket instance with the given configuration.
@@ -192,128 +388,191 @@ impl Client {
         H: AsRef<str>,
         P: AsRef<str>,
     {
-        let (socket, _) = tokio_tungstenite::connect_async(format!(
-            "{}://{}:{}",
-            if config.tls() { "wss" } else { "ws" },
-            config.host.as_ref(),
-            config.port
-        ))
-        .await
-        .map_err(Error::Connect)?;
+        let endpoint = Endpoint::new(&config)?;
+        let password = config.password.as_ref().map(|p| p.as_ref().to_owned());
 
-        let (mut write, mut read) = socket.split();
+        let ((write, read), session) = connect_socket(
+            &endpoint,
+            password.as_deref(),
+            config.event_subscriptions,
+            config.encoding(),
+        )
+        .await?;
 
-        let receivers = Arc::new(ReceiverList::default());
-        let receivers2 = Arc::clone(&receivers);
+        Self::start(write, read, session, config, Some(endpoint)).await
+    }
 
-        let reidentify_receivers = Arc::new(ReidentifyReceiverList::default());
-        let reidentify_receivers2 = Arc::clone(&reidentify_receivers);
+    /// Check whether an obs-websocket instance is reachable on the given host and port, without
+    /// fully connecting to it.
+    ///
+    /// This only waits for the initial greeting of obs-websocket and closes the connection right
+    /// after, so no password is needed. The result tells the version of obs-websocket and whether
+    /// a password would be required to connect.
+    pub async fn probe(host: impl AsRef<str>, port: u16) -> Result<Probe> {
+        Self::probe_with_config(ConnectConfig::new(host, port, None::<&str>)).await
+    }
 
-        #[cfg(feature = "events")]
-        let (event_sender, _) =
-            broadcast::channel(config.broadcast_capacity.unwrap_or(DEFAULT_CAPACITY));
-        #[cfg(feature = "events")]
-        let event_sender = Arc::new(event_sender);
-        #[cfg(feature = "events")]
-        let events_tx = Arc::clone(&event_sender);
+    /// Same as [`Self::probe`], but with a full configuration, for example to use TLS or to
+    /// adjust the timeouts. Only the settings related to the connection and handshake are used.
+    pub async fn probe_with_config<H, P>(config: ConnectConfig<H, P>) -> Result<Probe>
+    where
+        H: AsRef<str>,
+        P: AsRef<str>,
+    {
+        let endpoint = Endpoint::new(&config)?;
+        let (mut write, mut read) = endpoint.open(config.encoding()).await?;
+
+        self::connection::probe(
+            &mut write,
+            &mut read,
+            config.encoding(),
+            config.handshake_timeout,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Connect to a obs-websocket instance over an already established stream, like a tunneled
+    /// connection, a Unix socket or an in-memory [`tokio::io::duplex`] pipe.
+    ///
+    /// The stream must carry the web-socket connection as-is, therefore some parts of the
+    /// configuration work differently than with [`Self::connect_with_config`]:
+    ///
+    /// - The `host` and `port` are only used for the request URL of the web-socket handshake, which
+    ///   usually ends up in the `Host` header.
+    /// - The `tls` settings are ignored. For encrypted connections, wrap the stream in a TLS stream
+    ///   before passing it in.
+    /// - The `reconnect` policy is ignored, as there is no way to re-establish a custom stream.
+    pub async fn connect_with_stream<S, H, P>(
+        stream: S,
+        config: ConnectConfig<H, P>,
+    ) -> Result<Self>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+        H: AsRef<str>,
+        P: AsRef<str>,
+    {
+        let url = websocket_url(false, config.host.as_ref(), config.port);
+        let password = config.password.as_ref().map(|p| p.as_ref().to_owned());
+
+        let (socket, _) = with_connect_timeout(
+            config.connect_timeout,
+            tokio_tungstenite::client_async(client_request(&url, config.encoding())?, stream),
+        )
+        .await?
+        .map_err(|e| Error::Connect(Box::new(e)))?;
+        let (mut write, mut read) = config.record(split_socket(socket));
 
-        self::connection::handshake(
+        let session = self::connection::handshake(
             &mut write,
             &mut read,
-            config.password.as_ref().map(AsRef::as_ref),
+            password.as_deref(),
             config.event_subscriptions,
+            config.encoding(),
+            config.handshake_timeout,
         )
         .await?;
 
-        let handle = tokio::spawn(async move {
-            while let Some(Ok(msg)) = read.next().await {
-                if let Message::Close(info) = &msg {
-                    if let Some(CloseFrame { reason, .. }) = info {
-                        info!(%reason, "connection closed with reason");
-                    }
-
-                    #[cfg(feature = "events")]
-                    events_tx.send(Event::ServerStopping).ok();
-                    continue;
-                }
+        Self::start(write, read, session, config, None).await
+    }
 
-                let res: Result<(), InnerError> = async {
-                    let text = msg.into_text().map_err(InnerError::IntoText)?;
-
-                    let message = serde_json::from_str::<ServerMessage>(&text)
-                        .map_err(InnerError::DeserializeMessage)?;
-
-                    match message {
-                        ServerMessage::RequestResponse(response) => {
-                            trace!(
-                                id = %response.id,
-                                status = ?response.status,
-                                data = %response.data,
-                                "got request-response message",
-                            );
-                            receivers2.notify(response).await?;
-                        }
-                        #[cfg(feature = "events")]
-                        ServerMessage::Event(event) => {
-                            trace!(?event, "got OBS event");
-                            events_tx.send(event).ok();
-                        }
-                        ServerMessage::Identified(identified) => {
-                            trace!(?identified, "got identified message");
-                            reidentify_receivers2.notify(identified).await;
-                        }
-                        _ => return Err(InnerError::UnexpectedMessage(message)),
-                    }
+    /// Start the background task on a freshly connected and identified socket and finish setting
+    /// up the client. A reconnect is only possible if the endpoint of the connection is known.
+    async fn start<H, P>(
+        write: MessageWriter,
+        read: MessageReader,
+        session: Session,
+        config: ConnectConfig<H, P>,
+        endpoint: Option<Endpoint>,
+    ) -> Result<Self>
+    where
+        H: AsRef<str>,
+        P: AsRef<str>,
+    {
+        Self::verify_versions(&session.version)?;
 
-                    Ok(())
-                }
-                .await;
+        let password = config.password.as_ref().map(|p| p.as_ref().to_owned());
+        let encoding = config.encoding();
+        let write = Arc::new(Mutex::new(write));
 
-                if let Err(error) = res {
-                    error!(?error, "failed handling message");
-                }
-            }
+        let receivers = Arc::new(ReceiverList::default());
+        let batch_receivers = Arc::new(ReceiverList::default());
+        let reidentify_receivers = Arc::new(ReidentifyReceiverList::default());
+        let event_subscriptions = Arc::new(Mutex::new(config.event_subscriptions));
+        let (state_tx, state) = watch::channel(ConnectionState::Identified);
+        let state_tx = Arc::new(state_tx);
+        let session = Arc::new(watch::channel(session).0);
+        let disconnecting = Arc::new(AtomicBool::new(false));
 
+        #[cfg(feature = "events")]
+        let event_sender = Arc::new(EventSender::new(
+            config.broadcast_capacity.unwrap_or(DEFAULT_CAPACITY),
+        ));
+
+        let receive_loop = ReceiveLoop {
+            read,
+            write: Arc::clone(&write),
+            receivers: Arc::clone(&receivers),
+            batch_receivers: Arc::clone(&batch_receivers),
+            reidentify_receivers: Arc::clone(&reidentify_receivers),
+            state_tx: Arc::clone(&state_tx),
+            session: Arc::clone(&session),
+            disconnecting: Arc::clone(&disconnecting),
             #[cfg(feature = "events")]
-            events_tx.send(Event::ServerStopped).ok();
+            events_tx: Arc::clone(&event_sender),
+            encoding,
+            reconnect: endpoint
+                .zip(config.reconnect)
+                .map(|(endpoint, policy)| Reconnect {
+                    policy,
+                    endpoint,
+                    password,
+                    event_subscriptions: Arc::clone(&event_subscriptions),
+                    encoding,
+                }),
+        };
 
-            // clear all outstanding receivers to stop them from waiting forever on responses
-            // they'll never receive.
-            receivers2.reset().await;
-            reidentify_receivers2.reset().await;
-        });
+        let handle = tokio::spawn(receive_loop.run());
 
-        let write = Mutex::new(write);
         let id_counter = AtomicU64::new(1);
 
         let client = Self {
             write,
             id_counter,
+            request_timeout: config.request_timeout,
+            interceptors: config.interceptors.into(),
+            coalescer: config
+                .coalesce_setters
+                .then(|| Arc::new(Coalescer::default())),
+            retry: config.retry,
+            encoding,
             receivers,
+            batch_receivers,
             reidentify_receivers,
+            event_subscriptions,
+            session,
+            state_tx,
+            disconnecting,
+            state,
             #[cfg(feature = "events")]
             event_sender: Arc::downgrade(&event_sender),
             handle: Some(handle),
         };
 
-        client.verify_versions().await?;
-
         Ok(client)
     }
 
-    async fn verify_versions(&self) -> Result<()> {
-        let version = self.general().version().await?;
-
+    fn verify_versions(version: &Version) -> Result<()> {
         if !OBS_STUDIO_VERSION.matches(&version.obs_version) {
             return Err(Error::ObsStudioVersion(
-                version.obs_version,
+                version.obs_version.clone(),
                 OBS_STUDIO_VERSION,
             ));
         }
 
         if !OBS_WEBSOCKET_VERSION.matches(&version.obs_web_socket_version) {
             return Err(Error::ObsWebsocketVersion(
-                version.obs_web_socket_version,
+                version.obs_web_socket_version.clone(),
                 OBS_WEBSOCKET_VERSION,
             ));
         }
@@ -333,94 +592,459 @@ impl Client {
         R: Into<RequestType<'a>>,
         T: DeserializeOwned,
     {
-        async fn send<'a>(
-            id_counter: &AtomicU64,
-            receivers: &Arc<ReceiverList>,
-            write: &Mutex<MessageWriter>,
-            req: RequestType<'a>,
-        ) -> Result<serde_json::Value> {
-            let id = id_counter.fetch_add(1, Ordering::SeqCst);
-            let id_str = id.to_string();
-            let req = ClientRequest::Request(Request {
-                request_id: &id_str,
-                ty: req,
-            });
-            let json = serde_json::to_string(&req).map_err(Error::SerializeMessage)?;
+        let req = RawRequest::new(req.into()).map_err(Error::SerializeMessage)?;
+        let resp = match self.send_raw(req).await {
+            // Coalesced setters don't return any data, so a superseded call simply succeeds.
+            Err(Error::Superseded { request_type }) => {
+                return serde_json::from_value(serde_json::Value::Null)
+                    .map_err(|_| Error::Superseded { request_type });
+            }
+            resp => resp?,
+        };
+        serde_json::from_value(resp).map_err(Error::DeserializeResponse)
+    }
 
-            let rx = receivers.add(id).await;
+    async fn send_raw(&self, req: RawRequest) -> Result<serde_json::Value> {
+        if req.request_type == "Sleep" && BATCH_QUEUE.try_with(|_| ()).is_err() {
+            return Err(Error::SleepOutsideBatch);
+        }
 
-            trace!(%json, "sending message");
-            let write_result = write
-                .lock()
-                .await
-                .send(Message::Text(json))
-                .await
-                .map_err(Error::Send);
+        if !self.supports(&req.request_type) {
+            return Err(Error::Unsupported {
+                request_type: req.request_type,
+            });
+        }
 
-            if let Err(e) = write_result {
-                receivers.remove(id).await;
-                return Err(e);
-            }
+        // Coalesced setters wait for the previous request to the same target, and are skipped if a
+        // newer value comes in meanwhile. Batches must not be delayed, so they're left out.
+        let coalescer = self
+            .coalescer
+            .as_ref()
+            .filter(|_| BATCH_QUEUE.try_with(|_| ()).is_err());
+        let _turn = match coalescer.and_then(|coalescer| coalescer.acquire(&req)) {
+            Some(Acquired::Turn(turn)) => Some(turn),
+            Some(Acquired::Wait(rx)) => match rx.await {
+                Ok(turn) => Some(turn),
+                Err(_) => {
+                    trace!(request_type = %req.request_type, "request superseded by newer value");
+                    return Err(Error::Superseded {
+                        request_type: req.request_type,
+                    });
+                }
+            },
+            None => None,
+        };
 
-            let (status, resp) = rx.await.map_err(Error::ReceiveMessage)?;
-            if !status.result {
-                return Err(Error::Api {
-                    code: status.code,
-                    message: status.comment,
-                });
+        // Retrying must not delay the collection of requests within a batch either.
+        let policy = self
+            .retry
+            .as_ref()
+            .filter(|_| BATCH_QUEUE.try_with(|_| ()).is_err());
+        let max_attempts = policy.map_or(1, |policy| policy.max_attempts(&req.request_type));
+        let mut attempt = 1;
+
+        loop {
+            match self.send_attempt(&req).await {
+                Err(error) if attempt < max_attempts && error.is_retryable() => {
+                    let delay = policy
+                        .map(|policy| policy.backoff.delay(attempt - 1))
+                        .unwrap_or_default();
+                    debug!(
+                        request_type = %req.request_type,
+                        attempt,
+                        ?delay,
+                        %error,
+                        "retrying failed request",
+                    );
+
+                    time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
             }
+        }
+    }
 
-            Ok(resp)
+    /// Make a single attempt to send a request, either directly or as part of a batch.
+    async fn send_attempt(&self, req: &RawRequest) -> Result<serde_json::Value> {
+        let id = self.id_counter.fetch_add(1, Ordering::SeqCst);
+        let request = InterceptedRequest {
+            id,
+            request_type: &req.request_type,
+            request_data: req.request_data.as_ref(),
+        };
+        self.interceptors.before_send(&request)?;
+        let start = std::time::Instant::now();
+
+        // Requests made within a batch are collected instead of being sent directly. This must
+        // happen before anything else is awaited, as the batch only collects requests during
+        // the first poll.
+        let result = match BATCH_QUEUE.try_with(Arc::clone) {
+            Ok(queue) => match queue.push(req.clone()) {
+                Ok(rx) => rx.await.map_err(|_| Error::BatchHalted),
+                Err(e) => Err(e),
+            },
+            Err(_) => self.send_request(id, req).await,
+        };
+
+        self.interceptors
+            .after_response(&request, &result, start.elapsed());
+        let (status, resp) = result?;
+
+        if !status.result {
+            return Err(Error::Api {
+                code: status.code,
+                message: status.comment,
+            });
         }
 
-        let resp = send(&self.id_counter, &self.receivers, &self.write, req.into()).await?;
-        serde_json::from_value(resp).map_err(Error::DeserializeResponse)
+        Ok(resp)
+    }
+
+    /// Send a single request directly, outside of any batch.
+    async fn send_request(&self, id: u64, req: &RawRequest) -> Result<(Status, serde_json::Value)> {
+        let id_str = id.to_string();
+        let message = self
+            .encoding
+            .encode(&ClientRequest::Request(Request {
+                request_id: &id_str,
+                request_type: &req.request_type,
+                request_data: req.request_data.as_ref(),
+            }))
+            .map_err(Error::SerializeMessage)?;
+
+        self.send_and_receive(&self.receivers, id, message, &req.request_type)
+            .await
+    }
+
+    async fn send_batch(
+        &self,
+        requests: &[RawRequest],
+        halt_on_failure: Option<bool>,
+        execution_type: Option<ExecutionType>,
+    ) -> Result<Vec<RequestResponse>> {
+        let id = self.id_counter.fetch_add(1, Ordering::SeqCst);
+        let id_str = id.to_string();
+        let request_ids = (0..requests.len())
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>();
+        let requests = requests
+            .iter()
+            .zip(&request_ids)
+            .map(|(req, request_id)| Request {
+                request_id,
+                request_type: &req.request_type,
+                request_data: req.request_data.as_ref(),
+            })
+            .collect::<Vec<_>>();
+
+        let message = self
+            .encoding
+            .encode(&ClientRequest::RequestBatch(RequestBatch {
+                request_id: &id_str,
+                halt_on_failure,
+                requests: &requests,
+                execution_type,
+            }))
+            .map_err(Error::SerializeMessage)?;
+
+        self.send_and_receive(&self.batch_receivers, id, message, "RequestBatch")
+            .await
+    }
+
+    /// Send a message to obs-websocket and wait for the response with the given ID to arrive in
+    /// the receiver list.
+    async fn send_and_receive<T>(
+        &self,
+        receivers: &ReceiverList<T>,
+        id: u64,
+        message: Message,
+        request_type: &str,
+    ) -> Result<T> {
+        let rx = receivers.add(id).await;
+
+        trace!(?message, "sending message");
+        let write_result = self
+            .write
+            .lock()
+            .await
+            .send(message)
+            .await
+            .map_err(|e| Error::Send(Box::new(e)));
+
+        if let Err(e) = write_result {
+            receivers.remove(id).await;
+            return Err(e);
+        }
+
+        let timeout = REQUEST_TIMEOUT
+            .try_with(|t| *t)
+            .ok()
+            .or(self.request_timeout);
+        let resp = match timeout {
+            Some(timeout) => match time::timeout(timeout, rx).await {
+                Ok(resp) => resp,
+                Err(_) => {
+                    // late responses are simply ignored, once the receiver is gone.
+                    receivers.remove(id).await;
+                    return Err(Error::Timeout {
+                        request_type: request_type.to_owned(),
+                    });
+                }
+            },
+            None => rx.await,
+        };
+
+        resp.map_err(|_| Error::Disconnected)
+    }
+
+    /// Run the given future, applying a different timeout to all requests made within it, than
+    /// the default timeout configured in [`ConnectConfig::request_timeout`].
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # async fn run(client: obws::Client) -> obws::Result<()> {
+    /// let scenes = client
+    ///     .with_timeout(Duration::from_secs(1), client.scenes().list())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_timeout<F>(&self, timeout: Duration, future: F) -> F::Output
+    where
+        F: Future,
+    {
+        REQUEST_TIMEOUT.scope(timeout, future).await
     }
 
     /// Disconnect from obs-websocket and shut down all machinery.
     ///
+    /// The connection is closed gracefully, by sending a close frame to obs-websocket and waiting
+    /// for its confirmation for up to 5 seconds. Any responses that arrive in the meantime are
+    /// still delivered, and requests that are left without response fail with
+    /// [`Error::Disconnected`].
+    ///
     /// This is called automatically when dropping the client but doesn't wait for all background
     /// tasks to complete. Therefore, it is recommended to call this manually once the client is
     /// no longer needed.
     pub fn disconnect(&mut self) -> impl Future {
-        let handle = self.handle.take().map(|h| {
-            h.abort();
-            h
+        let shutdown = self.handle.take().and_then(|mut handle| {
+            // Without a runtime, like when dropping the client after the runtime shut down, the
+            // connection can only be cut off.
+            let runtime = match tokio::runtime::Handle::try_current() {
+                Ok(runtime) => runtime,
+                Err(_) => {
+                    handle.abort();
+                    self.state_tx.send(None.into()).ok();
+                    return None;
+                }
+            };
+
+            let write = Arc::clone(&self.write);
+            let state_tx = Arc::clone(&self.state_tx);
+            self.disconnecting.store(true, Ordering::SeqCst);
+
+            let close = async move {
+                let close = Message::Close(Some(CloseFrame {
+                    code: CloseCode::Normal,
+                    reason: "".into(),
+                }));
+
+                match write.lock().await.send(close).await {
+                    Ok(()) => {
+                        if time::timeout(DISCONNECT_TIMEOUT, &mut handle)
+                            .await
+                            .is_err()
+                        {
+                            warn!("obs-websocket didn't confirm closing the connection in time");
+                            handle.abort();
+                        }
+                    }
+                    Err(error) => {
+                        debug!(?error, "failed sending close frame to obs-websocket");
+                        handle.abort();
+                    }
+                }
+
+                handle.await.ok();
+
+                if !state_tx.borrow().is_closed() {
+                    state_tx.send(None.into()).ok();
+                }
+            };
+
+            Some(runtime.spawn(close))
         });
 
         async {
-            if let Some(h) = handle {
-                h.await.ok();
+            if let Some(shutdown) = shutdown {
+                shutdown.await.ok();
             }
         }
     }
 
+    /// Get the details about the current session with obs-websocket, like the negotiated RPC
+    /// version, active event subscriptions and the version information of OBS Studio.
+    ///
+    /// The version information is requested once while connecting and cached, so this doesn't
+    /// send any request.
+    pub fn session(&self) -> Session {
+        self.session.borrow().clone()
+    }
+
+    /// Check whether the connected obs-websocket instance supports the given request type, like
+    /// `GetSceneList`.
+    ///
+    /// This uses the list of available requests, that was received while connecting. Requests
+    /// that aren't supported fail with [`Error::Unsupported`] right away, without being sent. If
+    /// obs-websocket didn't report any available requests, all requests are assumed to be
+    /// supported.
+    pub fn supports(&self, request_type: &str) -> bool {
+        let session = self.session.borrow();
+        let available = &session.version.available_requests;
+
+        available.is_empty() || available.iter().any(|r| r == request_type)
+    }
+
+    /// Get a report about which of the requests covered by this crate are supported by the
+    /// connected obs-websocket instance, grouped by category, as well as the supported requests
+    /// that aren't covered yet.
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&self.session.borrow().version.available_requests)
+    }
+
+    /// Get a receiver for the current state of the connection to obs-websocket. It can be used to
+    /// check the state at any time, or to wait for changes of it.
+    pub fn state(&self) -> watch::Receiver<ConnectionState> {
+        self.state.clone()
+    }
+
+    /// Wait until the connection to obs-websocket is closed for good, returning the final
+    /// [`ConnectionState::Closed`] state with details about why the connection was closed.
+    ///
+    /// If automatic reconnects are enabled, this only resolves once all attempts to reconnect
+    /// failed or obs-websocket forbids to reconnect.
+    pub fn closed(&self) -> impl Future<Output = ConnectionState> + 'static {
+        let mut state = self.state.clone();
+
+        async move {
+            loop {
+                if state.borrow().is_closed() || state.changed().await.is_err() {
+                    break state.borrow().clone();
+                }
+            }
+        }
+    }
+
+    /// Create a new request batch, that allows to send many requests at once, as a single
+    /// message to obs-websocket.
+    ///
+    /// As the batch keeps the futures of the added requests around until it is sent, the API
+    /// categories (like [`Self::scene_items`]) must be bound to a variable first.
+    ///
+    /// ```no_run
+    /// # async fn run(client: obws::Client) -> obws::Result<()> {
+    /// use obws::requests::{inputs::Volume, scene_items::SetEnabled};
+    ///
+    /// let scene_items = client.scene_items();
+    /// let inputs = client.inputs();
+    ///
+    /// let mut batch = client.batch().halt_on_failure(true);
+    /// let enabled = batch.add(scene_items.set_enabled(SetEnabled {
+    ///     scene: "Scene",
+    ///     item_id: 1,
+    ///     enabled: true,
+    /// }));
+    /// let volume = batch.add(inputs.set_volume("Mic", Volume::Db(-6.0)));
+    ///
+    /// let mut results = batch.send().await?;
+    /// results.take(enabled)?;
+    /// results.take(volume)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn batch(&self) -> Batch<'_> {
+        Batch::new(self)
+    }
+
+    /// Send any request to obs-websocket, including ones that aren't covered by this crate yet,
+    /// and get back the raw response data.
+    ///
+    /// The data must serialize into a JSON object, or into nothing (like `()`) if the request
+    /// doesn't take any data. The request is handled like all others, so it fails with
+    /// [`Error::Api`] if obs-websocket reports an error, and can be added to a [`Batch`] as well.
+    /// Likewise, `Sleep` requests are only allowed within a batch.
+    ///
+    /// ```no_run
+    /// # async fn run(client: obws::Client) -> obws::Result<()> {
+    /// let response = client
+    ///     .raw_request(
+    ///         "GetInputVolume",
+    ///         serde_json::json!({ "inputName": "Mic" }),
+    ///     )
+    ///     .await?;
+    /// println!("{}", response["inputVolumeDb"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn raw_request(
+        &self,
+        request_type: &str,
+        data: impl Serialize,
+    ) -> Result<serde_json::Value> {
+        let data = serde_json::to_value(data).map_err(Error::SerializeCustomData)?;
+        if !data.is_object() && !data.is_null() {
+            return Err(Error::InvalidCustomData);
+        }
+
+        self.send_raw(RawRequest {
+            request_type: request_type.to_owned(),
+            request_data: (!data.is_null()).then(|| data),
+        })
+        .await
+    }
+
+    /// Same as [`Self::raw_request`], but deserializes the response data into the given type.
+    pub async fn raw_request_as<T>(&self, request_type: &str, data: impl Serialize) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let response = self.raw_request(request_type, data).await?;
+        serde_json::from_value(response).map_err(Error::DeserializeResponse)
+    }
+
     /// Adjust settings of the currently active connection by re-identifying against
     /// `obs-websocket`.
     ///
     /// This currently allows to change the events to listen for, without the need of a full
     /// disconnect and new connection.
     pub async fn reidentify(&self, event_subscriptions: EventSubscription) -> Result<()> {
-        let json = serde_json::to_string(&ClientRequest::Reidentify(Reidentify {
-            event_subscriptions: Some(event_subscriptions),
-        }))
-        .map_err(Error::SerializeMessage)?;
+        let message = self
+            .encoding
+            .encode(&ClientRequest::Reidentify(Reidentify {
+                event_subscriptions: Some(event_subscriptions),
+            }))
+            .map_err(Error::SerializeMessage)?;
 
         let rx = self.reidentify_receivers.add().await;
 
         self.write
             .lock()
             .await
-            .send(Message::Text(json))
+            .send(message)
             .await
-            .map_err(Error::Send)?;
+            .map_err(|e| Error::Send(Box::new(e)))?;
 
-        let resp = rx.await.map_err(Error::ReceiveMessage)?;
+        let resp = rx.await.map_err(|_| Error::Disconnected)?;
         debug!(
             rpc_version = %resp.negotiated_rpc_version,
             "re-identified against obs-websocket",
         );
 
+        *self.event_subscriptions.lock().await = Some(event_subscriptions);
+        self.session
+            .send_modify(|session| session.event_subscriptions = event_subscriptions);
+
         Ok(())
     }
 
@@ -435,13 +1059,77 @@ impl Client {
     /// Getting a new stream of events fails with [`Error::Disconnected`] if the client is
     /// disconnected from obs-websocket. That can happen either by manually disconnecting, stopping
     /// obs-websocket or closing OBS.
+    ///
+    /// # Lagging
+    ///
+    /// Each stream buffers up to [`ConnectConfig::broadcast_capacity`] events. If it isn't
+    /// consumed fast enough, the oldest events are dropped and an [`Event::Lagged`] with the
+    /// amount of missed events is emitted instead, before the stream continues. Use
+    /// [`Self::events_unbounded`] if no event must ever be missed.
     #[cfg(feature = "events")]
     pub fn events(&self) -> Result<impl Stream<Item = Event>> {
         if let Some(sender) = &self.event_sender.upgrade() {
             let mut receiver = sender.subscribe();
 
             Ok(async_stream::stream! {
-                while let Ok(event) = receiver.recv().await {
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) => yield event,
+                        Err(broadcast::error::RecvError::Lagged(count)) => {
+                            warn!(count, "event stream lagged behind");
+                            yield Event::Lagged(count);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            })
+        } else {
+            Err(crate::Error::Disconnected)
+        }
+    }
+
+    /// Get a stream of events, like [`Self::events`], but only with the events of the given
+    /// categories, as determined by [`Event::category`]. Events without a category, like
+    /// [`Event::ServerStopped`] or [`Event::Lagged`], are always passed through.
+    ///
+    /// The filter only applies to this stream. To receive events of a category at all, the client
+    /// must be subscribed to it, either when connecting or with [`Self::reidentify`].
+    ///
+    /// # Errors
+    ///
+    /// Getting a new stream of events fails with [`Error::Disconnected`] if the client is
+    /// disconnected from obs-websocket.
+    #[cfg(feature = "events")]
+    pub fn events_filtered(
+        &self,
+        categories: EventSubscription,
+    ) -> Result<impl Stream<Item = Event>> {
+        self.events().map(|events| {
+            events.filter(move |event| {
+                let category = event.category();
+                future::ready(category.is_empty() || categories.intersects(category))
+            })
+        })
+    }
+
+    /// Get a stream of events, like [`Self::events`], but without a limit on the amount of
+    /// buffered events. It never drops any events, for consumers that must not miss a single one,
+    /// like [`Event::RecordStateChanged`].
+    ///
+    /// **Note**: The buffer grows without bounds, if the stream isn't consumed fast enough. It's
+    /// important to keep consuming the stream, or drop it once it's no longer needed.
+    ///
+    /// # Errors
+    ///
+    /// Getting a new stream of events fails with [`Error::Disconnected`] if the client is
+    /// disconnected from obs-websocket.
+    #[cfg(feature = "events")]
+    pub fn events_unbounded(&self) -> Result<impl Stream<Item = Event>> {
+        if let Some(sender) = &self.event_sender.upgrade() {
+            let mut receiver = sender.subscribe_unbounded();
+
+            Ok(async_stream::stream! {
+                while let Some(event) = receiver.recv().await {
                     yield event;
                 }
             })
@@ -541,10 +1229,601 @@ impl Client {
     }
 }
 
+/// Build the web-socket URL for the given host and port.
+fn websocket_url(tls: bool, host: &str, port: u16) -> String {
+    let scheme = if tls { "wss" } else { "ws" };
+
+    // IPv6 addresses must be enclosed in brackets, to separate them from the port.
+    if host.contains(':') {
+        format!("{}://[{}]:{}", scheme, host, port)
+    } else {
+        format!("{}://{}:{}", scheme, host, port)
+    }
+}
+
+/// Location of an obs-websocket instance, and how to connect to it.
+struct Endpoint {
+    host: String,
+    port: u16,
+    #[cfg(feature = "tls")]
+    tls: Option<self::tls::TlsConnection>,
+    connect_timeout: Option<Duration>,
+    handshake_timeout: Option<Duration>,
+    #[cfg(feature = "testing")]
+    recorder: Option<Recorder>,
+}
+
+impl Endpoint {
+    fn new<H, P>(config: &ConnectConfig<H, P>) -> Result<Self>
+    where
+        H: AsRef<str>,
+        P: AsRef<str>,
+    {
+        Ok(Self {
+            host: config.host.as_ref().to_owned(),
+            port: config.port,
+            #[cfg(feature = "tls")]
+            tls: config
+                .tls
+                .as_ref()
+                .map(|tls| tls.build(config.host.as_ref()))
+                .transpose()?,
+            connect_timeout: config.connect_timeout,
+            handshake_timeout: config.handshake_timeout,
+            #[cfg(feature = "testing")]
+            recorder: config.recorder.clone(),
+        })
+    }
+
+    #[cfg(feature = "tls")]
+    fn tls(&self) -> bool {
+        self.tls.is_some()
+    }
+
+    #[cfg(not(feature = "tls"))]
+    fn tls(&self) -> bool {
+        false
+    }
+
+    fn url(&self) -> String {
+        websocket_url(self.tls(), &self.host, self.port)
+    }
+
+    /// Open the web-socket connection, without performing the obs-websocket handshake yet.
+    async fn open(&self, encoding: Encoding) -> Result<(MessageWriter, MessageReader)> {
+        with_connect_timeout(self.connect_timeout, self.connect(encoding)).await?
+    }
+
+    async fn connect(&self, encoding: Encoding) -> Result<(MessageWriter, MessageReader)> {
+        let request = client_request(&self.url(), encoding)?;
+
+        #[cfg(feature = "tls")]
+        if let Some(tls) = &self.tls {
+            let stream = tls
+                .connect(&self.host, self.port)
+                .await
+                .map_err(|e| Error::Connect(Box::new(tungstenite::Error::Io(e))))?;
+            let (socket, _) = tokio_tungstenite::client_async(request, stream)
+                .await
+                .map_err(|e| Error::Connect(Box::new(e)))?;
+
+            return Ok(self.record(split_socket(socket), encoding));
+        }
+
+        let (socket, _) = tokio_tungstenite::connect_async(request)
+            .await
+            .map_err(|e| Error::Connect(Box::new(e)))?;
+
+        Ok(self.record(split_socket(socket), encoding))
+    }
+
+    /// Start recording the messages of a freshly opened socket, if enabled.
+    #[cfg(feature = "testing")]
+    fn record(
+        &self,
+        socket: (MessageWriter, MessageReader),
+        encoding: Encoding,
+    ) -> (MessageWriter, MessageReader) {
+        match &self.recorder {
+            Some(recorder) => recorder.wrap(socket, encoding),
+            None => socket,
+        }
+    }
+
+    #[cfg(not(feature = "testing"))]
+    fn record(
+        &self,
+        socket: (MessageWriter, MessageReader),
+        _: Encoding,
+    ) -> (MessageWriter, MessageReader) {
+        socket
+    }
+}
+
+/// Connect to the web-socket at the given endpoint and perform the handshake with obs-websocket.
+async fn connect_socket(
+    endpoint: &Endpoint,
+    password: Option<&str>,
+    event_subscriptions: Option<EventSubscription>,
+    encoding: Encoding,
+) -> Result<((MessageWriter, MessageReader), Session)> {
+    let (mut write, mut read) = endpoint.open(encoding).await?;
+
+    let session = self::connection::handshake(
+        &mut write,
+        &mut read,
+        password,
+        event_subscriptions,
+        encoding,
+        endpoint.handshake_timeout,
+    )
+    .await?;
+
+    Ok(((write, read), session))
+}
+
+/// Run the future that establishes a connection, failing with a [`std::io::ErrorKind::TimedOut`]
+/// error if it doesn't finish within the optional timeout.
+async fn with_connect_timeout<F: Future>(
+    timeout: Option<Duration>,
+    future: F,
+) -> Result<F::Output> {
+    match timeout {
+        Some(timeout) => time::timeout(timeout, future).await.map_err(|_| {
+            Error::Connect(Box::new(tungstenite::Error::Io(
+                std::io::ErrorKind::TimedOut.into(),
+            )))
+        }),
+        None => Ok(future.await),
+    }
+}
+
+/// Create the request for the web-socket handshake, that negotiates the sub-protocol for the
+/// given encoding.
+fn client_request(
+    url: &str,
+    encoding: Encoding,
+) -> Result<tungstenite::handshake::client::Request> {
+    let mut request = url
+        .into_client_request()
+        .map_err(|e| Error::Connect(Box::new(e)))?;
+    request.headers_mut().insert(
+        SEC_WEBSOCKET_PROTOCOL,
+        HeaderValue::from_static(encoding.protocol()),
+    );
+
+    Ok(request)
+}
+
+/// Split a web-socket into its writer and reader halves, erasing the type of the underlying
+/// transport.
+fn split_socket<S>(socket: WebSocketStream<S>) -> (MessageWriter, MessageReader)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (write, read) = socket.split();
+    (Box::pin(write), Box::pin(read))
+}
+
+/// Background task that receives messages from obs-websocket and distributes them to waiting
+/// receivers and event listeners.
+struct ReceiveLoop {
+    read: MessageReader,
+    write: Arc<Mutex<MessageWriter>>,
+    receivers: Arc<ReceiverList>,
+    batch_receivers: Arc<ReceiverList<Vec<RequestResponse>>>,
+    reidentify_receivers: Arc<ReidentifyReceiverList>,
+    state_tx: Arc<watch::Sender<ConnectionState>>,
+    session: Arc<watch::Sender<Session>>,
+    disconnecting: Arc<AtomicBool>,
+    #[cfg(feature = "events")]
+    events_tx: Arc<EventSender>,
+    encoding: Encoding,
+    reconnect: Option<Reconnect>,
+}
+
+/// Everything needed to re-establish a lost connection.
+struct Reconnect {
+    policy: ReconnectPolicy,
+    endpoint: Endpoint,
+    password: Option<String>,
+    event_subscriptions: Arc<Mutex<Option<EventSubscription>>>,
+    encoding: Encoding,
+}
+
+impl ReceiveLoop {
+    async fn run(mut self) {
+        loop {
+            let mut close_details = self.receive().await;
+            let disconnecting = self.disconnecting.load(Ordering::SeqCst);
+
+            #[cfg(feature = "events")]
+            if !disconnecting {
+                self.events_tx.send(Event::ServerStopped);
+            }
+
+            // clear all outstanding receivers to stop them from waiting forever on responses
+            // they'll never receive.
+            self.receivers.reset().await;
+            self.batch_receivers.reset().await;
+            self.reidentify_receivers.reset().await;
+
+            let reconnect = match &self.reconnect {
+                Some(reconnect)
+                    if !disconnecting
+                        && self::reconnect::may_reconnect(
+                            close_details.as_ref().map(|d| d.code.into()),
+                        ) =>
+                {
+                    reconnect
+                }
+                _ => {
+                    self.state_tx.send(close_details.into()).ok();
+                    break;
+                }
+            };
+
+            self.state_tx.send(ConnectionState::Connecting).ok();
+
+            match reconnect.run().await {
+                Ok(((write, read), session)) => {
+                    *self.write.lock().await = write;
+                    self.read = read;
+                    self.session.send_replace(session);
+                    self.state_tx.send(ConnectionState::Identified).ok();
+                }
+                Err(refused) => {
+                    if refused.is_some() {
+                        close_details = refused;
+                    }
+                    self.state_tx.send(close_details.into()).ok();
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Handle incoming messages until the connection is closed, returning the details that were
+    /// sent by obs-websocket about why the connection was closed, if any.
+    async fn receive(&mut self) -> Option<CloseDetails> {
+        let mut close_details = None;
+
+        while let Some(Ok(msg)) = self.read.next().await {
+            if let Message::Close(info) = msg {
+                if let Some(CloseFrame { code, reason }) = info {
+                    info!(%code, %reason, "connection closed with reason");
+                    close_details = Some(CloseDetails {
+                        code,
+                        reason: reason.into_owned(),
+                    });
+                }
+
+                self.state_tx.send(ConnectionState::Closing).ok();
+                #[cfg(feature = "events")]
+                if !self.disconnecting.load(Ordering::SeqCst) {
+                    self.events_tx.send(Event::ServerStopping);
+                }
+                continue;
+            }
+
+            if let Err(error) = self.handle(msg).await {
+                error!(?error, "failed handling message");
+            }
+        }
+
+        close_details
+    }
+
+    async fn handle(&self, msg: Message) -> Result<(), InnerError> {
+        let message = self.encoding.decode::<ServerMessage>(msg)?;
+
+        match message {
+            ServerMessage::RequestResponse(response) => {
+                trace!(
+                    id = %response.id,
+                    status = ?response.status,
+                    data = %response.data,
+                    "got request-response message",
+                );
+                self.receivers
+                    .notify(response.id, (response.status, response.data))
+                    .await?;
+            }
+            ServerMessage::RequestBatchResponse(response) => {
+                trace!(
+                    id = %response.id,
+                    results = response.results.len(),
+                    "got request-batch-response message",
+                );
+                self.batch_receivers
+                    .notify(response.id, response.results)
+                    .await?;
+            }
+            #[cfg(feature = "events")]
+            ServerMessage::Event(event) => {
+                trace!(?event, "got OBS event");
+                self.events_tx.send(event);
+            }
+            ServerMessage::Identified(identified) => {
+                trace!(?identified, "got identified message");
+                self.reidentify_receivers.notify(identified).await;
+            }
+            _ => return Err(InnerError::UnexpectedMessage(message)),
+        }
+
+        Ok(())
+    }
+}
+
+impl Reconnect {
+    /// Try to re-establish the connection, according to the reconnect policy. Fails if all
+    /// attempts failed or obs-websocket refused the connection in a way that forbids further
+    /// attempts, in which case the details of the refusal are returned.
+    async fn run(&self) -> Result<((MessageWriter, MessageReader), Session), Option<CloseDetails>> {
+        for attempt in 0.. {
+            if self.policy.max_attempts.map_or(false, |max| attempt >= max) {
+                warn!(
+                    attempts = attempt,
+                    "giving up reconnecting to obs-websocket"
+                );
+                break;
+            }
+
+            let delay = self.policy.backoff.delay(attempt);
+            debug!(attempt, ?delay, "reconnecting to obs-websocket");
+            time::sleep(delay).await;
+
+            let event_subscriptions = *self.event_subscriptions.lock().await;
+
+            match connect_socket(
+                &self.endpoint,
+                self.password.as_deref(),
+                event_subscriptions,
+                self.encoding,
+            )
+            .await
+            {
+                Ok(socket) => {
+                    info!(attempt, "reconnected to obs-websocket");
+                    return Ok(socket);
+                }
+                Err(Error::Handshake(HandshakeError::ConnectionClosed(Some(details))))
+                    if !self::reconnect::may_reconnect(Some(details.code.into())) =>
+                {
+                    warn!(reason = %details.reason, "obs-websocket refused reconnecting");
+                    return Err(Some(details));
+                }
+                Err(error) => {
+                    warn!(?error, attempt, "failed reconnecting to obs-websocket");
+                }
+            }
+        }
+
+        Err(None)
+    }
+}
+
 impl Drop for Client {
     fn drop(&mut self) {
-        // We simply drop the future as the background task has been aborted but we have no way here
-        // to wait for it to fully shut down (except spinning up a new tokio runtime).
+        // We simply drop the future as the connection is closed in a background task but we have
+        // no way here to wait for it to fully shut down (except spinning up a new tokio runtime).
         drop(self.disconnect());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+    use super::*;
+
+    async fn serve(stream: tokio::io::DuplexStream, encoding: Encoding) -> Option<CloseCode> {
+        // The error type is dictated by the `Callback` trait of tungstenite.
+        #[allow(clippy::result_large_err)]
+        let check_protocol = |req: &Request, resp: Response| {
+            assert_eq!(
+                Some(encoding.protocol()),
+                req.headers()
+                    .get(SEC_WEBSOCKET_PROTOCOL)
+                    .and_then(|v| v.to_str().ok())
+            );
+            Ok(resp)
+        };
+        let mut socket = tokio_tungstenite::accept_hdr_async(stream, check_protocol)
+            .await
+            .unwrap();
+
+        let hello = json!({"op": 0, "d": {"obsWebSocketVersion": "5.0.1", "rpcVersion": 1}});
+        socket.send(encoding.encode(&hello).unwrap()).await.unwrap();
+
+        let identify = socket.next().await.unwrap().unwrap();
+        let identify = encoding.decode::<Value>(identify).ok().unwrap();
+        assert_eq!(1, identify["op"]);
+
+        let identified = json!({"op": 2, "d": {"negotiatedRpcVersion": 1}});
+        socket
+            .send(encoding.encode(&identified).unwrap())
+            .await
+            .unwrap();
+
+        while let Some(Ok(message)) = socket.next().await {
+            if let Message::Close(frame) = message {
+                return frame.map(|frame| frame.code);
+            }
+
+            let request = encoding.decode::<Value>(message).ok().unwrap();
+            assert_eq!("GetVersion", request["d"]["requestType"]);
+
+            let response = json!({
+                "op": 7,
+                "d": {
+                    "requestType": "GetVersion",
+                    "requestId": request["d"]["requestId"],
+                    "requestStatus": {"result": true, "code": 100},
+                    "responseData": {
+                        "obsVersion": "28.0.0",
+                        "obsWebSocketVersion": "5.0.1",
+                        "rpcVersion": 1,
+                        "availableRequests": ["GetVersion"],
+                        "supportedImageFormats": [],
+                        "platform": "linux",
+                        "platformDescription": "Linux",
+                    },
+                },
+            });
+            socket
+                .send(encoding.encode(&response).unwrap())
+                .await
+                .unwrap();
+        }
+
+        None
+    }
+
+    async fn connect(host: &str, encoding: Encoding) {
+        let (client_stream, server_stream) = tokio::io::duplex(4096);
+        let server = tokio::spawn(serve(server_stream, encoding));
+
+        let client = Client::connect_with_stream(
+            client_stream,
+            ConnectConfig {
+                host,
+                port: 4455,
+                password: None::<&str>,
+                event_subscriptions: None,
+                #[cfg(feature = "tls")]
+                tls: None,
+                #[cfg(feature = "msgpack")]
+                encoding,
+                broadcast_capacity: None,
+                reconnect: None,
+                request_timeout: Some(Duration::from_secs(5)),
+                connect_timeout: None,
+                handshake_timeout: None,
+                interceptors: Vec::new(),
+                coalesce_setters: false,
+                retry: None,
+                #[cfg(feature = "testing")]
+                recorder: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(ConnectionState::Identified, *client.state().borrow());
+
+        let session = client.session();
+        assert!(!session.authentication_required);
+        assert_eq!("linux", session.version.platform);
+
+        let version = client.general().version().await.unwrap();
+        assert_eq!(vec!["GetVersion".to_owned()], version.available_requests);
+
+        // Dropping the client closes the connection gracefully in the background.
+        drop(client);
+        assert_eq!(Some(CloseCode::Normal), server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn timeouts() {
+        // The server side never answers the web-socket upgrade.
+        let (client_stream, _server_stream) = tokio::io::duplex(4096);
+        let config = ConnectConfig {
+            connect_timeout: Some(Duration::from_millis(50)),
+            ..ConnectConfig::new("localhost", 4455, None::<&str>)
+        };
+        assert!(matches!(
+            Client::connect_with_stream(client_stream, config).await,
+            Err(Error::Connect(e))
+                if matches!(*e, tungstenite::Error::Io(ref e) if e.kind() == std::io::ErrorKind::TimedOut)
+        ));
+
+        // The server side accepts the web-socket connection, but never sends a `Hello`.
+        let (client_stream, server_stream) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut socket = tokio_tungstenite::accept_async(server_stream)
+                .await
+                .unwrap();
+            socket.next().await;
+        });
+        let config = ConnectConfig {
+            handshake_timeout: Some(Duration::from_millis(50)),
+            ..ConnectConfig::new("localhost", 4455, None::<&str>)
+        };
+        assert!(matches!(
+            Client::connect_with_stream(client_stream, config).await,
+            Err(Error::Handshake(HandshakeError::NoHello))
+        ));
+        server.await.unwrap();
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn request_timeouts() {
+        use crate::testing::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        server.respond(
+            "GetCurrentProgramScene",
+            json!({ "currentProgramSceneName": "Main" }),
+        );
+        server.delay("GetCurrentProgramScene", Duration::from_millis(100));
+
+        let config = ConnectConfig {
+            request_timeout: Some(Duration::from_millis(20)),
+            ..ConnectConfig::new("127.0.0.1", server.port(), None::<&str>)
+        };
+        let client = Client::connect_with_config(config).await.unwrap();
+        let scenes = client.scenes();
+
+        assert!(matches!(
+            scenes.current_program_scene().await,
+            Err(Error::Timeout { request_type }) if request_type == "GetCurrentProgramScene"
+        ));
+        assert!(client.receivers.is_empty().await);
+
+        // The late response arrives before this one, and must not be mistaken for it.
+        let version = client
+            .with_timeout(Duration::from_secs(1), client.general().version())
+            .await
+            .unwrap();
+        assert_eq!(1, version.rpc_version);
+        assert!(client.receivers.is_empty().await);
+
+        let scene = client
+            .with_timeout(Duration::from_secs(1), scenes.current_program_scene())
+            .await
+            .unwrap();
+        assert_eq!("Main", scene);
+    }
+
+    #[tokio::test]
+    async fn connect_with_stream() {
+        connect("localhost", Encoding::Json).await;
+    }
+
+    #[tokio::test]
+    async fn connect_with_stream_ipv6() {
+        connect("::1", Encoding::Json).await;
+    }
+
+    #[test]
+    fn websocket_urls() {
+        assert_eq!(
+            "ws://localhost:4455",
+            websocket_url(false, "localhost", 4455)
+        );
+        assert_eq!(
+            "wss://127.0.0.1:4455",
+            websocket_url(true, "127.0.0.1", 4455)
+        );
+        assert_eq!("ws://[::1]:4455", websocket_url(false, "::1", 4455));
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[tokio::test]
+    async fn connect_with_msgpack() {
+        connect("localhost", Encoding::MsgPack).await;
+    }
+}
@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use crate::responses::WebSocketCloseCode;
+
+/// Policy that controls whether and how the client automatically reconnects to obs-websocket,
+/// after the connection was lost.
+///
+/// On each successful reconnect, the handshake is performed again with the original password and
+/// the latest event subscriptions (as set during connect or by [`Client::reidentify`]).
+///
+/// [`Client::reidentify`]: super::Client::reidentify
+#[derive(Clone, Debug, Default)]
+pub struct ReconnectPolicy {
+    /// Maximum amount of reconnect attempts after the connection was lost. `None` means to try
+    /// forever.
+    pub max_attempts: Option<u32>,
+    /// Delay between reconnect attempts.
+    pub backoff: Backoff,
+}
+
+/// Exponential backoff with optional random jitter, describing how long to wait between
+/// consecutive attempts of an operation.
+#[derive(Clone, Debug)]
+pub struct Backoff {
+    /// Delay before the first attempt.
+    pub initial_delay: Duration,
+    /// Upper limit for the delay, no matter how many attempts were already made.
+    pub max_delay: Duration,
+    /// Factor that the delay is multiplied with, after each attempt.
+    pub multiplier: f64,
+    /// Random variance applied to each delay, as fraction of the delay. For example, `0.1` spreads
+    /// the delay randomly by ±10%.
+    pub jitter: f64,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.1,
+        }
+    }
+}
+
+impl Backoff {
+    /// Calculate the delay to wait before the given attempt, counting from zero.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.initial_delay.as_secs_f64()
+            * self
+                .multiplier
+                .powi(i32::try_from(attempt).unwrap_or(i32::MAX));
+        let delay = delay.min(self.max_delay.as_secs_f64());
+        let jitter = delay * self.jitter * (fastrand::f64() * 2.0 - 1.0);
+        let delay = (delay + jitter).max(0.0);
+
+        // Positive jitter can exceed the maximum, which might not even fit into a duration.
+        if delay >= self.max_delay.as_secs_f64() {
+            self.max_delay
+        } else {
+            Duration::from_secs_f64(delay)
+        }
+    }
+}
+
+/// Check whether the close code of a lost connection allows to reconnect.
+///
+/// As defined by the obs-websocket protocol, clients must not reconnect after their session was
+/// invalidated or the authentication failed.
+pub(super) fn may_reconnect(code: Option<u16>) -> bool {
+    !matches!(
+        code.map(WebSocketCloseCode::try_from),
+        Some(Ok(
+            WebSocketCloseCode::SessionInvalidated | WebSocketCloseCode::AuthenticationFailed
+        ))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn backoff_delay() {
+        let backoff = Backoff {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            multiplier: 2.0,
+            jitter: 0.0,
+        };
+
+        assert_eq!(Duration::from_millis(100), backoff.delay(0));
+        assert_eq!(Duration::from_millis(200), backoff.delay(1));
+        assert_eq!(Duration::from_millis(800), backoff.delay(3));
+        assert_eq!(Duration::from_secs(1), backoff.delay(4));
+        assert_eq!(Duration::from_secs(1), backoff.delay(u32::MAX));
+
+        let backoff = Backoff {
+            max_delay: Duration::MAX,
+            jitter: 0.5,
+            ..backoff
+        };
+        for _ in 0..100 {
+            assert!(backoff.delay(u32::MAX) >= Duration::from_secs(u64::MAX / 4));
+        }
+    }
+
+    #[test]
+    fn backoff_jitter() {
+        let backoff = Backoff {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(1),
+            multiplier: 1.0,
+            jitter: 0.5,
+        };
+
+        for _ in 0..100 {
+            let delay = backoff.delay(0);
+            assert!(delay >= Duration::from_millis(500));
+            assert!(delay <= Duration::from_millis(1500));
+        }
+    }
+
+    #[test]
+    fn reconnect_close_codes() {
+        assert!(may_reconnect(None));
+        assert!(may_reconnect(Some(1001)));
+        assert!(may_reconnect(Some(
+            WebSocketCloseCode::UnknownReason as u16
+        )));
+        assert!(!may_reconnect(Some(
+            WebSocketCloseCode::SessionInvalidated as u16
+        )));
+        assert!(!may_reconnect(Some(
+            WebSocketCloseCode::AuthenticationFailed as u16
+        )));
+    }
+}
@@ -0,0 +1,79 @@
+use std::{collections::HashMap, time::Duration};
+
+use super::Backoff;
+
+/// Policy that controls whether and how requests are retried, after they failed with a transient
+/// error as told by [`Error::is_retryable`](crate::Error::is_retryable).
+///
+/// Retries are only applied to idempotent requests, which can be sent several times without a
+/// different outcome. By default, these are all requests that only get (`Get…`) or set (`Set…`)
+/// a value, while other requests like `CreateScene` or `ToggleRecord` are never retried. This can
+/// be adjusted for single request types with [`Self::request_types`].
+///
+/// Requests within a [`Batch`](super::Batch) are never retried.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Maximum amount of attempts for each request, including the first one.
+    pub max_attempts: u32,
+    /// Delay between attempts.
+    pub backoff: Backoff,
+    /// Maximum amount of attempts for specific request types, like `GetSceneList`, overriding
+    /// the general `max_attempts`. This also declares requests as idempotent, that aren't by
+    /// default. A value of `1` disables retries for the request type.
+    pub request_types: HashMap<String, u32>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Backoff {
+                initial_delay: Duration::from_millis(100),
+                max_delay: Duration::from_secs(2),
+                multiplier: 2.0,
+                jitter: 0.1,
+            },
+            request_types: HashMap::new(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Maximum amount of attempts for the given request type.
+    pub(super) fn max_attempts(&self, request_type: &str) -> u32 {
+        match self.request_types.get(request_type) {
+            Some(max_attempts) => *max_attempts,
+            None if is_idempotent(request_type) => self.max_attempts,
+            None => 1,
+        }
+    }
+}
+
+/// Whether the request can be sent several times, without a different outcome than sending it
+/// once.
+fn is_idempotent(request_type: &str) -> bool {
+    request_type.starts_with("Get") || request_type.starts_with("Set")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_attempts() {
+        let policy = RetryPolicy {
+            request_types: [("GetSceneList", 5), ("SetInputMute", 1), ("CreateScene", 2)]
+                .into_iter()
+                .map(|(request_type, max)| (request_type.to_owned(), max))
+                .collect(),
+            ..RetryPolicy::default()
+        };
+
+        assert_eq!(3, policy.max_attempts("GetVersion"));
+        assert_eq!(3, policy.max_attempts("SetCurrentProgramScene"));
+        assert_eq!(1, policy.max_attempts("ToggleRecord"));
+        assert_eq!(5, policy.max_attempts("GetSceneList"));
+        assert_eq!(1, policy.max_attempts("SetInputMute"));
+        assert_eq!(2, policy.max_attempts("CreateScene"));
+    }
+}
@@ -0,0 +1,409 @@
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use rustls::{
+    client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier},
+    Certificate, ClientConfig, OwnedTrustAnchor, PrivateKey, RootCertStore, ServerName,
+};
+use rustls_pemfile::Item;
+use tokio::net::TcpStream;
+use tokio_rustls::{client::TlsStream, TlsConnector};
+
+/// Configuration for TLS encrypted connections to obs-websocket.
+///
+/// The default configuration trusts the publicly trusted root certificates only, like a browser
+/// would.
+#[derive(Clone, Default)]
+pub struct TlsConfig {
+    /// A fully custom client configuration. If set, it's used as-is and the
+    /// [`root_certificates`](Self::root_certificates),
+    /// [`client_identity`](Self::client_identity) and
+    /// [`insecure_skip_verify`](Self::insecure_skip_verify) settings are ignored.
+    pub client_config: Option<Arc<ClientConfig>>,
+    /// Paths to PEM files with additional root certificates to trust, for example of an internal
+    /// certificate authority. These are trusted in addition to the publicly trusted ones.
+    pub root_certificates: Vec<PathBuf>,
+    /// Optional client certificate, to authenticate against servers that require mutual TLS.
+    pub client_identity: Option<ClientIdentity>,
+    /// Skip the verification of the server's certificate entirely.
+    ///
+    /// **Dangerous:** This makes the connection vulnerable to man-in-the-middle attacks and
+    /// should only ever be used for testing, for example against self-signed servers in a lab.
+    pub insecure_skip_verify: bool,
+    /// Server name to use for SNI and the certificate verification, instead of the host that is
+    /// connected to. Useful when connecting through an IP address or a tunnel.
+    pub server_name: Option<String>,
+}
+
+/// Client certificate and private key, for mutual TLS.
+#[derive(Clone, Debug)]
+pub struct ClientIdentity {
+    /// Path to a PEM file with the client certificate, optionally followed by intermediate
+    /// certificates.
+    pub certificate_chain: PathBuf,
+    /// Path to a PEM file with the private key of the client certificate, either in PKCS#8, RSA or
+    /// EC format.
+    pub private_key: PathBuf,
+}
+
+/// Errors that can occur while setting up a TLS connection.
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    /// A certificate or key file could not be read.
+    #[error("failed reading `{path}`")]
+    ReadFile {
+        /// Location of the file.
+        path: PathBuf,
+        /// Underlying I/O error.
+        #[source]
+        source: io::Error,
+    },
+    /// A file didn't contain any (valid) certificates.
+    #[error("no valid certificates found in `{0}`")]
+    InvalidCertificates(PathBuf),
+    /// A file didn't contain any private key.
+    #[error("no private key found in `{0}`")]
+    NoPrivateKey(PathBuf),
+    /// The client certificate or private key was rejected.
+    #[error("invalid client identity")]
+    ClientIdentity(#[source] rustls::Error),
+    /// The host or configured server name is not valid for TLS.
+    #[error("invalid server name `{0}`")]
+    ServerName(String),
+}
+
+/// Prepared TLS settings, ready to encrypt new connections.
+#[derive(Clone)]
+pub(super) struct TlsConnection {
+    connector: TlsConnector,
+    server_name: ServerName,
+}
+
+impl TlsConfig {
+    /// Load all certificates and keys and create the settings for connecting to the given host.
+    pub(super) fn build(&self, host: &str) -> Result<TlsConnection, TlsError> {
+        let client_config = match &self.client_config {
+            Some(config) => Arc::clone(config),
+            None => Arc::new(self.client_config()?),
+        };
+
+        let server_name = self.server_name.as_deref().unwrap_or(host);
+        let server_name = ServerName::try_from(server_name)
+            .map_err(|_| TlsError::ServerName(server_name.to_owned()))?;
+
+        Ok(TlsConnection {
+            connector: TlsConnector::from(client_config),
+            server_name,
+        })
+    }
+
+    fn client_config(&self) -> Result<ClientConfig, TlsError> {
+        let builder = ClientConfig::builder().with_safe_defaults();
+
+        let builder = if self.insecure_skip_verify {
+            builder.with_custom_certificate_verifier(Arc::new(NoVerification))
+        } else {
+            let mut roots = RootCertStore::empty();
+            roots.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
+                OwnedTrustAnchor::from_subject_spki_name_constraints(
+                    ta.subject,
+                    ta.spki,
+                    ta.name_constraints,
+                )
+            }));
+
+            for path in &self.root_certificates {
+                let certs = read_certificates(path)?
+                    .into_iter()
+                    .map(|cert| cert.0)
+                    .collect::<Vec<_>>();
+                let (_, invalid) = roots.add_parsable_certificates(&certs);
+
+                if invalid > 0 {
+                    return Err(TlsError::InvalidCertificates(path.clone()));
+                }
+            }
+
+            builder.with_custom_certificate_verifier(Arc::new(WebPkiVerifier::new(roots, None)))
+        };
+
+        match &self.client_identity {
+            Some(identity) => builder
+                .with_single_cert(
+                    read_certificates(&identity.certificate_chain)?,
+                    read_private_key(&identity.private_key)?,
+                )
+                .map_err(TlsError::ClientIdentity),
+            None => Ok(builder.with_no_client_auth()),
+        }
+    }
+}
+
+impl TlsConnection {
+    /// Open a TCP connection to the given host and port, and encrypt it.
+    pub(super) async fn connect(&self, host: &str, port: u16) -> io::Result<TlsStream<TcpStream>> {
+        let stream = TcpStream::connect((host, port)).await?;
+        self.connector
+            .connect(self.server_name.clone(), stream)
+            .await
+    }
+}
+
+fn read_pem(path: &Path) -> Result<Vec<Item>, TlsError> {
+    let read_error = |source| TlsError::ReadFile {
+        path: path.to_owned(),
+        source,
+    };
+
+    let file = File::open(path).map_err(read_error)?;
+    rustls_pemfile::read_all(&mut BufReader::new(file)).map_err(read_error)
+}
+
+fn read_certificates(path: &Path) -> Result<Vec<Certificate>, TlsError> {
+    let certs = read_pem(path)?
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::X509Certificate(cert) => Some(Certificate(cert)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if certs.is_empty() {
+        return Err(TlsError::InvalidCertificates(path.to_owned()));
+    }
+
+    Ok(certs)
+}
+
+fn read_private_key(path: &Path) -> Result<PrivateKey, TlsError> {
+    read_pem(path)?
+        .into_iter()
+        .find_map(|item| match item {
+            Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| TlsError::NoPrivateKey(path.to_owned()))
+}
+
+/// Certificate verifier that accepts any certificate, for [`TlsConfig::insecure_skip_verify`].
+struct NoVerification;
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustls::{server::AllowAnyAuthenticatedClient, ServerConfig};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+    use tokio_rustls::TlsAcceptor;
+
+    use super::*;
+
+    /// Location of a certificate or key file of the test certificate authority.
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    fn identity(certificate_chain: &str, private_key: &str) -> Option<ClientIdentity> {
+        Some(ClientIdentity {
+            certificate_chain: fixture(certificate_chain),
+            private_key: fixture(private_key),
+        })
+    }
+
+    /// Start a server, that requires clients to authenticate with a certificate of the test
+    /// certificate authority, and echoes back a single message.
+    async fn echo_server() -> u16 {
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(&read_certificates(&fixture("ca.pem")).unwrap()[0])
+            .unwrap();
+
+        let config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots))
+            .with_single_cert(
+                read_certificates(&fixture("server.pem")).unwrap(),
+                read_private_key(&fixture("server.key")).unwrap(),
+            )
+            .unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let acceptor = acceptor.clone();
+
+                tokio::spawn(async move {
+                    if let Ok(mut stream) = acceptor.accept(stream).await {
+                        let mut buf = [0; 5];
+                        stream.read_exact(&mut buf).await.unwrap();
+                        stream.write_all(&buf).await.unwrap();
+                        stream.shutdown().await.ok();
+                    }
+                });
+            }
+        });
+
+        port
+    }
+
+    async fn echo(connection: &TlsConnection, port: u16) -> io::Result<[u8; 5]> {
+        let mut stream = connection.connect("127.0.0.1", port).await?;
+        stream.write_all(b"hello").await?;
+
+        let mut buf = [0; 5];
+        stream.read_exact(&mut buf).await?;
+        Ok(buf)
+    }
+
+    #[test]
+    fn build_default() {
+        assert!(TlsConfig::default().build("localhost").is_ok());
+        assert!(TlsConfig::default().build("127.0.0.1").is_ok());
+    }
+
+    #[test]
+    fn invalid_server_name() {
+        let config = TlsConfig {
+            server_name: Some("not a host".to_owned()),
+            ..TlsConfig::default()
+        };
+
+        assert!(matches!(
+            config.build("localhost"),
+            Err(TlsError::ServerName(name)) if name == "not a host"
+        ));
+    }
+
+    #[test]
+    fn invalid_files() {
+        let missing = TlsConfig {
+            root_certificates: vec![PathBuf::from("/does/not/exist.pem")],
+            ..TlsConfig::default()
+        };
+        assert!(matches!(
+            missing.build("localhost"),
+            Err(TlsError::ReadFile { .. })
+        ));
+
+        let empty =
+            std::env::temp_dir().join(format!("obws-{}-tls-invalid-files.pem", std::process::id()));
+        std::fs::write(&empty, "").unwrap();
+
+        let no_certs = TlsConfig {
+            root_certificates: vec![empty.clone()],
+            ..TlsConfig::default()
+        };
+        assert!(matches!(
+            no_certs.build("localhost"),
+            Err(TlsError::InvalidCertificates(_))
+        ));
+
+        assert!(matches!(
+            read_private_key(&empty),
+            Err(TlsError::NoPrivateKey(_))
+        ));
+
+        std::fs::remove_file(&empty).unwrap();
+    }
+
+    #[test]
+    fn load_files() {
+        let roots = TlsConfig {
+            root_certificates: vec![fixture("ca.pem")],
+            ..TlsConfig::default()
+        };
+        assert!(roots.build("localhost").is_ok());
+
+        for (certificate_chain, private_key) in [
+            ("client.pem", "client.key"),
+            ("client.pem", "client-ec.key"),
+            ("client-rsa.pem", "client-rsa.key"),
+        ] {
+            let config = TlsConfig {
+                client_identity: identity(certificate_chain, private_key),
+                ..TlsConfig::default()
+            };
+            assert!(
+                config.build("localhost").is_ok(),
+                "identity from `{}` and `{}`",
+                certificate_chain,
+                private_key,
+            );
+        }
+
+        let not_a_key = TlsConfig {
+            client_identity: identity("client.pem", "client.pem"),
+            ..TlsConfig::default()
+        };
+        assert!(matches!(
+            not_a_key.build("localhost"),
+            Err(TlsError::NoPrivateKey(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn connect() {
+        let port = echo_server().await;
+
+        let config = TlsConfig {
+            root_certificates: vec![fixture("ca.pem")],
+            client_identity: identity("client.pem", "client.key"),
+            server_name: Some("localhost".to_owned()),
+            ..TlsConfig::default()
+        };
+        let connection = config.build("127.0.0.1").unwrap();
+        assert_eq!(b"hello", &echo(&connection, port).await.unwrap());
+
+        // The server certificate isn't publicly trusted, and the server requires a client
+        // certificate.
+        let untrusted = TlsConfig {
+            client_identity: identity("client.pem", "client.key"),
+            ..TlsConfig::default()
+        };
+        assert!(echo(&untrusted.build("localhost").unwrap(), port)
+            .await
+            .is_err());
+
+        let anonymous = TlsConfig {
+            root_certificates: vec![fixture("ca.pem")],
+            ..TlsConfig::default()
+        };
+        assert!(echo(&anonymous.build("localhost").unwrap(), port)
+            .await
+            .is_err());
+
+        let insecure = TlsConfig {
+            client_identity: identity("client-rsa.pem", "client-rsa.key"),
+            insecure_skip_verify: true,
+            ..TlsConfig::default()
+        };
+        let connection = insecure.build("localhost").unwrap();
+        assert_eq!(b"hello", &echo(&connection, port).await.unwrap());
+    }
+}
@@ -2,550 +2,859 @@
 
 use std::{collections::BTreeMap, path::PathBuf};
 
-use serde::Deserialize;
+use serde::{
+    de::{self, DeserializeSeed, IgnoredAny, IntoDeserializer, MapAccess, SeqAccess, Visitor},
+    forward_to_deserialize_any, Deserialize, Deserializer, Serialize,
+};
 use time::Duration;
 
 use crate::{
     common::{MediaAction, MonitorType},
+    requests::EventSubscription,
     responses::{filters::SourceFilter, scene_items::SceneItemTransform},
 };
 
-/// All possible event types that can occur while the user interacts with OBS.
-#[derive(Clone, Debug, Deserialize)]
-#[serde(tag = "eventType", content = "eventData")]
-pub enum Event {
-    // --------------------------------
-    // Config
-    // --------------------------------
-    /// The current scene collection has begun changing.
-    ///
-    /// **Note:** We recommend using this event to trigger a pause of all polling requests, as
-    /// performing any requests during a scene collection change is considered undefined behavior
-    /// and can cause crashes!
-    CurrentSceneCollectionChanging {
-        /// Name of the current scene collection.
-        #[serde(rename = "sceneCollectionName")]
-        name: String,
-    },
-    /// The current scene collection has changed.
-    ///
-    /// **Note:** If polling has been paused during [`CurrentSceneCollectionChanging`], this is the
-    /// indicator to restart polling.
-    ///
-    /// [`CurrentSceneCollectionChanging`]: Event::CurrentSceneCollectionChanging
-    CurrentSceneCollectionChanged {
-        /// Name of the new scene collection.
-        #[serde(rename = "sceneCollectionName")]
-        name: String,
-    },
-    /// The scene collection list has changed.
-    SceneCollectionListChanged {
-        /// Updated list of scene collections.
-        #[serde(rename = "sceneCollections")]
-        collections: Vec<String>,
-    },
-    /// The current profile has begun changing.
-    CurrentProfileChanging {
-        /// Name of the current profile.
-        #[serde(rename = "profileName")]
-        name: String,
-    },
-    /// The current profile has changed.
-    CurrentProfileChanged {
-        /// Name of the new profile.
-        #[serde(rename = "profileName")]
-        name: String,
-    },
-    /// The profile list has changed.
-    ProfileListChanged {
-        /// Updated list of profiles.
-        #[serde(rename = "profiles")]
-        profiles: Vec<String>,
-    },
-    // --------------------------------
-    // Filters
-    // --------------------------------
-    /// A filter has been added to a source.
-    SourceFilterCreated {
-        /// Name of the source the filter was added to.
-        #[serde(rename = "sourceName")]
-        source: String,
-        /// Name of the filter.
-        #[serde(rename = "filterName")]
-        filter: String,
-        /// The kind of the filter.
-        #[serde(rename = "filterKind")]
-        kind: String,
-        /// Index position of the filter.
-        #[serde(rename = "filterIndex")]
-        index: u32,
-        /// The settings configured to the filter when it was created.
-        #[serde(rename = "filterSettings")]
-        settings: serde_json::Value,
-        /// The default settings for the filter.
-        #[serde(rename = "defaultFilterSettings")]
-        default_settings: serde_json::Value,
-    },
-    /// A filter has been removed from a source.
-    SourceFilterRemoved {
-        /// Name of the source the filter was on.
-        #[serde(rename = "sourceName")]
-        source: String,
-        /// Name of the filter.
-        #[serde(rename = "filterName")]
-        filter: String,
-    },
-    /// A source's filter list has been re-indexed.
-    SourceFilterListReindexed {
-        /// Name of the source.
-        #[serde(rename = "sourceName")]
-        source: String,
-        /// Array of filter objects.
-        filters: Vec<SourceFilter>,
-    },
-    /// A source filter's enable state has changed.
-    SourceFilterEnableStateChanged {
-        /// Name of the source the filter is on.
-        #[serde(rename = "sourceName")]
-        source: String,
-        /// Name of the filter.
-        #[serde(rename = "filterName")]
-        filter: String,
-        /// Whether the filter is enabled.
-        #[serde(rename = "filterEnabled")]
-        enabled: bool,
-    },
-    /// The name of a source filter has changed.
-    SourceFilterNameChanged {
-        /// The source the filter is on.
-        #[serde(rename = "sourceName")]
-        source: String,
-        /// Old name of the filter.
-        #[serde(rename = "oldFilterName")]
-        old_name: String,
-        /// New name of the filter.
-        #[serde(rename = "filterName")]
-        new_name: String,
-    },
-    // --------------------------------
-    // General
-    // --------------------------------
-    /// A custom event that was triggered by
-    /// [`crate::client::General::broadcast_custom_event`].
-    ///
-    /// The content can be any valid JSON object.
-    CustomEvent(serde_json::Value),
-    /// OBS has begun the shutdown process.
-    ExitStarted,
-    /// An event has been emitted from a vendor.
-    ///
-    /// A vendor is a unique name registered by a third-party plugin or script, which allows for
-    /// custom requests and events to be added to obs-websocket. If a plugin or script implements
-    /// vendor requests or events, documentation is expected to be provided with them.
-    VendorEvent {
-        /// Name of the vendor emitting the event.
-        #[serde(rename = "vendorName")]
-        vendor_name: String,
-        /// Vendor-provided event type definition.
-        #[serde(rename = "eventType")]
-        event_type: String,
-        /// Vendor-provided event data. `{}` if event does not provide any data.
-        #[serde(rename = "eventData")]
-        event_data: serde_json::Value,
-    },
-    // --------------------------------
-    // Inputs
-    // --------------------------------
-    /// An input has been created.
-    InputCreated {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-        /// The kind of the input.
-        #[serde(rename = "inputKind")]
-        kind: String,
-        /// The unversioned kind of input (aka no `_v2` stuff).
-        #[serde(rename = "unversionedInputKind")]
-        unversioned_kind: String,
-        /// The settings configured to the input when it was created.
-        #[serde(rename = "inputSettings")]
-        settings: serde_json::Value,
-        /// The default settings for the input.
-        #[serde(rename = "defaultInputSettings")]
-        default_settings: serde_json::Value,
-    },
-    /// An input has been removed.
-    InputRemoved {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-    },
-    /// The name of an input has changed.
-    InputNameChanged {
-        /// Old name of the input.
-        #[serde(rename = "oldInputName")]
-        old_name: String,
-        /// New name of the input.
-        #[serde(rename = "inputName")]
-        new_name: String,
-    },
-    /// An input's active state has changed.
-    ///
-    /// When an input is active, it means it's being shown by the program feed.
-    InputActiveStateChanged {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-        /// Whether the input is active.
-        #[serde(rename = "videoActive")]
-        active: bool,
-    },
-    /// An input's show state has changed.
-    ///
-    /// When an input is showing, it means it's being shown by the preview or a dialog.
-    InputShowStateChanged {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-        /// Whether the input is showing.
-        #[serde(rename = "videoShowing")]
-        showing: bool,
-    },
-    /// An input's mute state has changed.
-    InputMuteStateChanged {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-        /// Whether the input is muted.
-        #[serde(rename = "inputMuted")]
-        muted: bool,
-    },
-    /// An input's volume level has changed.
-    InputVolumeChanged {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-        /// New volume level in `multimap`.
-        #[serde(rename = "inputVolumeMul")]
-        mul: f64,
-        /// New volume level in `dB`.
-        #[serde(rename = "inputVolumeDb")]
-        db: f64,
-    },
-    /// The audio balance value of an input has changed.
-    InputAudioBalanceChanged {
-        /// Name of the affected input.
-        #[serde(rename = "inputName")]
-        name: String,
-        /// New audio balance value of the input.
-        #[serde(rename = "inputAudioBalance")]
-        audio_balance: f64,
-    },
-    /// The sync offset of an input has changed.
-    InputAudioSyncOffsetChanged {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-        /// New sync offset in milliseconds.
-        #[serde(
-            rename = "inputAudioSyncOffset",
-            with = "crate::serde::duration_millis"
-        )]
-        offset: Duration,
-    },
-    /// The audio tracks of an input have changed.
-    InputAudioTracksChanged {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-        /// Object of audio tracks along with their associated enable states.
-        #[serde(rename = "inputAudioTracks")]
-        tracks: BTreeMap<String, bool>,
-    },
-    /// The monitor type of an input has changed.
-    InputAudioMonitorTypeChanged {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-        /// New monitor type of the input.
-        #[serde(rename = "monitorType")]
-        monitor_type: MonitorType,
-    },
-    /// A high-volume event providing volume levels of all active inputs every 50 milliseconds.
-    InputVolumeMeters {
-        /// Array of active inputs with their associated volume levels.
-        #[serde(rename = "inputs")]
-        inputs: Vec<InputVolumeMeter>,
-    },
-    // --------------------------------
-    // Media Inputs
-    // --------------------------------
-    /// A media input has started playing.
-    MediaInputPlaybackStarted {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-    },
-    /// A media input has finished playing.
-    MediaInputPlaybackEnded {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-    },
-    /// An action has been performed on an input.
-    MediaInputActionTriggered {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: String,
-        /// Action performed on the input.
-        #[serde(rename = "mediaAction")]
-        media_action: MediaAction,
-    },
-    // --------------------------------
-    // Outputs
-    // --------------------------------
-    /// The state of the stream output has changed.
-    StreamStateChanged {
-        /// Whether the output is active.
-        #[serde(rename = "outputActive")]
-        active: bool,
-        /// The specific state of the output.
-        #[serde(rename = "outputState")]
-        state: OutputState,
-    },
-    /// The state of the record output has changed.
-    RecordStateChanged {
-        /// Whether the output is active.
-        #[serde(rename = "outputActive")]
-        active: bool,
-        /// The specific state of the output.
-        #[serde(rename = "outputState")]
-        state: OutputState,
-        /// File name for the saved recording, if record stopped.
-        #[serde(rename = "outputPath")]
-        path: Option<String>,
-    },
-    /// The state of the replay buffer output has changed.
-    ReplayBufferStateChanged {
-        /// Whether the output is active.
-        #[serde(rename = "outputActive")]
-        active: bool,
-        /// The specific state of the output.
-        #[serde(rename = "outputState")]
-        state: OutputState,
-    },
-    /// The state of the virtual cam output has changed.
-    VirtualcamStateChanged {
-        /// Whether the output is active.
-        #[serde(rename = "outputActive")]
-        active: bool,
-        /// The specific state of the output.
-        #[serde(rename = "outputState")]
-        state: OutputState,
-    },
-    /// The replay buffer has been saved.
-    ReplayBufferSaved {
-        /// Path of the saved replay file.
-        #[serde(rename = "savedReplayPath")]
-        path: PathBuf,
-    },
-    // --------------------------------
-    // Scene Items
-    // --------------------------------
-    /// A scene item has been created.
-    SceneItemCreated {
-        /// Name of the scene the item was added to.
-        #[serde(rename = "sceneName")]
-        scene: String,
-        /// Name of the underlying source (input/scene).
-        #[serde(rename = "sourceName")]
-        source: String,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: u64,
-        /// Index position of the item.
-        #[serde(rename = "sceneItemIndex")]
-        index: u32,
-    },
-    /// A scene item has been removed.
-    ///
-    /// This event is not emitted when the scene the item is in is removed.
-    SceneItemRemoved {
-        /// Name of the scene the item was removed from.
-        #[serde(rename = "sceneName")]
-        scene: String,
-        /// Name of the underlying source (input/scene).
-        #[serde(rename = "sourceName")]
-        source: String,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: u64,
-    },
-    /// A scene's item list has been re-indexed.
-    SceneItemListReindexed {
-        /// Name of the scene.
-        #[serde(rename = "sceneName")]
-        scene: String,
-        /// Array of scene item objects.
-        #[serde(rename = "sceneItems")]
-        items: Vec<BasicSceneItem>,
-    },
-    /// A scene item's enable state has changed.
-    SceneItemEnableStateChanged {
-        /// Name of the scene the item is in.
-        #[serde(rename = "sceneName")]
-        scene: String,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: u64,
-        /// Whether the scene item is enabled (visible).
-        #[serde(rename = "sceneItemEnabled")]
-        enabled: bool,
-    },
-    /// A scene item's lock state has changed.
-    SceneItemLockStateChanged {
-        /// Name of the scene the item is in.
-        #[serde(rename = "sceneName")]
-        scene: String,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: u64,
-        /// Whether the scene item is locked.
-        #[serde(rename = "sceneItemLocked")]
-        locked: bool,
-    },
-    /// A scene item has been selected in the UI.
-    SceneItemSelected {
-        /// Name of the scene the item is in.
-        #[serde(rename = "sceneName")]
-        scene: String,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: u64,
-    },
-    /// The transform/crop of a scene item has changed.
-    SceneItemTransformChanged {
-        /// The name of the scene the item is in.
-        #[serde(rename = "sceneName")]
-        scene: String,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: u64,
-        /// New transform/crop info of the scene item.
-        #[serde(rename = "sceneItemTransform")]
-        transform: SceneItemTransform,
-    },
-    // --------------------------------
-    // Scenes
-    // --------------------------------
-    /// A new scene has been created.
-    SceneCreated {
-        /// Name of the new scene.
-        #[serde(rename = "sceneName")]
-        name: String,
-        /// Whether the new scene is a group.
-        #[serde(rename = "isGroup")]
-        is_group: bool,
-    },
-    /// A scene has been removed.
-    SceneRemoved {
-        /// Name of the removed scene.
-        #[serde(rename = "sceneName")]
-        name: String,
-        /// Whether the scene was a group.
-        #[serde(rename = "isGroup")]
-        is_group: bool,
-    },
-    /// The name of a scene has changed.
-    SceneNameChanged {
-        /// Old name of the scene.
-        #[serde(rename = "oldSceneName")]
-        old_name: String,
-        /// New name of the scene.
-        #[serde(rename = "sceneName")]
-        new_name: String,
-    },
-    /// The current program scene has changed.
-    CurrentProgramSceneChanged {
-        /// Name of the scene that was switched to.
-        #[serde(rename = "sceneName")]
-        name: String,
-    },
-    /// The current preview scene has changed.
-    CurrentPreviewSceneChanged {
-        /// Name of the scene that was switched to.
-        #[serde(rename = "sceneName")]
-        name: String,
-    },
-    /// The list of scenes has changed.
-    SceneListChanged {
-        /// Updated array of scenes.
-        scenes: Vec<Scene>,
-    },
-    // --------------------------------
-    // Transitions
-    // --------------------------------
-    /// The current scene transition has changed.
-    CurrentSceneTransitionChanged {
-        /// Name of the new transition.
-        #[serde(rename = "transitionName")]
-        name: String,
-    },
-    /// The current scene transition duration has changed.
-    CurrentSceneTransitionDurationChanged {
-        /// Transition duration in milliseconds.
-        #[serde(rename = "transitionDuration", with = "crate::serde::duration_millis")]
-        duration: Duration,
-    },
-    /// A scene transition has started.
-    SceneTransitionStarted {
-        /// Scene transition name.
-        #[serde(rename = "transitionName")]
-        name: String,
-    },
-    /// A scene transition has completed fully.
-    ///
-    /// **Note:** Does not appear to trigger when the transition is interrupted by the user.
-    SceneTransitionEnded {
-        /// Scene transition name.
-        #[serde(rename = "transitionName")]
-        name: String,
-    },
-    /// A scene transition's video has completed fully.
-    ///
-    /// Useful for stinger transitions to tell when the video *actually* ends.
-    /// [`Self::SceneTransitionEnded`] only signifies the cut point, not the completion of
-    /// transition playback.
+/// Generate the [`Event`] enum from the events of all categories, together with a separate enum
+/// for each category, that can be converted from and into an [`Event`].
+///
+/// Tuple variants must name their single field, so it can be moved between the enums. Variants
+/// without a category are listed last and only added to the [`Event`] enum.
+macro_rules! events {
+    (
+        $(#[$meta:meta])*
+        pub enum Event {
+            $(
+                $(#[$cmeta:meta])*
+                $category:ident => $name:ident {
+                    $(
+                        $(#[$vmeta:meta])*
+                        $variant:ident
+                        $({ $($(#[$fmeta:meta])* $field:ident: $fty:ty),* $(,)? })?
+                        $(($tfield:ident: $tty:ty))?
+                    ),* $(,)?
+                }
+            )*
+            _ => {
+                $($custom:tt)*
+            }
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Serialize)]
+        #[serde(tag = "eventType", content = "eventData")]
+        pub enum Event {
+            $($(
+                $(#[$vmeta])*
+                $variant $({ $($(#[$fmeta])* $field: $fty),* })? $(($tty))?,
+            )*)*
+            $($custom)*
+        }
+
+        impl Event {
+            /// Category of the event, as defined by the group it belongs to in the event
+            /// subscriptions. Events that are generated by this crate have no category.
+            fn group(&self) -> EventSubscription {
+                match self {
+                    $($(Self::$variant { .. } => EventSubscription::$category,)*)*
+                    _ => EventSubscription::NONE,
+                }
+            }
+        }
+
+        impl Event {
+            /// Deserialize the data of a known event type straight into the enum of its
+            /// category, or hand back the deserializer if the event type is unknown.
+            fn deserialize_known<'de, D>(
+                event_type: &str,
+                data: D,
+            ) -> Result<Result<Self, D::Error>, D>
+            where
+                D: Deserializer<'de>,
+            {
+                match event_type {
+                    $(
+                        $(stringify!($variant))|* => Ok($name::deserialize(TaggedData {
+                            event_type: Some(event_type),
+                            data: Some(data),
+                        })
+                        .map(Self::from)),
+                    )*
+                    _ => Err(data),
+                }
+            }
+        }
+
+        $(
+            $(#[$cmeta])*
+            #[derive(Clone, Debug, Deserialize, Serialize)]
+            #[serde(tag = "eventType", content = "eventData")]
+            pub enum $name {
+                $(
+                    $(#[$vmeta])*
+                    $variant $({ $($(#[$fmeta])* $field: $fty),* })? $(($tty))?,
+                )*
+            }
+
+            impl From<$name> for Event {
+                fn from(event: $name) -> Self {
+                    match event {
+                        $(
+                            $name::$variant $({ $($field),* })? $(($tfield))? => {
+                                Self::$variant $({ $($field),* })? $(($tfield))?
+                            }
+                        )*
+                    }
+                }
+            }
+
+            impl TryFrom<Event> for $name {
+                type Error = Event;
+
+                fn try_from(event: Event) -> Result<Self, Self::Error> {
+                    match event {
+                        $(
+                            Event::$variant $({ $($field),* })? $(($tfield))? => {
+                                Ok(Self::$variant $({ $($field),* })? $(($tfield))?)
+                            }
+                        )*
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+events! {
+    /// All possible event types that can occur while the user interacts with OBS.
+    pub enum Event {
+        /// Events of the `Config` category, about scene collections and profiles.
+        CONFIG => ConfigEvent {
+            /// The current scene collection has begun changing.
+            ///
+            /// **Note:** We recommend using this event to trigger a pause of all polling requests,
+            /// as performing any requests during a scene collection change is considered undefined
+            /// behavior and can cause crashes!
+            CurrentSceneCollectionChanging {
+                /// Name of the current scene collection.
+                #[serde(rename = "sceneCollectionName")]
+                name: String,
+            },
+            /// The current scene collection has changed.
+            ///
+            /// **Note:** If polling has been paused during [`CurrentSceneCollectionChanging`], this
+            /// is the indicator to restart polling.
+            ///
+            /// [`CurrentSceneCollectionChanging`]: Event::CurrentSceneCollectionChanging
+            CurrentSceneCollectionChanged {
+                /// Name of the new scene collection.
+                #[serde(rename = "sceneCollectionName")]
+                name: String,
+            },
+            /// The scene collection list has changed.
+            SceneCollectionListChanged {
+                /// Updated list of scene collections.
+                #[serde(rename = "sceneCollections")]
+                collections: Vec<String>,
+            },
+            /// The current profile has begun changing.
+            CurrentProfileChanging {
+                /// Name of the current profile.
+                #[serde(rename = "profileName")]
+                name: String,
+            },
+            /// The current profile has changed.
+            CurrentProfileChanged {
+                /// Name of the new profile.
+                #[serde(rename = "profileName")]
+                name: String,
+            },
+            /// The profile list has changed.
+            ProfileListChanged {
+                /// Updated list of profiles.
+                #[serde(rename = "profiles")]
+                profiles: Vec<String>,
+            },
+        }
+        /// Events of the `Filters` category, about source filters.
+        FILTERS => FilterEvent {
+            /// A filter has been added to a source.
+            SourceFilterCreated {
+                /// Name of the source the filter was added to.
+                #[serde(rename = "sourceName")]
+                source: String,
+                /// Name of the filter.
+                #[serde(rename = "filterName")]
+                filter: String,
+                /// The kind of the filter.
+                #[serde(rename = "filterKind")]
+                kind: String,
+                /// Index position of the filter.
+                #[serde(rename = "filterIndex")]
+                index: u32,
+                /// The settings configured to the filter when it was created.
+                #[serde(rename = "filterSettings")]
+                settings: serde_json::Value,
+                /// The default settings for the filter.
+                #[serde(rename = "defaultFilterSettings")]
+                default_settings: serde_json::Value,
+            },
+            /// A filter has been removed from a source.
+            SourceFilterRemoved {
+                /// Name of the source the filter was on.
+                #[serde(rename = "sourceName")]
+                source: String,
+                /// Name of the filter.
+                #[serde(rename = "filterName")]
+                filter: String,
+            },
+            /// A source's filter list has been re-indexed.
+            SourceFilterListReindexed {
+                /// Name of the source.
+                #[serde(rename = "sourceName")]
+                source: String,
+                /// Array of filter objects.
+                filters: Vec<SourceFilter>,
+            },
+            /// A source filter's enable state has changed.
+            SourceFilterEnableStateChanged {
+                /// Name of the source the filter is on.
+                #[serde(rename = "sourceName")]
+                source: String,
+                /// Name of the filter.
+                #[serde(rename = "filterName")]
+                filter: String,
+                /// Whether the filter is enabled.
+                #[serde(rename = "filterEnabled")]
+                enabled: bool,
+            },
+            /// The name of a source filter has changed.
+            SourceFilterNameChanged {
+                /// The source the filter is on.
+                #[serde(rename = "sourceName")]
+                source: String,
+                /// Old name of the filter.
+                #[serde(rename = "oldFilterName")]
+                old_name: String,
+                /// New name of the filter.
+                #[serde(rename = "filterName")]
+                new_name: String,
+            },
+        }
+        /// Events of the `General` category, and vendor events.
+        GENERAL => GeneralEvent {
+            /// A custom event that was triggered by
+            /// [`crate::client::General::broadcast_custom_event`].
+            ///
+            /// The content can be any valid JSON object.
+            CustomEvent(value: serde_json::Value),
+            /// OBS has begun the shutdown process.
+            ExitStarted,
+            /// An event has been emitted from a vendor.
+            ///
+            /// A vendor is a unique name registered by a third-party plugin or script, which allows
+            /// for custom requests and events to be added to obs-websocket. If a plugin or script
+            /// implements vendor requests or events, documentation is expected to be provided with
+            /// them.
+            VendorEvent {
+                /// Name of the vendor emitting the event.
+                #[serde(rename = "vendorName")]
+                vendor_name: String,
+                /// Vendor-provided event type definition.
+                #[serde(rename = "eventType")]
+                event_type: String,
+                /// Vendor-provided event data. `{}` if event does not provide any data.
+                #[serde(rename = "eventData")]
+                event_data: serde_json::Value,
+            },
+        }
+        /// Events of the `Inputs` category, including its high-volume events.
+        INPUTS => InputEvent {
+            /// An input has been created.
+            InputCreated {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+                /// The kind of the input.
+                #[serde(rename = "inputKind")]
+                kind: String,
+                /// The unversioned kind of input (aka no `_v2` stuff).
+                #[serde(rename = "unversionedInputKind")]
+                unversioned_kind: String,
+                /// The settings configured to the input when it was created.
+                #[serde(rename = "inputSettings")]
+                settings: serde_json::Value,
+                /// The default settings for the input.
+                #[serde(rename = "defaultInputSettings")]
+                default_settings: serde_json::Value,
+            },
+            /// An input has been removed.
+            InputRemoved {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+            },
+            /// The name of an input has changed.
+            InputNameChanged {
+                /// Old name of the input.
+                #[serde(rename = "oldInputName")]
+                old_name: String,
+                /// New name of the input.
+                #[serde(rename = "inputName")]
+                new_name: String,
+            },
+            /// An input's active state has changed.
+            ///
+            /// When an input is active, it means it's being shown by the program feed.
+            InputActiveStateChanged {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+                /// Whether the input is active.
+                #[serde(rename = "videoActive")]
+                active: bool,
+            },
+            /// An input's show state has changed.
+            ///
+            /// When an input is showing, it means it's being shown by the preview or a dialog.
+            InputShowStateChanged {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+                /// Whether the input is showing.
+                #[serde(rename = "videoShowing")]
+                showing: bool,
+            },
+            /// An input's mute state has changed.
+            InputMuteStateChanged {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+                /// Whether the input is muted.
+                #[serde(rename = "inputMuted")]
+                muted: bool,
+            },
+            /// An input's volume level has changed.
+            InputVolumeChanged {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+                /// New volume level in `multimap`.
+                #[serde(rename = "inputVolumeMul")]
+                mul: f64,
+                /// New volume level in `dB`.
+                #[serde(rename = "inputVolumeDb")]
+                db: f64,
+            },
+            /// The audio balance value of an input has changed.
+            InputAudioBalanceChanged {
+                /// Name of the affected input.
+                #[serde(rename = "inputName")]
+                name: String,
+                /// New audio balance value of the input.
+                #[serde(rename = "inputAudioBalance")]
+                audio_balance: f64,
+            },
+            /// The sync offset of an input has changed.
+            InputAudioSyncOffsetChanged {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+                /// New sync offset in milliseconds.
+                #[serde(
+                    rename = "inputAudioSyncOffset",
+                    with = "crate::serde::duration_millis"
+                )]
+                offset: Duration,
+            },
+            /// The audio tracks of an input have changed.
+            InputAudioTracksChanged {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+                /// Object of audio tracks along with their associated enable states.
+                #[serde(rename = "inputAudioTracks")]
+                tracks: BTreeMap<String, bool>,
+            },
+            /// The monitor type of an input has changed.
+            InputAudioMonitorTypeChanged {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+                /// New monitor type of the input.
+                #[serde(rename = "monitorType")]
+                monitor_type: MonitorType,
+            },
+            /// A high-volume event providing volume levels of all active inputs every 50
+            /// milliseconds.
+            InputVolumeMeters {
+                /// Array of active inputs with their associated volume levels.
+                #[serde(rename = "inputs")]
+                inputs: Vec<InputVolumeMeter>,
+            },
+        }
+        /// Events of the `MediaInputs` category, about the playback of media inputs.
+        MEDIA_INPUTS => MediaInputEvent {
+            /// A media input has started playing.
+            MediaInputPlaybackStarted {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+            },
+            /// A media input has finished playing.
+            MediaInputPlaybackEnded {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+            },
+            /// An action has been performed on an input.
+            MediaInputActionTriggered {
+                /// Name of the input.
+                #[serde(rename = "inputName")]
+                name: String,
+                /// Action performed on the input.
+                #[serde(rename = "mediaAction")]
+                media_action: MediaAction,
+            },
+        }
+        /// Events of the `Outputs` category, like streaming and recording state changes.
+        OUTPUTS => OutputEvent {
+            /// The state of the stream output has changed.
+            StreamStateChanged {
+                /// Whether the output is active.
+                #[serde(rename = "outputActive")]
+                active: bool,
+                /// The specific state of the output.
+                #[serde(rename = "outputState")]
+                state: OutputState,
+            },
+            /// The state of the record output has changed.
+            RecordStateChanged {
+                /// Whether the output is active.
+                #[serde(rename = "outputActive")]
+                active: bool,
+                /// The specific state of the output.
+                #[serde(rename = "outputState")]
+                state: OutputState,
+                /// File name for the saved recording, if record stopped.
+                #[serde(rename = "outputPath")]
+                path: Option<String>,
+            },
+            /// The state of the replay buffer output has changed.
+            ReplayBufferStateChanged {
+                /// Whether the output is active.
+                #[serde(rename = "outputActive")]
+                active: bool,
+                /// The specific state of the output.
+                #[serde(rename = "outputState")]
+                state: OutputState,
+            },
+            /// The state of the virtual cam output has changed.
+            VirtualcamStateChanged {
+                /// Whether the output is active.
+                #[serde(rename = "outputActive")]
+                active: bool,
+                /// The specific state of the output.
+                #[serde(rename = "outputState")]
+                state: OutputState,
+            },
+            /// The replay buffer has been saved.
+            ReplayBufferSaved {
+                /// Path of the saved replay file.
+                #[serde(rename = "savedReplayPath")]
+                path: PathBuf,
+            },
+        }
+        /// Events of the `SceneItems` category, including its high-volume events.
+        SCENE_ITEMS => SceneItemEvent {
+            /// A scene item has been created.
+            SceneItemCreated {
+                /// Name of the scene the item was added to.
+                #[serde(rename = "sceneName")]
+                scene: String,
+                /// Name of the underlying source (input/scene).
+                #[serde(rename = "sourceName")]
+                source: String,
+                /// Numeric ID of the scene item.
+                #[serde(rename = "sceneItemId")]
+                item_id: u64,
+                /// Index position of the item.
+                #[serde(rename = "sceneItemIndex")]
+                index: u32,
+            },
+            /// A scene item has been removed.
+            ///
+            /// This event is not emitted when the scene the item is in is removed.
+            SceneItemRemoved {
+                /// Name of the scene the item was removed from.
+                #[serde(rename = "sceneName")]
+                scene: String,
+                /// Name of the underlying source (input/scene).
+                #[serde(rename = "sourceName")]
+                source: String,
+                /// Numeric ID of the scene item.
+                #[serde(rename = "sceneItemId")]
+                item_id: u64,
+            },
+            /// A scene's item list has been re-indexed.
+            SceneItemListReindexed {
+                /// Name of the scene.
+                #[serde(rename = "sceneName")]
+                scene: String,
+                /// Array of scene item objects.
+                #[serde(rename = "sceneItems")]
+                items: Vec<BasicSceneItem>,
+            },
+            /// A scene item's enable state has changed.
+            SceneItemEnableStateChanged {
+                /// Name of the scene the item is in.
+                #[serde(rename = "sceneName")]
+                scene: String,
+                /// Numeric ID of the scene item.
+                #[serde(rename = "sceneItemId")]
+                item_id: u64,
+                /// Whether the scene item is enabled (visible).
+                #[serde(rename = "sceneItemEnabled")]
+                enabled: bool,
+            },
+            /// A scene item's lock state has changed.
+            SceneItemLockStateChanged {
+                /// Name of the scene the item is in.
+                #[serde(rename = "sceneName")]
+                scene: String,
+                /// Numeric ID of the scene item.
+                #[serde(rename = "sceneItemId")]
+                item_id: u64,
+                /// Whether the scene item is locked.
+                #[serde(rename = "sceneItemLocked")]
+                locked: bool,
+            },
+            /// A scene item has been selected in the UI.
+            SceneItemSelected {
+                /// Name of the scene the item is in.
+                #[serde(rename = "sceneName")]
+                scene: String,
+                /// Numeric ID of the scene item.
+                #[serde(rename = "sceneItemId")]
+                item_id: u64,
+            },
+            /// The transform/crop of a scene item has changed.
+            SceneItemTransformChanged {
+                /// The name of the scene the item is in.
+                #[serde(rename = "sceneName")]
+                scene: String,
+                /// Numeric ID of the scene item.
+                #[serde(rename = "sceneItemId")]
+                item_id: u64,
+                /// New transform/crop info of the scene item.
+                #[serde(rename = "sceneItemTransform")]
+                transform: SceneItemTransform,
+            },
+        }
+        /// Events of the `Scenes` category.
+        SCENES => SceneEvent {
+            /// A new scene has been created.
+            SceneCreated {
+                /// Name of the new scene.
+                #[serde(rename = "sceneName")]
+                name: String,
+                /// Whether the new scene is a group.
+                #[serde(rename = "isGroup")]
+                is_group: bool,
+            },
+            /// A scene has been removed.
+            SceneRemoved {
+                /// Name of the removed scene.
+                #[serde(rename = "sceneName")]
+                name: String,
+                /// Whether the scene was a group.
+                #[serde(rename = "isGroup")]
+                is_group: bool,
+            },
+            /// The name of a scene has changed.
+            SceneNameChanged {
+                /// Old name of the scene.
+                #[serde(rename = "oldSceneName")]
+                old_name: String,
+                /// New name of the scene.
+                #[serde(rename = "sceneName")]
+                new_name: String,
+            },
+            /// The current program scene has changed.
+            CurrentProgramSceneChanged {
+                /// Name of the scene that was switched to.
+                #[serde(rename = "sceneName")]
+                name: String,
+            },
+            /// The current preview scene has changed.
+            CurrentPreviewSceneChanged {
+                /// Name of the scene that was switched to.
+                #[serde(rename = "sceneName")]
+                name: String,
+            },
+            /// The list of scenes has changed.
+            SceneListChanged {
+                /// Updated array of scenes.
+                scenes: Vec<Scene>,
+            },
+        }
+        /// Events of the `Transitions` category, about scene transitions.
+        TRANSITIONS => TransitionEvent {
+            /// The current scene transition has changed.
+            CurrentSceneTransitionChanged {
+                /// Name of the new transition.
+                #[serde(rename = "transitionName")]
+                name: String,
+            },
+            /// The current scene transition duration has changed.
+            CurrentSceneTransitionDurationChanged {
+                /// Transition duration in milliseconds.
+                #[serde(rename = "transitionDuration", with = "crate::serde::duration_millis")]
+                duration: Duration,
+            },
+            /// A scene transition has started.
+            SceneTransitionStarted {
+                /// Scene transition name.
+                #[serde(rename = "transitionName")]
+                name: String,
+            },
+            /// A scene transition has completed fully.
+            ///
+            /// **Note:** Does not appear to trigger when the transition is interrupted by the user.
+            SceneTransitionEnded {
+                /// Scene transition name.
+                #[serde(rename = "transitionName")]
+                name: String,
+            },
+            /// A scene transition's video has completed fully.
+            ///
+            /// Useful for stinger transitions to tell when the video *actually* ends.
+            /// [`Self::SceneTransitionEnded`] only signifies the cut point, not the completion of
+            /// transition playback.
+            ///
+            /// **Note:** Appears to be called by every transition, regardless of relevance.
+            SceneTransitionVideoEnded {
+                /// Scene transition name.
+                #[serde(rename = "transitionName")]
+                name: String,
+            },
+        }
+        /// Events of the `Ui` category, about the user interface of OBS.
+        UI => UiEvent {
+            /// Studio mode has been enabled or disabled.
+            StudioModeStateChanged {
+                /// Whether the studio mode is enabled.
+                #[serde(rename = "studioModeEnabled")]
+                enabled: bool,
+            },
+        }
+        // Events that are generated by this crate instead of obs-websocket, and have no category.
+        // They have no wire format, so serializing them fails.
+        _ => {
+            /// Web-socket server is stopping.
+            #[serde(skip_serializing)]
+            ServerStopping,
+            /// Web-socket server has stopped.
+            #[serde(skip_serializing)]
+            ServerStopped,
+            /// The listener fell behind and missed the given amount of events, that were dropped
+            /// before it could receive them. The stream continues with the oldest event that is
+            /// still available.
+            ///
+            /// This is only emitted by [`Client::events`](crate::Client::events), as the streams of
+            /// [`Client::events_unbounded`](crate::Client::events_unbounded) never drop events.
+            #[serde(skip_serializing)]
+            Lagged(u64),
+            /// Fallback for any unknown event type, like events that were added in a newer version
+            /// of obs-websocket.
+            #[serde(untagged)]
+            Unknown {
+                /// Name of the event.
+                #[serde(rename = "eventType")]
+                event_type: String,
+                /// Raw data of the event. [`serde_json::Value::Null`] if the event doesn't carry
+                /// any data.
+                #[serde(
+                    rename = "eventData",
+                    default,
+                    skip_serializing_if = "serde_json::Value::is_null"
+                )]
+                data: serde_json::Value,
+            },
+        }
+    }
+}
+
+/// Events are deserialized through the enum of their category, so that only unknown event types
+/// end up as [`Event::Unknown`], while known events with invalid data fail.
+///
+/// The data is deserialized right away, if the event type comes first as sent by obs-websocket,
+/// and only buffered otherwise.
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(EventVisitor)
+    }
+}
+
+struct EventVisitor;
+
+impl<'de> Visitor<'de> for EventVisitor {
+    type Value = Event;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str("an event with `eventType` and optional `eventData`")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        #[derive(Deserialize)]
+        enum Field {
+            #[serde(rename = "eventType")]
+            Type,
+            #[serde(rename = "eventData")]
+            Data,
+            #[serde(other)]
+            Other,
+        }
+
+        let mut event_type = None::<String>;
+        let mut buffered = None::<serde_json::Value>;
+        let mut event = None;
+
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Type if event_type.is_some() => {
+                    return Err(de::Error::duplicate_field("eventType"));
+                }
+                Field::Type => event_type = Some(map.next_value()?),
+                Field::Data if event.is_some() || buffered.is_some() => {
+                    return Err(de::Error::duplicate_field("eventData"));
+                }
+                Field::Data => match &event_type {
+                    Some(event_type) => event = Some(map.next_value_seed(EventData(event_type))?),
+                    None => buffered = Some(map.next_value()?),
+                },
+                Field::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        let event_type = event_type.ok_or_else(|| de::Error::missing_field("eventType"))?;
+
+        match event {
+            Some(event) => Ok(event),
+            None => EventData(&event_type)
+                .deserialize(buffered.unwrap_or_default())
+                .map_err(de::Error::custom),
+        }
+    }
+}
+
+/// Seed to deserialize the data of an event, once its type is known.
+struct EventData<'a>(&'a str);
+
+impl<'de, 'a> DeserializeSeed<'de> for EventData<'a> {
+    type Value = Event;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Event::deserialize_known(self.0, deserializer) {
+            Ok(event) => event,
+            Err(deserializer) => Ok(Event::Unknown {
+                event_type: self.0.to_owned(),
+                data: serde_json::Value::deserialize(deserializer)?,
+            }),
+        }
+    }
+}
+
+/// Deserializer, that presents the type and data of an event as a sequence of both. That is one
+/// of the forms, that adjacently tagged enums accept, so the data can be passed on as-is.
+struct TaggedData<'a, D> {
+    event_type: Option<&'a str>,
+    data: Option<D>,
+}
+
+impl<'de, 'a, D> Deserializer<'de> for TaggedData<'a, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+impl<'de, 'a, D> SeqAccess<'de> for TaggedData<'a, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if let Some(event_type) = self.event_type.take() {
+            return seed
+                .deserialize(IntoDeserializer::<D::Error>::into_deserializer(event_type))
+                .map(Some);
+        }
+
+        self.data
+            .take()
+            .map(|data| seed.deserialize(data))
+            .transpose()
+    }
+}
+
+impl Event {
+    /// Get the event subscription, that is needed to receive this event. That is the category of
+    /// the event, or the flag of its own for vendor and high-volume events.
     ///
-    /// **Note:** Appears to be called by every transition, regardless of relevance.
-    SceneTransitionVideoEnded {
-        /// Scene transition name.
-        #[serde(rename = "transitionName")]
-        name: String,
-    },
-    // --------------------------------
-    // UI
-    // --------------------------------
-    /// Studio mode has been enabled or disabled.
-    StudioModeStateChanged {
-        /// Whether the studio mode is enabled.
-        #[serde(rename = "studioModeEnabled")]
-        enabled: bool,
-    },
-    // --------------------------------
-    // Custom
-    // --------------------------------
-    /// Web-socket server is stopping.
-    ServerStopping,
-    /// Web-socket server has stopped.
-    ServerStopped,
-    /// Fallback value for any unknown event type.
-    #[serde(other)]
-    Unknown,
+    /// Events that are generated by this crate instead of obs-websocket, like
+    /// [`Self::ServerStopped`], as well as unknown events, return [`EventSubscription::NONE`].
+    pub fn category(&self) -> EventSubscription {
+        match self {
+            Self::VendorEvent { .. } => EventSubscription::VENDORS,
+            Self::InputVolumeMeters { .. } => EventSubscription::INPUT_VOLUME_METERS,
+            Self::InputActiveStateChanged { .. } => EventSubscription::INPUT_ACTIVE_STATE_CHANGED,
+            Self::InputShowStateChanged { .. } => EventSubscription::INPUT_SHOW_STATE_CHANGED,
+            Self::SceneItemTransformChanged { .. } => {
+                EventSubscription::SCENE_ITEM_TRANSFORM_CHANGED
+            }
+            _ => self.group(),
+        }
+    }
+
+    /// Whether this is a high-volume event, that is only received when explicitly subscribing to
+    /// it, as it's not part of [`EventSubscription::ALL`].
+    pub fn is_high_volume(&self) -> bool {
+        let category = self.category();
+        !category.is_empty() && !EventSubscription::ALL.contains(category)
+    }
 }
 
 /// Volume meter information for a single input, describing the current volume level.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct InputVolumeMeter {
     /// Name of this input.
     #[serde(rename = "inputName")]
@@ -556,7 +865,7 @@ pub struct InputVolumeMeter {
 }
 
 /// The output state describes the current status of any output (like recording, virtual-cam, ...).
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum OutputState {
     /// A request to start the output has been issued.
     #[serde(rename = "OBS_WEBSOCKET_OUTPUT_STARTING")]
@@ -576,13 +885,13 @@ pub enum OutputState {
     /// Current output resumed.
     #[serde(rename = "OBS_WEBSOCKET_OUTPUT_RESUMED")]
     Resumed,
-    /// Fallback for any unknown event type.
-    #[serde(other)]
-    Unknown,
+    /// Fallback for any unknown output state, containing its raw value.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// A basic scene item, only describing identifier and position.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct BasicSceneItem {
     /// Identifier of this scene item.
     #[serde(rename = "sceneItemId")]
@@ -593,7 +902,7 @@ pub struct BasicSceneItem {
 }
 
 /// The scene describes basic details about a single scene setup in OBS.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Scene {
     /// Name of this scene.
     #[serde(rename = "sceneName")]
@@ -602,3 +911,279 @@ pub struct Scene {
     #[serde(rename = "sceneIndex")]
     pub index: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// Deserialize the payload into an event and serialize it again, which must result in the
+    /// same payload.
+    fn roundtrip(payload: Value) -> Event {
+        let event = serde_json::from_value::<Event>(payload.clone()).unwrap();
+        assert_eq!(payload, serde_json::to_value(&event).unwrap());
+        event
+    }
+
+    #[test]
+    fn serialize() {
+        let payloads = [
+            json!({
+                "eventType": "CurrentSceneCollectionChanging",
+                "eventData": { "sceneCollectionName": "Untitled" },
+            }),
+            json!({
+                "eventType": "SourceFilterListReindexed",
+                "eventData": {
+                    "sourceName": "Camera",
+                    "filters": [{
+                        "filterEnabled": true,
+                        "filterIndex": 0,
+                        "filterKind": "color_filter_v2",
+                        "filterName": "Color",
+                        "filterSettings": { "gamma": 0.5 },
+                    }],
+                },
+            }),
+            json!({
+                "eventType": "CustomEvent",
+                "eventData": { "hello": "world" },
+            }),
+            json!({ "eventType": "ExitStarted" }),
+            json!({
+                "eventType": "VendorEvent",
+                "eventData": {
+                    "vendorName": "vendor",
+                    "eventType": "custom",
+                    "eventData": {},
+                },
+            }),
+            json!({
+                "eventType": "InputAudioSyncOffsetChanged",
+                "eventData": { "inputName": "Mic", "inputAudioSyncOffset": 250 },
+            }),
+            json!({
+                "eventType": "InputAudioTracksChanged",
+                "eventData": {
+                    "inputName": "Mic",
+                    "inputAudioTracks": { "1": true, "2": false },
+                },
+            }),
+            json!({
+                "eventType": "InputAudioMonitorTypeChanged",
+                "eventData": {
+                    "inputName": "Mic",
+                    "monitorType": "OBS_MONITORING_TYPE_MONITOR_ONLY",
+                },
+            }),
+            json!({
+                "eventType": "InputVolumeMeters",
+                "eventData": {
+                    "inputs": [{ "inputName": "Mic", "inputLevelsMul": [[0.5, 0.25, 0.0]] }],
+                },
+            }),
+            json!({
+                "eventType": "MediaInputActionTriggered",
+                "eventData": {
+                    "inputName": "Media",
+                    "mediaAction": "OBS_WEBSOCKET_MEDIA_INPUT_ACTION_PAUSE",
+                },
+            }),
+            json!({
+                "eventType": "RecordStateChanged",
+                "eventData": {
+                    "outputActive": false,
+                    "outputState": "OBS_WEBSOCKET_OUTPUT_STOPPED",
+                    "outputPath": "/tmp/recording.mkv",
+                },
+            }),
+            json!({
+                "eventType": "SceneItemListReindexed",
+                "eventData": {
+                    "sceneName": "Main",
+                    "sceneItems": [{ "sceneItemId": 3, "sceneItemIndex": 0 }],
+                },
+            }),
+            json!({
+                "eventType": "SceneItemTransformChanged",
+                "eventData": {
+                    "sceneName": "Main",
+                    "sceneItemId": 3,
+                    "sceneItemTransform": {
+                        "sourceWidth": 1920.0,
+                        "sourceHeight": 1080.0,
+                        "positionX": 0.0,
+                        "positionY": 0.0,
+                        "rotation": 0.0,
+                        "scaleX": 0.5,
+                        "scaleY": 0.5,
+                        "width": 960.0,
+                        "height": 540.0,
+                        "alignment": 5,
+                        "boundsType": "OBS_BOUNDS_NONE",
+                        "boundsAlignment": 0,
+                        "boundsWidth": 0.0,
+                        "boundsHeight": 0.0,
+                        "cropLeft": 0,
+                        "cropRight": 0,
+                        "cropTop": 0,
+                        "cropBottom": 0,
+                    },
+                },
+            }),
+            json!({
+                "eventType": "SceneListChanged",
+                "eventData": {
+                    "scenes": [{ "sceneName": "Main", "sceneIndex": 0 }],
+                },
+            }),
+            json!({
+                "eventType": "CurrentSceneTransitionDurationChanged",
+                "eventData": { "transitionDuration": 300 },
+            }),
+            json!({
+                "eventType": "StudioModeStateChanged",
+                "eventData": { "studioModeEnabled": true },
+            }),
+        ];
+
+        for payload in payloads {
+            roundtrip(payload);
+        }
+    }
+
+    #[test]
+    fn serialize_category() {
+        let payload = json!({
+            "eventType": "SceneCreated",
+            "eventData": { "sceneName": "Main", "isGroup": false },
+        });
+
+        let event = SceneEvent::try_from(roundtrip(payload.clone())).unwrap();
+        assert_eq!(payload, serde_json::to_value(&event).unwrap());
+    }
+
+    #[test]
+    fn deserialize_fields() {
+        // Fields in the order of obs-websocket, which allows to parse the data right away.
+        let event = serde_json::from_str::<Event>(concat!(
+            r#"{"eventType":"SceneCreated","eventIntent":4,"#,
+            r#""eventData":{"sceneName":"Main","isGroup":false}}"#,
+        ))
+        .unwrap();
+        assert!(matches!(event, Event::SceneCreated { ref name, .. } if name == "Main"));
+
+        let event = serde_json::from_str::<Event>(
+            r#"{"eventData":{"sceneName":"Main","isGroup":true},"eventType":"SceneCreated"}"#,
+        )
+        .unwrap();
+        assert!(matches!(event, Event::SceneCreated { is_group: true, .. }));
+
+        let event = serde_json::from_str::<Event>(r#"{"eventType":"ExitStarted"}"#).unwrap();
+        assert!(matches!(event, Event::ExitStarted));
+
+        assert!(serde_json::from_str::<Event>(r#"{"eventType":"SceneCreated"}"#).is_err());
+        assert!(serde_json::from_str::<Event>(r#"{"eventData":{}}"#).is_err());
+    }
+
+    #[test]
+    fn serialize_generated() {
+        for event in [
+            Event::ServerStopping,
+            Event::ServerStopped,
+            Event::Lagged(5),
+        ] {
+            assert!(serde_json::to_value(&event).is_err());
+        }
+
+        assert!(matches!(
+            serde_json::from_value(json!({ "eventType": "ServerStopped" })).unwrap(),
+            Event::Unknown { ref event_type, .. } if event_type == "ServerStopped"
+        ));
+    }
+
+    #[test]
+    fn unknown() {
+        let payload = json!({
+            "eventType": "SomeFutureEvent",
+            "eventData": { "answer": 42 },
+        });
+        let event = roundtrip(payload);
+        assert!(matches!(
+            event,
+            Event::Unknown { ref event_type, ref data }
+                if event_type == "SomeFutureEvent" && data["answer"] == 42
+        ));
+        assert_eq!(EventSubscription::NONE, event.category());
+
+        let event = roundtrip(json!({ "eventType": "SomeFutureEvent" }));
+        assert!(matches!(
+            event,
+            Event::Unknown {
+                data: Value::Null,
+                ..
+            }
+        ));
+
+        let payload = json!({
+            "eventType": "SceneCreated",
+            "eventData": { "sceneName": 5 },
+        });
+        assert!(serde_json::from_value::<Event>(payload).is_err());
+
+        let payload = json!({
+            "eventType": "RecordStateChanged",
+            "eventData": {
+                "outputActive": true,
+                "outputState": "OBS_WEBSOCKET_OUTPUT_FUTURE",
+                "outputPath": null,
+            },
+        });
+        assert!(matches!(
+            roundtrip(payload),
+            Event::RecordStateChanged {
+                state: OutputState::Unknown(ref state),
+                ..
+            } if state == "OBS_WEBSOCKET_OUTPUT_FUTURE"
+        ));
+    }
+
+    #[test]
+    fn category() {
+        let event = Event::SceneCreated {
+            name: "Main".to_owned(),
+            is_group: false,
+        };
+        assert_eq!(EventSubscription::SCENES, event.category());
+        assert!(!event.is_high_volume());
+
+        let event = Event::InputVolumeMeters { inputs: Vec::new() };
+        assert_eq!(EventSubscription::INPUT_VOLUME_METERS, event.category());
+        assert!(event.is_high_volume());
+
+        let event = Event::VendorEvent {
+            vendor_name: "vendor".to_owned(),
+            event_type: "custom".to_owned(),
+            event_data: serde_json::Value::Null,
+        };
+        assert_eq!(EventSubscription::VENDORS, event.category());
+        assert!(!event.is_high_volume());
+
+        assert_eq!(EventSubscription::NONE, Event::ServerStopped.category());
+        assert!(!Event::ServerStopped.is_high_volume());
+    }
+
+    #[test]
+    fn convert() {
+        let event = Event::CustomEvent(serde_json::json!({ "hello": "world" }));
+        let general = GeneralEvent::try_from(event).unwrap();
+        assert!(matches!(
+            Event::from(general),
+            Event::CustomEvent(value) if value["hello"] == "world"
+        ));
+
+        let event = SceneEvent::try_from(Event::ExitStarted).unwrap_err();
+        assert!(matches!(event, Event::ExitStarted));
+    }
+}
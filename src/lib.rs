@@ -34,11 +34,15 @@
 
 #![warn(missing_docs, rust_2018_idioms, clippy::all)]
 
+use requests::ExecutionType;
 use responses::StatusCode;
+#[cfg(feature = "tls")]
+pub use rustls;
 pub use semver::{Comparator, Version};
 
 pub use self::client::Client;
 
+pub mod animation;
 pub mod client;
 pub mod common;
 #[cfg(doc)]
@@ -47,6 +51,8 @@ pub mod docs;
 pub mod events;
 pub mod requests;
 pub mod responses;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 mod serde;
 
@@ -58,7 +64,15 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 pub enum Error {
     /// An error occurred while trying to connect to the web-socket.
     #[error("failed to connect to the obs-websocket plugin")]
-    Connect(#[source] tokio_tungstenite::tungstenite::Error),
+    Connect(#[source] Box<tokio_tungstenite::tungstenite::Error>),
+    /// The TLS settings for the connection could not be set up.
+    #[cfg(feature = "tls")]
+    #[error("failed to set up TLS")]
+    Tls(#[from] crate::client::TlsError),
+    /// The connection details could not be determined from a URL, the environment or the local
+    /// OBS configuration.
+    #[error("failed to discover the connection details")]
+    Discovery(#[from] crate::client::DiscoveryError),
     /// The initial handshake with `obs-websocket` didn't succeed.
     #[error("failed to execute the handshake with obs-websocket")]
     Handshake(#[from] crate::client::HandshakeError),
@@ -67,10 +81,60 @@ pub enum Error {
     SerializeMessage(#[source] serde_json::Error),
     /// A message could not be send through the web-socket.
     #[error("failed to send message to the obs-websocket plugin")]
-    Send(#[source] tokio_tungstenite::tungstenite::Error),
+    Send(#[source] Box<tokio_tungstenite::tungstenite::Error>),
     /// Tried to receive data while the send side was already closed.
+    #[deprecated(
+        since = "0.10.0",
+        note = "requests left without response now fail with `Error::Disconnected` instead"
+    )]
     #[error("send side is closed")]
     ReceiveMessage(#[source] tokio::sync::oneshot::error::RecvError),
+    /// A request in a batch was not executed, because the batch was halted after a previous
+    /// request failed.
+    #[error("request was not executed, because the batch was halted")]
+    BatchHalted,
+    /// An item of a request batch didn't make exactly one request right away, so its requests
+    /// can't be part of the batch.
+    #[error("batch items must make a single request, without awaiting anything else before")]
+    InvalidBatchItem,
+    /// The result of a batch item was requested from the results of a different batch.
+    #[error("batch item belongs to a different batch")]
+    ForeignBatchItem,
+    /// A sleep request was made outside of a request batch.
+    #[error("sleep requests are only allowed within request batches")]
+    SleepOutsideBatch,
+    /// A sleep request doesn't match the execution type of its request batch.
+    #[error("sleep request doesn't match the batch execution type {0:?}")]
+    SleepExecutionType(ExecutionType),
+    /// The response to a request didn't arrive in time.
+    #[error("request `{request_type}` timed out")]
+    Timeout {
+        /// Name of the request that timed out.
+        request_type: String,
+    },
+    /// A [`RequestInterceptor`](crate::client::RequestInterceptor) vetoed the request, so it was
+    /// never sent.
+    #[error("request `{request_type}` was vetoed: {reason}")]
+    Vetoed {
+        /// Name of the request that was vetoed.
+        request_type: String,
+        /// Reason given by the interceptor.
+        reason: String,
+    },
+    /// A coalesced setter was superseded by a newer call for the same target, so it was never
+    /// sent. This is only returned by [`Client::raw_request`] and [`Client::raw_request_as`], as
+    /// the typed setters succeed in that case.
+    #[error("request `{request_type}` was superseded by a newer value")]
+    Superseded {
+        /// Name of the request that was superseded.
+        request_type: String,
+    },
+    /// The connected obs-websocket instance doesn't support the request, so it was never sent.
+    #[error("request `{request_type}` is not supported by obs-websocket")]
+    Unsupported {
+        /// Name of the unsupported request.
+        request_type: String,
+    },
     /// Failed to deserialize the message that came back as response.
     #[error("the response message could not be deserialized")]
     DeserializeResponse(#[source] serde_json::Error),
@@ -94,8 +158,11 @@ pub enum Error {
     /// Unknown flags were found while trying to parse bitflags.
     #[error("value {0} contains unknown flags")]
     UnknownFlags(u8),
+    /// The web-socket close code is not one of the codes defined by obs-websocket.
+    #[error("value {0} is not a known obs-websocket close code")]
+    UnknownCloseCode(u16),
     /// Tried to interact with obs-websocket while not connected (for example trying to get a new
-    /// event stream).
+    /// event stream), or the connection was closed before the response to a request arrived.
     #[error("currently not connected to obs-websocket")]
     Disconnected,
     /// The OBS studio version of the connected instance doesn't match the required version for this
@@ -114,3 +181,31 @@ pub enum Error {
         negotiated: u32,
     },
 }
+
+impl Error {
+    /// Get the status code, if this error was returned from the obs-websocket API.
+    pub fn status_code(&self) -> Option<StatusCode> {
+        match self {
+            Self::Api { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Whether the error is likely transient, so that sending the same request again might
+    /// succeed.
+    ///
+    /// This is the case for lost connections and timeouts, as well as API errors that usually
+    /// only happen while OBS is busy, like switching the scene collection or stopping an output.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Send(_) | Self::Timeout { .. } | Self::Disconnected => true,
+            Self::Api { code, .. } => matches!(
+                code,
+                StatusCode::OutputRunning
+                    | StatusCode::ResourceActionFailed
+                    | StatusCode::RequestProcessingFailed
+            ),
+            _ => false,
+        }
+    }
+}
@@ -3,37 +3,39 @@
 use serde::Serialize;
 use serde_with::skip_serializing_none;
 
-#[derive(Serialize)]
-#[serde(tag = "requestType", content = "requestData")]
-pub(crate) enum Request<'a> {
-    #[serde(rename = "GetPersistentData")]
-    GetPersistentData {
-        /// The data realm to select.
-        #[serde(rename = "realm")]
-        realm: Realm,
-        /// The name of the slot to retrieve data from.
-        #[serde(rename = "slotName")]
-        slot_name: &'a str,
-    },
-    #[serde(rename = "SetPersistentData")]
-    SetPersistentData(SetPersistentData<'a>),
-    #[serde(rename = "GetVideoSettings")]
-    VideoSettings,
-    #[serde(rename = "SetVideoSettings")]
-    SetVideoSettings(SetVideoSettings),
-    #[serde(rename = "GetStreamServiceSettings")]
-    StreamServiceSettings,
-    #[serde(rename = "SetStreamServiceSettings")]
-    SetStreamServiceSettings {
-        /// Type of stream service to apply. Example: `rtmp_common` or `rtmp_custom`.
-        #[serde(rename = "streamServiceType")]
-        r#type: &'a str,
-        /// Settings to apply to the service.
-        #[serde(rename = "streamServiceSettings")]
-        settings: serde_json::Value,
-    },
-    #[serde(rename = "GetRecordDirectory")]
-    RecordDirectory,
+requests! {
+    #[derive(Serialize)]
+    #[serde(tag = "requestType", content = "requestData")]
+    pub(crate) enum Request<'a> {
+        #[serde(rename = "GetPersistentData")]
+        GetPersistentData {
+            /// The data realm to select.
+            #[serde(rename = "realm")]
+            realm: Realm,
+            /// The name of the slot to retrieve data from.
+            #[serde(rename = "slotName")]
+            slot_name: &'a str,
+        },
+        #[serde(rename = "SetPersistentData")]
+        SetPersistentData(SetPersistentData<'a>),
+        #[serde(rename = "GetVideoSettings")]
+        VideoSettings,
+        #[serde(rename = "SetVideoSettings")]
+        SetVideoSettings(SetVideoSettings),
+        #[serde(rename = "GetStreamServiceSettings")]
+        StreamServiceSettings,
+        #[serde(rename = "SetStreamServiceSettings")]
+        SetStreamServiceSettings {
+            /// Type of stream service to apply. Example: `rtmp_common` or `rtmp_custom`.
+            #[serde(rename = "streamServiceType")]
+            r#type: &'a str,
+            /// Settings to apply to the service.
+            #[serde(rename = "streamServiceSettings")]
+            settings: serde_json::Value,
+        },
+        #[serde(rename = "GetRecordDirectory")]
+        RecordDirectory,
+    }
 }
 
 impl<'a> From<Request<'a>> for super::RequestType<'a> {
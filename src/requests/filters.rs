@@ -3,49 +3,51 @@
 use serde::Serialize;
 use serde_with::skip_serializing_none;
 
-#[derive(Serialize)]
-#[serde(tag = "requestType", content = "requestData")]
-pub(crate) enum Request<'a> {
-    #[serde(rename = "GetSourceFilterList")]
-    List {
-        /// Name of the source.
-        #[serde(rename = "sourceName")]
-        source: &'a str,
-    },
-    #[serde(rename = "GetSourceFilterDefaultSettings")]
-    DefaultSettings {
-        /// Filter kind to get the default settings for.
-        #[serde(rename = "filterKind")]
-        kind: &'a str,
-    },
-    #[serde(rename = "CreateSourceFilter")]
-    Create(CreateInternal<'a>),
-    #[serde(rename = "RemoveSourceFilter")]
-    Remove {
-        /// Name of the source the filter is on.
-        #[serde(rename = "sourceName")]
-        source: &'a str,
-        /// Name of the filter to remove.
-        #[serde(rename = "filterName")]
-        filter: &'a str,
-    },
-    #[serde(rename = "SetSourceFilterName")]
-    SetName(SetName<'a>),
-    #[serde(rename = "GetSourceFilter")]
-    Get {
-        /// Name of the source.
-        #[serde(rename = "sourceName")]
-        source: &'a str,
-        /// Name of the filter.
-        #[serde(rename = "filterName")]
-        filter: &'a str,
-    },
-    #[serde(rename = "SetSourceFilterIndex")]
-    SetIndex(SetIndex<'a>),
-    #[serde(rename = "SetSourceFilterSettings")]
-    SetSettings(SetSettingsInternal<'a>),
-    #[serde(rename = "SetSourceFilterEnabled")]
-    SetEnabled(SetEnabled<'a>),
+requests! {
+    #[derive(Serialize)]
+    #[serde(tag = "requestType", content = "requestData")]
+    pub(crate) enum Request<'a> {
+        #[serde(rename = "GetSourceFilterList")]
+        List {
+            /// Name of the source.
+            #[serde(rename = "sourceName")]
+            source: &'a str,
+        },
+        #[serde(rename = "GetSourceFilterDefaultSettings")]
+        DefaultSettings {
+            /// Filter kind to get the default settings for.
+            #[serde(rename = "filterKind")]
+            kind: &'a str,
+        },
+        #[serde(rename = "CreateSourceFilter")]
+        Create(CreateInternal<'a>),
+        #[serde(rename = "RemoveSourceFilter")]
+        Remove {
+            /// Name of the source the filter is on.
+            #[serde(rename = "sourceName")]
+            source: &'a str,
+            /// Name of the filter to remove.
+            #[serde(rename = "filterName")]
+            filter: &'a str,
+        },
+        #[serde(rename = "SetSourceFilterName")]
+        SetName(SetName<'a>),
+        #[serde(rename = "GetSourceFilter")]
+        Get {
+            /// Name of the source.
+            #[serde(rename = "sourceName")]
+            source: &'a str,
+            /// Name of the filter.
+            #[serde(rename = "filterName")]
+            filter: &'a str,
+        },
+        #[serde(rename = "SetSourceFilterIndex")]
+        SetIndex(SetIndex<'a>),
+        #[serde(rename = "SetSourceFilterSettings")]
+        SetSettings(SetSettingsInternal<'a>),
+        #[serde(rename = "SetSourceFilterEnabled")]
+        SetEnabled(SetEnabled<'a>),
+    }
 }
 
 impl<'a> From<Request<'a>> for super::RequestType<'a> {
@@ -1,23 +1,28 @@
 //! General requests, not fitting into any category.
 
 use serde::Serialize;
+use time::Duration;
 
-#[derive(Serialize)]
-#[serde(tag = "requestType", content = "requestData")]
-pub(crate) enum Request<'a> {
-    #[serde(rename = "GetVersion")]
-    Version,
-    #[serde(rename = "GetStats")]
-    Stats,
-    #[serde(rename = "BroadcastCustomEvent")]
-    BroadcastCustomEvent {
-        /// Data payload to emit to all receivers.
-        #[serde(rename = "eventData")]
-        event_data: serde_json::Value,
-    },
-    #[serde(rename = "CallVendorRequest")]
-    CallVendorRequest(CallVendorRequestInternal<'a>),
-    // TODO: Sleep
+requests! {
+    #[allow(clippy::enum_variant_names)]
+    #[derive(Serialize)]
+    #[serde(tag = "requestType", content = "requestData")]
+    pub(crate) enum Request<'a> {
+        #[serde(rename = "GetVersion")]
+        Version,
+        #[serde(rename = "GetStats")]
+        Stats,
+        #[serde(rename = "BroadcastCustomEvent")]
+        BroadcastCustomEvent {
+            /// Data payload to emit to all receivers.
+            #[serde(rename = "eventData")]
+            event_data: serde_json::Value,
+        },
+        #[serde(rename = "CallVendorRequest")]
+        CallVendorRequest(CallVendorRequestInternal<'a>),
+        #[serde(rename = "Sleep")]
+        Sleep(Sleep),
+    }
 }
 
 impl<'a> From<Request<'a>> for super::RequestType<'a> {
@@ -49,3 +54,16 @@ pub(crate) struct CallVendorRequestInternal<'a> {
     #[serde(rename = "requestData")]
     pub request_data: serde_json::Value,
 }
+
+/// Request information for [`crate::client::General::sleep`].
+#[derive(Clone, Copy, Debug, Serialize)]
+pub enum Sleep {
+    /// Sleep for the given duration. Only allowed in batches of type
+    /// [`SerialRealtime`](super::ExecutionType::SerialRealtime).
+    #[serde(rename = "sleepMillis", with = "crate::serde::duration_millis")]
+    Millis(Duration),
+    /// Sleep for the given amount of frames. Only allowed in batches of type
+    /// [`SerialFrame`](super::ExecutionType::SerialFrame).
+    #[serde(rename = "sleepFrames")]
+    Frames(u32),
+}
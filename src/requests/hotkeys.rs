@@ -2,27 +2,28 @@
 
 use serde::Serialize;
 
-#[derive(Serialize)]
-#[serde(tag = "requestType", content = "requestData")]
-pub(crate) enum Request<'a> {
-    #[serde(rename = "GetHotkeyList")]
-    List,
-    #[serde(rename = "TriggerHotkeyByName")]
-    TriggerByName {
-        /// Name of the hotkey to trigger.
-        #[serde(rename = "hotkeyName")]
-        name: &'a str,
-    },
-    #[serde(rename = "TriggerHotkeyByKeySequence")]
-    TriggerBySequence {
-        /// The OBS key ID to use.
-        #[serde(rename = "keyId")]
-        id: &'a str,
-        /// Object containing key modifiers to apply.
-        #[serde(rename = "keyModifiers")]
-        modifiers: KeyModifiers,
-    },
-    // TODO: Sleep
+requests! {
+    #[derive(Serialize)]
+    #[serde(tag = "requestType", content = "requestData")]
+    pub(crate) enum Request<'a> {
+        #[serde(rename = "GetHotkeyList")]
+        List,
+        #[serde(rename = "TriggerHotkeyByName")]
+        TriggerByName {
+            /// Name of the hotkey to trigger.
+            #[serde(rename = "hotkeyName")]
+            name: &'a str,
+        },
+        #[serde(rename = "TriggerHotkeyByKeySequence")]
+        TriggerBySequence {
+            /// The OBS key ID to use.
+            #[serde(rename = "keyId")]
+            id: &'a str,
+            /// Object containing key modifiers to apply.
+            #[serde(rename = "keyModifiers")]
+            modifiers: KeyModifiers,
+        },
+    }
 }
 
 impl<'a> From<Request<'a>> for super::RequestType<'a> {
@@ -6,171 +6,173 @@ use time::Duration;
 
 use crate::common::MonitorType;
 
-#[derive(Serialize)]
-#[serde(tag = "requestType", content = "requestData")]
-pub(crate) enum Request<'a> {
-    #[serde(rename = "GetInputList")]
-    List {
-        /// Restrict the array to only inputs of the specified kind.
-        #[serde(rename = "inputKind", skip_serializing_if = "Option::is_none")]
-        kind: Option<&'a str>,
-    },
-    #[serde(rename = "GetInputKindList")]
-    ListKinds {
-        /// Return all kinds as unversioned or with version suffixes (if available).
-        #[serde(rename = "unversioned")]
-        unversioned: bool,
-    },
-    #[serde(rename = "GetSpecialInputs")]
-    Specials,
-    #[serde(rename = "GetInputDefaultSettings")]
-    DefaultSettings {
-        /// Input kind to get the default settings for.
-        #[serde(rename = "inputKind")]
-        kind: &'a str,
-    },
-    #[serde(rename = "GetInputSettings")]
-    Settings {
-        /// Name of the input to get the settings of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-    },
-    #[serde(rename = "SetInputSettings")]
-    SetSettings(SetSettingsInternal<'a>),
-    #[serde(rename = "GetInputMute")]
-    Muted {
-        /// Name of input to get the mute state of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-    },
-    #[serde(rename = "SetInputMute")]
-    SetMuted {
-        /// Name of the input to set the mute state of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-        /// Whether to mute the input.
-        #[serde(rename = "inputMuted")]
-        muted: bool,
-    },
-    #[serde(rename = "ToggleInputMute")]
-    ToggleMute {
-        /// Name of the input to toggle the mute state of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-    },
-    #[serde(rename = "GetInputVolume")]
-    Volume {
-        /// Name of the input to get the volume of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-    },
-    #[serde(rename = "SetInputVolume")]
-    SetVolume {
-        /// Name of the input to set the volume of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-        /// Volume settings in either mul or dB.
-        #[serde(rename = "volume", flatten)]
-        volume: Volume,
-    },
-    #[serde(rename = "SetInputName")]
-    SetName {
-        /// Current input name.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-        /// New name for the input.
-        #[serde(rename = "newInputName")]
-        new: &'a str,
-    },
-    #[serde(rename = "CreateInput")]
-    Create(CreateInputInternal<'a>),
-    #[serde(rename = "RemoveInput")]
-    Remove {
-        /// Name of the input to remove.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-    },
-    #[serde(rename = "GetInputAudioBalance")]
-    AudioBalance {
-        /// Name of the input to get the audio balance of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-    },
-    #[serde(rename = "SetInputAudioBalance")]
-    SetAudioBalance {
-        /// Name of the input to set the audio balance of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-        /// New audio balance value. Must be in range of `0.0..=1.0`.
-        #[serde(rename = "inputAudioBalance")]
-        balance: f32,
-    },
-    #[serde(rename = "GetInputAudioSyncOffset")]
-    AudioSyncOffset {
-        /// Name of the input to get the audio sync offset of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-    },
-    #[serde(rename = "SetInputAudioSyncOffset")]
-    SetAudioSyncOffset {
-        /// Name of the input to set the audio sync offset of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-        /// New audio sync offset in milliseconds.
-        #[serde(
-            rename = "inputAudioSyncOffset",
-            with = "crate::serde::duration_millis"
-        )]
-        offset: Duration,
-    },
-    #[serde(rename = "GetInputAudioMonitorType")]
-    AudioMonitorType {
-        /// Name of the input to get the audio monitor type of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-    },
-    #[serde(rename = "SetInputAudioMonitorType")]
-    SetAudioMonitorType {
-        /// Name of the input to set the audio monitor type of.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-        /// Audio monitor type.
-        #[serde(rename = "monitorType")]
-        monitor_type: MonitorType,
-    },
-    #[serde(rename = "GetInputAudioTracks")]
-    AudioTracks {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-    },
-    #[serde(rename = "SetInputAudioTracks")]
-    SetAudioTracks {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        name: &'a str,
-        /// Track settings to apply.
-        #[serde(rename = "inputAudioTracks", with = "crate::serde::audio_tracks")]
-        tracks: [Option<bool>; 6],
-    },
-    #[serde(rename = "GetInputPropertiesListPropertyItems")]
-    PropertiesListPropertyItems {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        input: &'a str,
-        /// Name of the list property to get the items of.
-        #[serde(rename = "propertyName")]
-        property: &'a str,
-    },
-    #[serde(rename = "PressInputPropertiesButton")]
-    PressPropertiesButton {
-        /// Name of the input.
-        #[serde(rename = "inputName")]
-        input: &'a str,
-        /// Name of the button property to press.
-        #[serde(rename = "propertyName")]
-        property: &'a str,
-    },
+requests! {
+    #[derive(Serialize)]
+    #[serde(tag = "requestType", content = "requestData")]
+    pub(crate) enum Request<'a> {
+        #[serde(rename = "GetInputList")]
+        List {
+            /// Restrict the array to only inputs of the specified kind.
+            #[serde(rename = "inputKind", skip_serializing_if = "Option::is_none")]
+            kind: Option<&'a str>,
+        },
+        #[serde(rename = "GetInputKindList")]
+        ListKinds {
+            /// Return all kinds as unversioned or with version suffixes (if available).
+            #[serde(rename = "unversioned")]
+            unversioned: bool,
+        },
+        #[serde(rename = "GetSpecialInputs")]
+        Specials,
+        #[serde(rename = "GetInputDefaultSettings")]
+        DefaultSettings {
+            /// Input kind to get the default settings for.
+            #[serde(rename = "inputKind")]
+            kind: &'a str,
+        },
+        #[serde(rename = "GetInputSettings")]
+        Settings {
+            /// Name of the input to get the settings of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+        },
+        #[serde(rename = "SetInputSettings")]
+        SetSettings(SetSettingsInternal<'a>),
+        #[serde(rename = "GetInputMute")]
+        Muted {
+            /// Name of input to get the mute state of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+        },
+        #[serde(rename = "SetInputMute")]
+        SetMuted {
+            /// Name of the input to set the mute state of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+            /// Whether to mute the input.
+            #[serde(rename = "inputMuted")]
+            muted: bool,
+        },
+        #[serde(rename = "ToggleInputMute")]
+        ToggleMute {
+            /// Name of the input to toggle the mute state of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+        },
+        #[serde(rename = "GetInputVolume")]
+        Volume {
+            /// Name of the input to get the volume of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+        },
+        #[serde(rename = "SetInputVolume")]
+        SetVolume {
+            /// Name of the input to set the volume of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+            /// Volume settings in either mul or dB.
+            #[serde(rename = "volume", flatten)]
+            volume: Volume,
+        },
+        #[serde(rename = "SetInputName")]
+        SetName {
+            /// Current input name.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+            /// New name for the input.
+            #[serde(rename = "newInputName")]
+            new: &'a str,
+        },
+        #[serde(rename = "CreateInput")]
+        Create(CreateInputInternal<'a>),
+        #[serde(rename = "RemoveInput")]
+        Remove {
+            /// Name of the input to remove.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+        },
+        #[serde(rename = "GetInputAudioBalance")]
+        AudioBalance {
+            /// Name of the input to get the audio balance of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+        },
+        #[serde(rename = "SetInputAudioBalance")]
+        SetAudioBalance {
+            /// Name of the input to set the audio balance of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+            /// New audio balance value. Must be in range of `0.0..=1.0`.
+            #[serde(rename = "inputAudioBalance")]
+            balance: f32,
+        },
+        #[serde(rename = "GetInputAudioSyncOffset")]
+        AudioSyncOffset {
+            /// Name of the input to get the audio sync offset of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+        },
+        #[serde(rename = "SetInputAudioSyncOffset")]
+        SetAudioSyncOffset {
+            /// Name of the input to set the audio sync offset of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+            /// New audio sync offset in milliseconds.
+            #[serde(
+                rename = "inputAudioSyncOffset",
+                with = "crate::serde::duration_millis"
+            )]
+            offset: Duration,
+        },
+        #[serde(rename = "GetInputAudioMonitorType")]
+        AudioMonitorType {
+            /// Name of the input to get the audio monitor type of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+        },
+        #[serde(rename = "SetInputAudioMonitorType")]
+        SetAudioMonitorType {
+            /// Name of the input to set the audio monitor type of.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+            /// Audio monitor type.
+            #[serde(rename = "monitorType")]
+            monitor_type: MonitorType,
+        },
+        #[serde(rename = "GetInputAudioTracks")]
+        AudioTracks {
+            /// Name of the input.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+        },
+        #[serde(rename = "SetInputAudioTracks")]
+        SetAudioTracks {
+            /// Name of the input.
+            #[serde(rename = "inputName")]
+            name: &'a str,
+            /// Track settings to apply.
+            #[serde(rename = "inputAudioTracks", with = "crate::serde::audio_tracks")]
+            tracks: [Option<bool>; 6],
+        },
+        #[serde(rename = "GetInputPropertiesListPropertyItems")]
+        PropertiesListPropertyItems {
+            /// Name of the input.
+            #[serde(rename = "inputName")]
+            input: &'a str,
+            /// Name of the list property to get the items of.
+            #[serde(rename = "propertyName")]
+            property: &'a str,
+        },
+        #[serde(rename = "PressInputPropertiesButton")]
+        PressPropertiesButton {
+            /// Name of the input.
+            #[serde(rename = "inputName")]
+            input: &'a str,
+            /// Name of the button property to press.
+            #[serde(rename = "propertyName")]
+            property: &'a str,
+        },
+    }
 }
 
 impl<'a> From<Request<'a>> for super::RequestType<'a> {
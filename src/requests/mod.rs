@@ -5,6 +5,35 @@ use serde::{ser::SerializeStruct, Serialize};
 use serde_repr::Serialize_repr;
 use serde_with::skip_serializing_none;
 
+/// Declare the `Request` enum of a category, together with a `REQUEST_TYPES` list of the names of
+/// all its requests, as they're sent to obs-websocket.
+///
+/// Each variant must be renamed to its request type, with the rename being its first attribute.
+macro_rules! requests {
+    (
+        $(#[$meta:meta])*
+        pub(crate) enum Request$(<$lt:lifetime>)? {
+            $(
+                #[serde(rename = $request_type:literal)]
+                $(#[$vmeta:meta])*
+                $variant:ident $({ $($fields:tt)* })? $(( $($tfields:tt)* ))?
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        pub(crate) enum Request$(<$lt>)? {
+            $(
+                #[serde(rename = $request_type)]
+                $(#[$vmeta])*
+                $variant $({ $($fields)* })? $(( $($tfields)* ))?,
+            )*
+        }
+
+        /// Names of all requests of this category.
+        pub(crate) const REQUEST_TYPES: &[&str] = &[$($request_type),*];
+    };
+}
+
 pub mod config;
 pub mod custom;
 pub mod filters;
@@ -36,7 +65,6 @@ pub(crate) enum ClientRequest<'a> {
     Request(Request<'a>),
     /// Client is making a batch of requests for obs-websocket. Requests are processed serially
     /// (in order) by the server.
-    #[allow(dead_code)]
     RequestBatch(RequestBatch<'a>),
 }
 
@@ -106,12 +134,45 @@ pub(crate) struct Reidentify {
 }
 
 /// Client is making a request to obs-websocket. For example get current scene, create source.
+#[skip_serializing_none]
 #[derive(Serialize)]
 pub(crate) struct Request<'a> {
     #[serde(rename = "requestId")]
     pub request_id: &'a str,
-    #[serde(flatten)]
-    pub ty: RequestType<'a>,
+    #[serde(rename = "requestType")]
+    pub request_type: &'a str,
+    #[serde(rename = "requestData")]
+    pub request_data: Option<&'a serde_json::Value>,
+}
+
+/// Generic form of any request, with the type of request separated from its data. All typed
+/// requests are turned into this form before sending them.
+#[derive(Clone, Debug)]
+pub(crate) struct RawRequest {
+    /// Name of the request, like `GetVersion`.
+    pub request_type: String,
+    /// Additional data for the request, if it takes any.
+    pub request_data: Option<serde_json::Value>,
+}
+
+impl RawRequest {
+    /// Serialize a typed request into its generic form.
+    pub fn new(request: RequestType<'_>) -> serde_json::Result<Self> {
+        let mut request = match serde_json::to_value(request)? {
+            serde_json::Value::Object(request) => request,
+            _ => return Err(serde::ser::Error::custom("request must be an object")),
+        };
+
+        let request_type = match request.remove("requestType") {
+            Some(serde_json::Value::String(request_type)) => request_type,
+            _ => return Err(serde::ser::Error::custom("request type is missing")),
+        };
+
+        Ok(Self {
+            request_type,
+            request_data: request.remove("requestData"),
+        })
+    }
 }
 
 /// Client is making a batch of requests for obs-websocket. Requests are processed serially
@@ -127,7 +188,7 @@ pub(crate) struct RequestBatch<'a> {
     #[serde(rename = "haltOnFailure")]
     pub halt_on_failure: Option<bool>,
     #[serde(rename = "requests")]
-    pub requests: &'a [RequestType<'a>],
+    pub requests: &'a [Request<'a>],
     #[serde(rename = "executionType")]
     pub execution_type: Option<ExecutionType>,
 }
@@ -198,12 +259,10 @@ bitflags! {
     }
 }
 
-#[allow(dead_code)]
-#[derive(Serialize_repr)]
+/// Execution mode of a request batch, defining how obs-websocket processes the requests.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize_repr)]
 #[repr(i8)]
-pub(crate) enum ExecutionType {
-    /// Not a request batch.
-    None = -1,
+pub enum ExecutionType {
     /// A request batch which processes all requests serially, as fast as possible.
     SerialRealtime = 0,
     /// A request batch type which processes all requests serially, in sync with the graphics
@@ -262,3 +321,56 @@ impl<'a> Serialize for RequestType<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn raw_request() {
+        let raw = RawRequest::new(general::Request::Version.into()).unwrap();
+        assert_eq!("GetVersion", raw.request_type);
+        assert_eq!(None, raw.request_data);
+
+        let raw =
+            RawRequest::new(hotkeys::Request::TriggerByName { name: "hotkey" }.into()).unwrap();
+        assert_eq!("TriggerHotkeyByName", raw.request_type);
+        assert_eq!(Some(json!({ "hotkeyName": "hotkey" })), raw.request_data);
+
+        let raw = RawRequest::new(
+            general::Request::Sleep(general::Sleep::Millis(time::Duration::milliseconds(250)))
+                .into(),
+        )
+        .unwrap();
+        assert_eq!("Sleep", raw.request_type);
+        assert_eq!(Some(json!({ "sleepMillis": 250 })), raw.request_data);
+
+        let raw =
+            RawRequest::new(general::Request::Sleep(general::Sleep::Frames(3)).into()).unwrap();
+        assert_eq!(Some(json!({ "sleepFrames": 3 })), raw.request_data);
+    }
+
+    #[test]
+    fn serialize_request() {
+        let data = json!({ "hotkeyName": "hotkey" });
+        let req = ClientRequest::Request(Request {
+            request_id: "1",
+            request_type: "TriggerHotkeyByName",
+            request_data: Some(&data),
+        });
+
+        assert_eq!(
+            json!({
+                "op": 6,
+                "d": {
+                    "requestId": "1",
+                    "requestType": "TriggerHotkeyByName",
+                    "requestData": { "hotkeyName": "hotkey" },
+                },
+            }),
+            serde_json::to_value(&req).unwrap(),
+        );
+    }
+}
@@ -3,40 +3,42 @@
 use serde::Serialize;
 use serde_with::skip_serializing_none;
 
-#[derive(Serialize)]
-#[serde(tag = "requestType", content = "requestData")]
-pub(crate) enum Request<'a> {
-    #[serde(rename = "GetProfileList")]
-    List,
-    #[serde(rename = "SetCurrentProfile")]
-    SetCurrent {
-        /// Name of the profile to switch to.
-        #[serde(rename = "profileName")]
-        name: &'a str,
-    },
-    #[serde(rename = "CreateProfile")]
-    Create {
-        /// Name for the new profile.
-        #[serde(rename = "profileName")]
-        name: &'a str,
-    },
-    #[serde(rename = "RemoveProfile")]
-    Remove {
-        /// Name of the profile to remove.
-        #[serde(rename = "profileName")]
-        name: &'a str,
-    },
-    #[serde(rename = "GetProfileParameter")]
-    Parameter {
-        /// Category of the parameter to get.
-        #[serde(rename = "parameterCategory")]
-        category: &'a str,
-        /// Name of the parameter to get.
-        #[serde(rename = "parameterName")]
-        name: &'a str,
-    },
-    #[serde(rename = "SetProfileParameter")]
-    SetParameter(SetParameter<'a>),
+requests! {
+    #[derive(Serialize)]
+    #[serde(tag = "requestType", content = "requestData")]
+    pub(crate) enum Request<'a> {
+        #[serde(rename = "GetProfileList")]
+        List,
+        #[serde(rename = "SetCurrentProfile")]
+        SetCurrent {
+            /// Name of the profile to switch to.
+            #[serde(rename = "profileName")]
+            name: &'a str,
+        },
+        #[serde(rename = "CreateProfile")]
+        Create {
+            /// Name for the new profile.
+            #[serde(rename = "profileName")]
+            name: &'a str,
+        },
+        #[serde(rename = "RemoveProfile")]
+        Remove {
+            /// Name of the profile to remove.
+            #[serde(rename = "profileName")]
+            name: &'a str,
+        },
+        #[serde(rename = "GetProfileParameter")]
+        Parameter {
+            /// Category of the parameter to get.
+            #[serde(rename = "parameterCategory")]
+            category: &'a str,
+            /// Name of the parameter to get.
+            #[serde(rename = "parameterName")]
+            name: &'a str,
+        },
+        #[serde(rename = "SetProfileParameter")]
+        SetParameter(SetParameter<'a>),
+    }
 }
 
 impl<'a> From<Request<'a>> for super::RequestType<'a> {
@@ -2,21 +2,23 @@
 
 use serde::Serialize;
 
-#[derive(Serialize)]
-#[serde(tag = "requestType", content = "requestData")]
-pub(crate) enum Request {
-    #[serde(rename = "GetReplayBufferStatus")]
-    Status,
-    #[serde(rename = "ToggleReplayBuffer")]
-    Toggle,
-    #[serde(rename = "StartReplayBuffer")]
-    Start,
-    #[serde(rename = "StopReplayBuffer")]
-    Stop,
-    #[serde(rename = "SaveReplayBuffer")]
-    Save,
-    #[serde(rename = "GetLastReplayBufferReplay")]
-    LastReplay,
+requests! {
+    #[derive(Serialize)]
+    #[serde(tag = "requestType", content = "requestData")]
+    pub(crate) enum Request {
+        #[serde(rename = "GetReplayBufferStatus")]
+        Status,
+        #[serde(rename = "ToggleReplayBuffer")]
+        Toggle,
+        #[serde(rename = "StartReplayBuffer")]
+        Start,
+        #[serde(rename = "StopReplayBuffer")]
+        Stop,
+        #[serde(rename = "SaveReplayBuffer")]
+        Save,
+        #[serde(rename = "GetLastReplayBufferReplay")]
+        LastReplay,
+    }
 }
 
 impl<'a> From<Request> for super::RequestType<'a> {
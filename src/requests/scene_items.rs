@@ -5,102 +5,104 @@ use serde_with::skip_serializing_none;
 
 use crate::common::{Alignment, BlendMode, BoundsType};
 
-#[derive(Serialize)]
-#[serde(tag = "requestType", content = "requestData")]
-pub(crate) enum Request<'a> {
-    #[serde(rename = "GetSceneItemList")]
-    List {
-        /// Name of the scene to get the items of.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-    },
-    #[serde(rename = "GetGroupSceneItemList")]
-    ListGroup {
-        /// Name of the group to get the items of.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-    },
-    #[serde(rename = "GetSceneItemId")]
-    Id(Id<'a>),
-    #[serde(rename = "CreateSceneItem")]
-    Create(CreateSceneItem<'a>),
-    #[serde(rename = "RemoveSceneItem")]
-    Remove {
-        /// Name of the scene the item is in.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: i64,
-    },
-    #[serde(rename = "DuplicateSceneItem")]
-    Duplicate(Duplicate<'a>),
-    #[serde(rename = "GetSceneItemTransform")]
-    Transform {
-        /// Name of the scene the item is in.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: i64,
-    },
-    #[serde(rename = "SetSceneItemTransform")]
-    SetTransform(SetTransform<'a>),
-    #[serde(rename = "GetSceneItemEnabled")]
-    Enabled {
-        /// Name of the scene the item is in.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: i64,
-    },
-    #[serde(rename = "SetSceneItemEnabled")]
-    SetEnabled(SetEnabled<'a>),
-    #[serde(rename = "GetSceneItemLocked")]
-    Locked {
-        /// Name of the scene the item is in.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: i64,
-    },
-    #[serde(rename = "SetSceneItemLocked")]
-    SetLocked(SetLocked<'a>),
-    #[serde(rename = "GetSceneItemIndex")]
-    Index {
-        /// Name of the scene the item is in.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: i64,
-    },
-    #[serde(rename = "SetSceneItemIndex")]
-    SetIndex(SetIndex<'a>),
-    #[serde(rename = "GetSceneItemBlendMode")]
-    BlendMode {
-        /// Name of the scene the item is in.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-        ///  Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: i64,
-    },
-    #[serde(rename = "SetSceneItemBlendMode")]
-    SetBlendMode(SetBlendMode<'a>),
-    #[serde(rename = "GetSceneItemPrivateSettings")]
-    PrivateSettings {
-        /// Name of the scene the item is in.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-        /// Numeric ID of the scene item.
-        #[serde(rename = "sceneItemId")]
-        item_id: i64,
-    },
-    #[serde(rename = "SetSceneItemPrivateSettings")]
-    SetPrivateSettings(SetPrivateSettingsInternal<'a>),
+requests! {
+    #[derive(Serialize)]
+    #[serde(tag = "requestType", content = "requestData")]
+    pub(crate) enum Request<'a> {
+        #[serde(rename = "GetSceneItemList")]
+        List {
+            /// Name of the scene to get the items of.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+        },
+        #[serde(rename = "GetGroupSceneItemList")]
+        ListGroup {
+            /// Name of the group to get the items of.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+        },
+        #[serde(rename = "GetSceneItemId")]
+        Id(Id<'a>),
+        #[serde(rename = "CreateSceneItem")]
+        Create(CreateSceneItem<'a>),
+        #[serde(rename = "RemoveSceneItem")]
+        Remove {
+            /// Name of the scene the item is in.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+            /// Numeric ID of the scene item.
+            #[serde(rename = "sceneItemId")]
+            item_id: i64,
+        },
+        #[serde(rename = "DuplicateSceneItem")]
+        Duplicate(Duplicate<'a>),
+        #[serde(rename = "GetSceneItemTransform")]
+        Transform {
+            /// Name of the scene the item is in.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+            /// Numeric ID of the scene item.
+            #[serde(rename = "sceneItemId")]
+            item_id: i64,
+        },
+        #[serde(rename = "SetSceneItemTransform")]
+        SetTransform(SetTransform<'a>),
+        #[serde(rename = "GetSceneItemEnabled")]
+        Enabled {
+            /// Name of the scene the item is in.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+            /// Numeric ID of the scene item.
+            #[serde(rename = "sceneItemId")]
+            item_id: i64,
+        },
+        #[serde(rename = "SetSceneItemEnabled")]
+        SetEnabled(SetEnabled<'a>),
+        #[serde(rename = "GetSceneItemLocked")]
+        Locked {
+            /// Name of the scene the item is in.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+            /// Numeric ID of the scene item.
+            #[serde(rename = "sceneItemId")]
+            item_id: i64,
+        },
+        #[serde(rename = "SetSceneItemLocked")]
+        SetLocked(SetLocked<'a>),
+        #[serde(rename = "GetSceneItemIndex")]
+        Index {
+            /// Name of the scene the item is in.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+            /// Numeric ID of the scene item.
+            #[serde(rename = "sceneItemId")]
+            item_id: i64,
+        },
+        #[serde(rename = "SetSceneItemIndex")]
+        SetIndex(SetIndex<'a>),
+        #[serde(rename = "GetSceneItemBlendMode")]
+        BlendMode {
+            /// Name of the scene the item is in.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+            ///  Numeric ID of the scene item.
+            #[serde(rename = "sceneItemId")]
+            item_id: i64,
+        },
+        #[serde(rename = "SetSceneItemBlendMode")]
+        SetBlendMode(SetBlendMode<'a>),
+        #[serde(rename = "GetSceneItemPrivateSettings")]
+        PrivateSettings {
+            /// Name of the scene the item is in.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+            /// Numeric ID of the scene item.
+            #[serde(rename = "sceneItemId")]
+            item_id: i64,
+        },
+        #[serde(rename = "SetSceneItemPrivateSettings")]
+        SetPrivateSettings(SetPrivateSettingsInternal<'a>),
+    }
 }
 
 impl<'a> From<Request<'a>> for super::RequestType<'a> {
@@ -173,7 +175,7 @@ pub struct SetTransform<'a> {
 /// Request information for [`crate::client::SceneItems::set_transform`] as part of
 /// [`SetTransform`].
 #[skip_serializing_none]
-#[derive(Default, Serialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
 pub struct SceneItemTransform {
     /// Position (or offset) on the screen.
     #[serde(rename = "position", flatten)]
@@ -227,7 +229,7 @@ impl From<crate::responses::scene_items::SceneItemTransform> for SceneItemTransf
 /// Request information for [`crate::client::SceneItems::set_transform`] as part of
 /// [`SceneItemTransform`].
 #[skip_serializing_none]
-#[derive(Default, Serialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
 pub struct Position {
     /// The x position of the source from the left.
     #[serde(rename = "positionX")]
@@ -240,7 +242,7 @@ pub struct Position {
 /// Request information for [`crate::client::SceneItems::set_transform`] as part of
 /// [`SceneItemTransform`].
 #[skip_serializing_none]
-#[derive(Default, Serialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
 pub struct Scale {
     /// The x-scale factor of the source.
     #[serde(rename = "scaleX")]
@@ -253,7 +255,7 @@ pub struct Scale {
 /// Request information for [`crate::client::SceneItems::set_transform`] as part of
 /// [`SceneItemTransform`].
 #[skip_serializing_none]
-#[derive(Default, Serialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
 pub struct Bounds {
     /// Type of bounding box.
     #[serde(rename = "boundsType")]
@@ -272,7 +274,7 @@ pub struct Bounds {
 /// Request information for [`crate::client::SceneItems::set_transform`] as part of
 /// [`SceneItemTransform`].
 #[skip_serializing_none]
-#[derive(Default, Serialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
 pub struct Crop {
     /// The number of pixels cropped off the left of the source before scaling.
     #[serde(rename = "cropLeft")]
@@ -4,58 +4,60 @@ use serde::Serialize;
 use serde_with::skip_serializing_none;
 use time::Duration;
 
-#[derive(Serialize)]
-#[serde(tag = "requestType", content = "requestData")]
-pub(crate) enum Request<'a> {
-    #[serde(rename = "GetSceneList")]
-    List,
-    #[serde(rename = "GetGroupList")]
-    ListGroups,
-    #[serde(rename = "GetCurrentProgramScene")]
-    CurrentProgramScene,
-    #[serde(rename = "SetCurrentProgramScene")]
-    SetCurrentProgramScene {
-        /// Scene to set as the current program scene.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-    },
-    #[serde(rename = "GetCurrentPreviewScene")]
-    CurrentPreviewScene,
-    #[serde(rename = "SetCurrentPreviewScene")]
-    SetCurrentPreviewScene {
-        /// Scene to set as the current preview scene.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-    },
-    #[serde(rename = "SetSceneName")]
-    SetName {
-        /// Name of the scene to be renamed.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-        /// New name for the scene.
-        #[serde(rename = "newSceneName")]
-        new_name: &'a str,
-    },
-    #[serde(rename = "CreateScene")]
-    Create {
-        /// Name for the new scene.
-        #[serde(rename = "sceneName")]
-        name: &'a str,
-    },
-    #[serde(rename = "RemoveScene")]
-    Remove {
-        /// Name of the scene to remove.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-    },
-    #[serde(rename = "GetSceneSceneTransitionOverride")]
-    TransitionOverride {
-        /// Name of the scene.
-        #[serde(rename = "sceneName")]
-        scene: &'a str,
-    },
-    #[serde(rename = "SetSceneSceneTransitionOverride")]
-    SetTransitionOverride(SetTransitionOverride<'a>),
+requests! {
+    #[derive(Serialize)]
+    #[serde(tag = "requestType", content = "requestData")]
+    pub(crate) enum Request<'a> {
+        #[serde(rename = "GetSceneList")]
+        List,
+        #[serde(rename = "GetGroupList")]
+        ListGroups,
+        #[serde(rename = "GetCurrentProgramScene")]
+        CurrentProgramScene,
+        #[serde(rename = "SetCurrentProgramScene")]
+        SetCurrentProgramScene {
+            /// Scene to set as the current program scene.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+        },
+        #[serde(rename = "GetCurrentPreviewScene")]
+        CurrentPreviewScene,
+        #[serde(rename = "SetCurrentPreviewScene")]
+        SetCurrentPreviewScene {
+            /// Scene to set as the current preview scene.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+        },
+        #[serde(rename = "SetSceneName")]
+        SetName {
+            /// Name of the scene to be renamed.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+            /// New name for the scene.
+            #[serde(rename = "newSceneName")]
+            new_name: &'a str,
+        },
+        #[serde(rename = "CreateScene")]
+        Create {
+            /// Name for the new scene.
+            #[serde(rename = "sceneName")]
+            name: &'a str,
+        },
+        #[serde(rename = "RemoveScene")]
+        Remove {
+            /// Name of the scene to remove.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+        },
+        #[serde(rename = "GetSceneSceneTransitionOverride")]
+        TransitionOverride {
+            /// Name of the scene.
+            #[serde(rename = "sceneName")]
+            scene: &'a str,
+        },
+        #[serde(rename = "SetSceneSceneTransitionOverride")]
+        SetTransitionOverride(SetTransitionOverride<'a>),
+    }
 }
 
 impl<'a> From<Request<'a>> for super::RequestType<'a> {
@@ -5,19 +5,21 @@ use std::path::Path;
 use serde::Serialize;
 use serde_with::skip_serializing_none;
 
-#[derive(Serialize)]
-#[serde(tag = "requestType", content = "requestData")]
-pub(crate) enum Request<'a> {
-    #[serde(rename = "GetSourceActive")]
-    Active {
-        /// Name of the source to get the active state of.
-        #[serde(rename = "sourceName")]
-        name: &'a str,
-    },
-    #[serde(rename = "GetSourceScreenshot")]
-    TakeScreenshot(TakeScreenshot<'a>),
-    #[serde(rename = "SaveSourceScreenshot")]
-    SaveScreenshot(SaveScreenshot<'a>),
+requests! {
+    #[derive(Serialize)]
+    #[serde(tag = "requestType", content = "requestData")]
+    pub(crate) enum Request<'a> {
+        #[serde(rename = "GetSourceActive")]
+        Active {
+            /// Name of the source to get the active state of.
+            #[serde(rename = "sourceName")]
+            name: &'a str,
+        },
+        #[serde(rename = "GetSourceScreenshot")]
+        TakeScreenshot(TakeScreenshot<'a>),
+        #[serde(rename = "SaveSourceScreenshot")]
+        SaveScreenshot(SaveScreenshot<'a>),
+    }
 }
 
 impl<'a> From<Request<'a>> for super::RequestType<'a> {
@@ -2,17 +2,19 @@
 
 use serde::Serialize;
 
-#[derive(Serialize)]
-#[serde(tag = "requestType", content = "requestData")]
-pub(crate) enum Request {
-    #[serde(rename = "GetVirtualCamStatus")]
-    Status,
-    #[serde(rename = "ToggleVirtualCam")]
-    Toggle,
-    #[serde(rename = "StartVirtualCam")]
-    Start,
-    #[serde(rename = "StopVirtualCam")]
-    Stop,
+requests! {
+    #[derive(Serialize)]
+    #[serde(tag = "requestType", content = "requestData")]
+    pub(crate) enum Request {
+        #[serde(rename = "GetVirtualCamStatus")]
+        Status,
+        #[serde(rename = "ToggleVirtualCam")]
+        Toggle,
+        #[serde(rename = "StartVirtualCam")]
+        Start,
+        #[serde(rename = "StopVirtualCam")]
+        Stop,
+    }
 }
 
 impl<'a> From<Request> for super::RequestType<'a> {
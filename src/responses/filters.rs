@@ -1,6 +1,6 @@
 //! Responses related to filters.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Response value for [`crate::client::Filters::get_source_filter_list`].
 #[derive(Debug, Deserialize)]
@@ -11,7 +11,7 @@ pub(crate) struct Filters {
 }
 
 /// Response value for [`crate::client::Filters::list`] and [`crate::client::Filters::get`].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SourceFilter {
     /// Whether the filter is enabled.
     #[serde(rename = "filterEnabled")]
@@ -3,7 +3,7 @@
 use serde::Deserialize;
 
 /// Response value for [`crate::client::General::version`].
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct Version {
     /// Current OBS Studio version.
     #[serde(rename = "obsVersion")]
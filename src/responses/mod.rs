@@ -20,7 +20,9 @@ pub mod ui;
 pub(crate) mod virtual_cam;
 
 use serde::{de, Deserialize, Deserializer};
-use serde_repr::Deserialize_repr;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+use crate::Error;
 
 #[derive(Debug)]
 pub(crate) enum ServerMessage {
@@ -107,7 +109,7 @@ impl<'de> Deserialize<'de> for ServerMessage {
 /// information if authentication is required. Also contains RPC version for version negotiation.
 #[derive(Debug, Deserialize)]
 pub(crate) struct Hello {
-    #[allow(dead_code)]
+    /// Version of the obs-websocket server, that the client connected to.
     #[serde(rename = "obsWebSocketVersion")]
     pub obs_web_socket_version: semver::Version,
     /// version number which gets incremented on each **breaking change** to the obs-websocket
@@ -142,13 +144,14 @@ pub(crate) struct RequestResponse {
     pub data: serde_json::Value,
 }
 
+/// `obs-websocket` is responding to a request batch coming from the client.
 #[derive(Debug, Deserialize)]
 pub(crate) struct RequestBatchResponse {
-    #[allow(dead_code)]
     #[serde(rename = "requestId")]
     pub id: String,
-    #[allow(dead_code)]
-    pub results: Vec<serde_json::Value>,
+    /// Responses to all requests that were processed, in the same order as the requests.
+    #[serde(rename = "results")]
+    pub results: Vec<RequestResponse>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -157,10 +160,12 @@ pub(crate) struct Authentication {
     pub salt: String,
 }
 
-#[derive(Debug, Deserialize)]
-pub(crate) struct Status {
+/// Status of a request, as reported by obs-websocket in its response.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Status {
     /// Is true if the request resulted in [`StatusCode::Success`]. False if otherwise.
     pub result: bool,
+    /// Status code that describes the result of the request.
     pub code: StatusCode,
     /// May be provided by the server on errors to offer further details on why a request failed.
     pub comment: Option<String>,
@@ -168,7 +173,7 @@ pub(crate) struct Status {
 
 /// The status code gives information about the result of a request. It gives further insight into
 /// what went wrong, if a request failed.
-#[derive(Debug, Deserialize_repr)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize_repr, Deserialize_repr)]
 #[repr(u16)]
 pub enum StatusCode {
     /// Unknown status, should never be used.
@@ -263,7 +268,7 @@ pub enum StatusCode {
 
 /// Additional close codes, defined by `obs-websocket`. These can be used to further pin down the
 /// details of why the web-socket connection was closed.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[repr(u16)]
 pub enum WebSocketCloseCode {
     /// Unknown reason, should never be used.
@@ -297,3 +302,25 @@ pub enum WebSocketCloseCode {
     /// A requested feature is not supported due to hardware/software limitations.
     UnsupportedFeature = 4012,
 }
+
+impl TryFrom<u16> for WebSocketCloseCode {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        Ok(match value {
+            4000 => Self::UnknownReason,
+            4002 => Self::MessageDecodeError,
+            4003 => Self::MissingDataField,
+            4004 => Self::InvalidDataFieldType,
+            4005 => Self::InvalidDataFieldValue,
+            4006 => Self::UnknownOpCode,
+            4007 => Self::NotIdentified,
+            4008 => Self::AlreadyIdentified,
+            4009 => Self::AuthenticationFailed,
+            4010 => Self::UnsupportedRpcVersion,
+            4011 => Self::SessionInvalidated,
+            4012 => Self::UnsupportedFeature,
+            _ => return Err(Error::UnknownCloseCode(value)),
+        })
+    }
+}
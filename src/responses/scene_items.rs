@@ -1,6 +1,6 @@
 //! Responses related to scene items.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::common::{Alignment, BlendMode, BoundsType};
 
@@ -70,7 +70,7 @@ pub(crate) struct GetSceneItemTransform {
 }
 
 /// Response value for [`crate::client::SceneItems::transform`].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SceneItemTransform {
     /// Base width (without scaling) of the source.
     #[serde(rename = "sourceWidth")]
@@ -0,0 +1,44 @@
+//! Utilities to test code that uses this crate, without the need for a running OBS instance.
+//!
+//! The [`MockServer`] runs an obs-websocket compatible server in the background, that performs
+//! the same handshake (optionally with authentication) as the real plugin. Responses for requests
+//! are defined per request type, either as fixed data or with a closure, and events can be pushed
+//! to all connected clients at any time.
+//!
+//! Alternatively, the server can be backed by a [`Simulator`], that keeps track of scenes,
+//! inputs, outputs and more, to test automation logic end-to-end against a simulated OBS
+//! instance.
+//!
+//! To reproduce a session with a real OBS instance, a [`Recorder`] captures all messages of a
+//! client, which a [`MockServer`] can later play back from the resulting [`RecordedSession`].
+//!
+//! ```no_run
+//! use obws::{responses::StatusCode, testing::{MockServer, RequestError}};
+//! use serde_json::json;
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = MockServer::builder().password("secret").start().await?;
+//!
+//! server.respond("GetCurrentProgramScene", json!({ "currentProgramSceneName": "Main" }));
+//! server.on_request("SetCurrentProgramScene", |data| match data {
+//!     Some(data) if data["sceneName"] == "Main" => Ok(json!(null)),
+//!     _ => Err(RequestError::new(StatusCode::ResourceNotFound, "no such scene")),
+//! });
+//!
+//! let client = server.connect().await?;
+//! assert_eq!("Main", client.scenes().current_program_scene().await?);
+//! # Ok(())
+//! # }
+//! ```
+
+pub use self::{
+    record::{Direction, Frame, Recorder},
+    replay::RecordedSession,
+    server::{MockRequest, MockServer, MockServerBuilder, RequestError},
+    simulator::Simulator,
+};
+
+mod record;
+mod replay;
+mod server;
+mod simulator;
@@ -0,0 +1,156 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, LineWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex, PoisonError},
+    time::Instant,
+};
+
+use futures_util::{future, SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::Duration;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tracing::warn;
+
+use crate::client::{
+    codec::Encoding,
+    {MessageReader, MessageWriter},
+};
+
+/// Direction of a recorded [`Frame`], as seen from the client.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Direction {
+    /// The client sent the message to obs-websocket.
+    Sent,
+    /// The client received the message from obs-websocket.
+    Received,
+}
+
+/// A single message, that was exchanged between the client and obs-websocket.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Frame {
+    /// Time since the recording started.
+    #[serde(rename = "elapsedMs", with = "crate::serde::duration_millis")]
+    pub elapsed: Duration,
+    /// Whether the message was sent or received.
+    pub direction: Direction,
+    /// The full message, including the `op` code and `d` data, always in its JSON form even if
+    /// the connection used a different encoding.
+    pub message: Value,
+}
+
+/// Recorder, that captures every message that a client sends and receives into a JSONL file,
+/// with one [`Frame`] per line.
+///
+/// It's enabled with the `recorder` field of the [`ConnectConfig`](crate::client::ConnectConfig)
+/// and captures the whole session, including the handshake and any reconnects. The recording can
+/// later be played back with [`MockServerBuilder::replay`](super::MockServerBuilder::replay).
+///
+/// **Note:** The recording contains the authentication response of the handshake. It can't be
+/// used to recover the password, but should still not be shared publicly.
+#[derive(Clone)]
+pub struct Recorder {
+    inner: Arc<Mutex<Box<dyn Write + Send>>>,
+    started: Instant,
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("started", &self.started)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Recorder {
+    /// Create a recorder, that writes to a new file at the given location, replacing any
+    /// existing file.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        File::create(path).map(|file| Self::new(LineWriter::new(file)))
+    }
+
+    /// Create a recorder, that writes to the given writer.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Box::new(writer))),
+            started: Instant::now(),
+        }
+    }
+
+    /// Wrap both halves of a web-socket, to record all messages passing through them.
+    pub(crate) fn wrap(
+        &self,
+        (write, read): (MessageWriter, MessageReader),
+        encoding: Encoding,
+    ) -> (MessageWriter, MessageReader) {
+        let sent = self.clone();
+        let write = write.with(move |message: Message| {
+            sent.record(Direction::Sent, &message, encoding);
+            future::ready(Ok::<_, tungstenite::Error>(message))
+        });
+
+        let received = self.clone();
+        let read = read.inspect(move |message| {
+            if let Ok(message) = message {
+                received.record(Direction::Received, message, encoding);
+            }
+        });
+
+        (Box::pin(write), Box::pin(read))
+    }
+
+    fn record(&self, direction: Direction, message: &Message, encoding: Encoding) {
+        if !message.is_text() && !message.is_binary() {
+            return;
+        }
+
+        let message = match encoding.decode::<Value>(message.clone()) {
+            Ok(message) => message,
+            Err(_) => {
+                warn!(?direction, "failed decoding message for the recording");
+                return;
+            }
+        };
+
+        let frame = Frame {
+            elapsed: Duration::try_from(self.started.elapsed()).unwrap_or(Duration::MAX),
+            direction,
+            message,
+        };
+
+        let mut writer = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let result = serde_json::to_writer(&mut *writer, &frame)
+            .map_err(io::Error::from)
+            .and_then(|_| writer.write_all(b"\n"));
+
+        if let Err(e) = result {
+            warn!(error = ?e, "failed writing frame to the recording");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn frame_format() {
+        let frame = Frame {
+            elapsed: Duration::milliseconds(1500),
+            direction: Direction::Received,
+            message: json!({ "op": 5, "d": { "eventType": "ExitStarted" } }),
+        };
+
+        let line = serde_json::to_string(&frame).unwrap();
+        assert_eq!(
+            r#"{"elapsedMs":1500,"direction":"received","message":{"d":{"eventType":"ExitStarted"},"op":5}}"#,
+            line
+        );
+        assert_eq!(frame, serde_json::from_str(&line).unwrap());
+    }
+}
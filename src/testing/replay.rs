@@ -0,0 +1,326 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use serde_json::Value;
+
+use super::{Direction, Frame, RequestError};
+use crate::responses::StatusCode;
+
+/// A recorded session, as captured by a [`Recorder`](super::Recorder), that can be played back
+/// with [`MockServerBuilder::replay`](super::MockServerBuilder::replay).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RecordedSession {
+    frames: Vec<Frame>,
+}
+
+impl RecordedSession {
+    /// Load a recording from a JSONL file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = fs::File::open(path)?;
+        let mut frames = Vec::new();
+
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            frames.push(serde_json::from_str(&line)?);
+        }
+
+        Ok(Self { frames })
+    }
+
+    /// All frames of the recording, in the order they were captured.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+}
+
+impl From<Vec<Frame>> for RecordedSession {
+    fn from(frames: Vec<Frame>) -> Self {
+        Self { frames }
+    }
+}
+
+/// A single request of the recording together with its response, and the events that were
+/// received after it.
+#[derive(Debug)]
+struct Exchange {
+    request_type: String,
+    request_data: Option<Value>,
+    status: Value,
+    response_data: Option<Value>,
+    events: Vec<(String, Value)>,
+    used: bool,
+}
+
+/// Plays back the responses of a recording, for requests that match the recorded ones.
+#[derive(Debug)]
+pub(super) struct Replay {
+    exchanges: Vec<Exchange>,
+    /// Events that were emitted while handling the current request.
+    events: Vec<(String, Value)>,
+}
+
+impl Replay {
+    pub(super) fn new(session: RecordedSession) -> Self {
+        let mut exchanges: Vec<Exchange> = Vec::new();
+        let mut requests = HashMap::new();
+        let mut batches = HashMap::new();
+        let mut early_events = Vec::new();
+
+        for frame in session.frames {
+            let message = frame.message;
+            let data = &message["d"];
+            let request_id = data["requestId"].as_str().unwrap_or_default().to_owned();
+
+            match (frame.direction, message["op"].as_u64()) {
+                // Request
+                (Direction::Sent, Some(6)) => {
+                    requests.insert(request_id, request_key(data));
+                }
+                // RequestBatch
+                (Direction::Sent, Some(8)) => {
+                    let batch = data["requests"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(request_key)
+                        .collect::<Vec<_>>();
+                    batches.insert(request_id, batch);
+                }
+                // RequestResponse
+                (Direction::Received, Some(7)) => {
+                    if let Some(request) = requests.remove(&request_id) {
+                        exchanges.push(Exchange::new(request, data));
+                    }
+                }
+                // RequestBatchResponse
+                (Direction::Received, Some(9)) => {
+                    let batch = batches.remove(&request_id).unwrap_or_default();
+                    let results = data["results"].as_array().into_iter().flatten();
+
+                    exchanges.extend(
+                        batch
+                            .into_iter()
+                            .zip(results)
+                            .map(|(request, result)| Exchange::new(request, result)),
+                    );
+                }
+                // Event
+                (Direction::Received, Some(5)) => {
+                    let event = (
+                        data["eventType"].as_str().unwrap_or_default().to_owned(),
+                        data.get("eventData").cloned().unwrap_or_default(),
+                    );
+
+                    match exchanges.last_mut() {
+                        Some(exchange) => exchange.events.push(event),
+                        None => early_events.push(event),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Events before the first request are sent together with its response.
+        if let Some(first) = exchanges.first_mut() {
+            first.events.splice(0..0, early_events);
+        }
+
+        Self {
+            exchanges,
+            events: Vec::new(),
+        }
+    }
+
+    /// Handle a request with the next matching response of the recording, returning [`None`] if
+    /// the request was never recorded.
+    ///
+    /// Once all recorded responses for the same request are used up, the last one is repeated.
+    pub(super) fn handle(
+        &mut self,
+        request_type: &str,
+        request_data: Option<&Value>,
+    ) -> Option<Result<Value, RequestError>> {
+        let mut matching = self.exchanges.iter_mut().filter(|exchange| {
+            exchange.request_type == request_type && exchange.request_data.as_ref() == request_data
+        });
+
+        let mut last = None;
+        let exchange = loop {
+            match matching.next() {
+                Some(exchange) if !exchange.used => break exchange,
+                Some(exchange) => last = Some(exchange),
+                None => break last?,
+            }
+        };
+
+        if !exchange.used {
+            exchange.used = true;
+            self.events.append(&mut exchange.events);
+        }
+
+        Some(exchange.response())
+    }
+
+    /// Types of all recorded requests.
+    pub(super) fn request_types(&self) -> impl Iterator<Item = &str> {
+        self.exchanges
+            .iter()
+            .map(|exchange| exchange.request_type.as_str())
+    }
+
+    /// Take all events, that were recorded after the responses of the last request.
+    pub(super) fn take_events(&mut self) -> Vec<(String, Value)> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+/// Extract the request type and data, that are used to match requests.
+fn request_key(data: &Value) -> (String, Option<Value>) {
+    (
+        data["requestType"].as_str().unwrap_or_default().to_owned(),
+        data.get("requestData").filter(|d| !d.is_null()).cloned(),
+    )
+}
+
+impl Exchange {
+    fn new((request_type, request_data): (String, Option<Value>), response: &Value) -> Self {
+        Self {
+            request_type,
+            request_data,
+            status: response["requestStatus"].clone(),
+            response_data: response.get("responseData").cloned(),
+            events: Vec::new(),
+            used: false,
+        }
+    }
+
+    fn response(&self) -> Result<Value, RequestError> {
+        if self.status["result"] == true {
+            return Ok(self.response_data.clone().unwrap_or_default());
+        }
+
+        let code = serde_json::from_value(self.status["code"].clone())
+            .unwrap_or(StatusCode::RequestProcessingFailed);
+
+        Err(RequestError {
+            code,
+            comment: self.status["comment"].as_str().map(ToOwned::to_owned),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use time::Duration;
+
+    use super::*;
+
+    fn frame(direction: Direction, message: Value) -> Frame {
+        Frame {
+            elapsed: Duration::ZERO,
+            direction,
+            message,
+        }
+    }
+
+    #[test]
+    fn matches_requests() {
+        let mut replay = Replay::new(RecordedSession::from(vec![
+            frame(
+                Direction::Sent,
+                json!({ "op": 6, "d": {
+                    "requestType": "SetCurrentProgramScene",
+                    "requestId": "1",
+                    "requestData": { "sceneName": "Main" },
+                } }),
+            ),
+            frame(
+                Direction::Received,
+                json!({ "op": 7, "d": {
+                    "requestType": "SetCurrentProgramScene",
+                    "requestId": "1",
+                    "requestStatus": { "result": true, "code": 100 },
+                } }),
+            ),
+            frame(
+                Direction::Received,
+                json!({ "op": 5, "d": {
+                    "eventType": "CurrentProgramSceneChanged",
+                    "eventIntent": 4,
+                    "eventData": { "sceneName": "Main" },
+                } }),
+            ),
+            frame(
+                Direction::Sent,
+                json!({ "op": 8, "d": {
+                    "requestId": "2",
+                    "requests": [
+                        { "requestType": "GetCurrentProgramScene" },
+                        { "requestType": "StartRecord" },
+                    ],
+                } }),
+            ),
+            frame(
+                Direction::Received,
+                json!({ "op": 9, "d": {
+                    "requestId": "2",
+                    "results": [
+                        {
+                            "requestType": "GetCurrentProgramScene",
+                            "requestStatus": { "result": true, "code": 100 },
+                            "responseData": { "currentProgramSceneName": "Main" },
+                        },
+                        {
+                            "requestType": "StartRecord",
+                            "requestStatus": { "result": false, "code": 500, "comment": "running" },
+                        },
+                    ],
+                } }),
+            ),
+        ]));
+
+        let data = json!({ "sceneName": "Main" });
+        assert_eq!(
+            Value::Null,
+            replay
+                .handle("SetCurrentProgramScene", Some(&data))
+                .unwrap()
+                .unwrap()
+        );
+        assert_eq!(
+            vec![(
+                "CurrentProgramSceneChanged".to_owned(),
+                json!({ "sceneName": "Main" })
+            )],
+            replay.take_events()
+        );
+
+        let other = json!({ "sceneName": "Other" });
+        assert!(replay
+            .handle("SetCurrentProgramScene", Some(&other))
+            .is_none());
+
+        let scene = replay.handle("GetCurrentProgramScene", None).unwrap();
+        assert_eq!("Main", scene.unwrap()["currentProgramSceneName"]);
+
+        let error = replay.handle("StartRecord", None).unwrap().unwrap_err();
+        assert_eq!(StatusCode::OutputRunning, error.code);
+        assert_eq!(Some("running"), error.comment.as_deref());
+
+        // Used up responses are repeated, but their events are not.
+        assert!(replay
+            .handle("SetCurrentProgramScene", Some(&data))
+            .unwrap()
+            .is_ok());
+        assert!(replay.take_events().is_empty());
+    }
+}
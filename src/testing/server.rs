@@ -0,0 +1,1196 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex, PoisonError, RwLock},
+    time::Duration,
+};
+
+use futures_util::{future, FutureExt, SinkExt, StreamExt};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::{broadcast, watch},
+    task::JoinHandle,
+};
+use tokio_tungstenite::{
+    tungstenite::{
+        handshake::server::{Request, Response},
+        http::{header::SEC_WEBSOCKET_PROTOCOL, HeaderValue},
+        protocol::{frame::coding::CloseCode, CloseFrame},
+        Message,
+    },
+    WebSocketStream,
+};
+use tracing::{debug, warn};
+
+use super::{
+    replay::{RecordedSession, Replay},
+    simulator::{self, Simulator},
+};
+use crate::{
+    client::{codec::Encoding, connection::create_auth_response, RequestCategory},
+    requests::EventSubscription,
+    responses::{StatusCode, WebSocketCloseCode},
+    Client,
+};
+
+/// Version of obs-websocket that the mock server pretends to be.
+const OBS_WEBSOCKET_VERSION: &str = "5.0.1";
+/// Version of OBS that the mock server pretends to be.
+const OBS_VERSION: &str = "28.0.0";
+/// The only RPC version of obs-websocket.
+const RPC_VERSION: u32 = 1;
+
+type Handler = Arc<dyn Fn(Option<&Value>) -> Result<Value, RequestError> + Send + Sync>;
+
+/// Error response for a mocked request.
+#[derive(Clone, Debug)]
+pub struct RequestError {
+    /// Status code that describes the kind of error.
+    pub code: StatusCode,
+    /// Optional message to provide additional details about the error.
+    pub comment: Option<String>,
+}
+
+impl RequestError {
+    /// Create a new error with the given status code and comment.
+    pub fn new(code: StatusCode, comment: impl Into<String>) -> Self {
+        Self {
+            code,
+            comment: Some(comment.into()),
+        }
+    }
+}
+
+impl From<StatusCode> for RequestError {
+    fn from(code: StatusCode) -> Self {
+        Self {
+            code,
+            comment: None,
+        }
+    }
+}
+
+/// A request that was received by the [`MockServer`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockRequest {
+    /// Name of the request, like `GetVersion`.
+    pub request_type: String,
+    /// Additional data of the request, if any.
+    pub request_data: Option<Value>,
+}
+
+/// State shared between the server handle and all connections.
+struct Shared {
+    password: Option<String>,
+    handlers: RwLock<HashMap<String, Handler>>,
+    delays: RwLock<HashMap<String, Duration>>,
+    requests: Mutex<Vec<MockRequest>>,
+    replay: Option<Mutex<Replay>>,
+    simulator: Option<Mutex<Simulator>>,
+    available_requests: Option<Vec<String>>,
+    events: broadcast::Sender<Value>,
+    controls: broadcast::Sender<Control>,
+}
+
+/// Instruction from the [`MockServer`] to all of its connections.
+#[derive(Clone, Debug)]
+enum Control {
+    /// Cut off the connection, without closing it properly.
+    Drop,
+    /// Close the connection with a close code and reason.
+    Close(WebSocketCloseCode, String),
+}
+
+impl Shared {
+    /// Send an event to all connections, that subscribed to it.
+    fn broadcast_event(&self, event_type: &str, data: Value) {
+        let mut event = json!({
+            "eventType": event_type,
+            "eventIntent": event_intent(event_type).bits(),
+        });
+        if !data.is_null() {
+            event["eventData"] = data;
+        }
+
+        self.events.send(json!({ "op": 5, "d": event })).ok();
+    }
+}
+
+/// Builder for a [`MockServer`].
+#[derive(Default)]
+pub struct MockServerBuilder {
+    password: Option<String>,
+    replay: Option<RecordedSession>,
+    simulator: Option<Simulator>,
+    available_requests: Option<Vec<String>>,
+}
+
+impl MockServerBuilder {
+    /// Require clients to authenticate with the given password.
+    pub fn password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Play back the responses of a recorded session. Requests with the same type and data as a
+    /// recorded one are answered with the recorded response, and the events that were received
+    /// right after it are sent out again. Once all recorded responses of a request are used up,
+    /// the last one is repeated.
+    ///
+    /// The recorded timing is not reproduced, all responses are sent immediately.
+    pub fn replay(mut self, session: RecordedSession) -> Self {
+        self.replay = Some(session);
+        self
+    }
+
+    /// Back the server with a simulated OBS instance, that handles all requests without a
+    /// registered response and emits events for every change of its state.
+    pub fn simulator(mut self, simulator: Simulator) -> Self {
+        self.simulator = Some(simulator);
+        self
+    }
+
+    /// Restrict the list of available requests in the version info to the given request types,
+    /// to test how clients deal with older versions of obs-websocket.
+    pub fn available_requests<I, T>(mut self, request_types: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.available_requests = Some(request_types.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Start the server on a random local port.
+    pub async fn start(self) -> io::Result<MockServer> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+
+        let shared = Arc::new(Shared {
+            password: self.password,
+            handlers: RwLock::default(),
+            delays: RwLock::default(),
+            requests: Mutex::default(),
+            replay: self.replay.map(|session| Mutex::new(Replay::new(session))),
+            simulator: self.simulator.map(Mutex::new),
+            available_requests: self.available_requests,
+            events: broadcast::channel(100).0,
+            controls: broadcast::channel(10).0,
+        });
+        let (shutdown, shutdown_rx) = watch::channel(());
+
+        let handle = tokio::spawn(accept(listener, Arc::clone(&shared), shutdown_rx));
+
+        let server = MockServer {
+            addr,
+            shared,
+            shutdown,
+            handle,
+        };
+        server.respond("Sleep", Value::Null);
+
+        Ok(server)
+    }
+}
+
+/// A local server, that mimics obs-websocket for testing purposes.
+///
+/// Requests without a registered response are answered from the recorded session to replay and
+/// then by the [`Simulator`], if the server was built with them. Otherwise, they fail with
+/// [`StatusCode::UnknownRequestType`], except for `GetVersion`, which responds with a version
+/// supported by this crate, and `Sleep`.
+///
+/// The list of available requests in the version info contains all requests covered by this
+/// crate, together with all registered, replayed and simulated request types. Clients only send
+/// requests that were available while connecting, so other requests must be registered before
+/// connecting, or they fail with [`Error::Unsupported`](crate::Error::Unsupported) on the client
+/// side. The list can be restricted with [`MockServerBuilder::available_requests`].
+///
+/// Events are only sent to clients, that subscribed to their category.
+///
+/// The server shuts down and closes all connections, once dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+    shutdown: watch::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Create a builder to configure the server before starting it.
+    pub fn builder() -> MockServerBuilder {
+        MockServerBuilder::default()
+    }
+
+    /// Start a server with default settings, that doesn't require authentication.
+    pub async fn start() -> io::Result<Self> {
+        Self::builder().start().await
+    }
+
+    /// Address that the server listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Port that the server listens on.
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// Connect a new client to the server, using the password if one is required.
+    pub async fn connect(&self) -> crate::Result<Client> {
+        Client::connect("127.0.0.1", self.port(), self.shared.password.as_deref()).await
+    }
+
+    /// Handle requests of the given type with a closure, that receives the request data and
+    /// returns either the response data or an error. A response of [`Value::Null`] means the
+    /// response doesn't contain any data.
+    ///
+    /// Replaces any previously registered response for the same request type.
+    pub fn on_request<F>(&self, request_type: impl Into<String>, handler: F)
+    where
+        F: Fn(Option<&Value>) -> Result<Value, RequestError> + Send + Sync + 'static,
+    {
+        self.shared
+            .handlers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(request_type.into(), Arc::new(handler));
+    }
+
+    /// Always respond to requests of the given type with the same data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data can't be serialized into JSON.
+    pub fn respond(&self, request_type: impl Into<String>, data: impl Serialize) {
+        let data = serde_json::to_value(data).expect("response data must be serializable");
+        self.on_request(request_type, move |_| Ok(data.clone()));
+    }
+
+    /// Always fail requests of the given type with the same error.
+    pub fn fail(&self, request_type: impl Into<String>, error: impl Into<RequestError>) {
+        let error = error.into();
+        self.on_request(request_type, move |_| Err(error.clone()));
+    }
+
+    /// Delay the responses to requests of the given type, to simulate a slow obs-websocket. Each
+    /// connection handles its messages in order, so any messages after the delayed request are
+    /// held back as well.
+    pub fn delay(&self, request_type: impl Into<String>, delay: Duration) {
+        self.shared
+            .delays
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(request_type.into(), delay);
+    }
+
+    /// Send an event to all connected and identified clients, that subscribed to its category.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data can't be serialized into JSON.
+    pub fn push_event(&self, event_type: &str, data: impl Serialize) {
+        let data = serde_json::to_value(data).expect("event data must be serializable");
+        self.shared.broadcast_event(event_type, data);
+    }
+
+    /// Cut off all current connections without a close frame, like a crashing OBS or a broken
+    /// network would.
+    pub fn drop_connections(&self) {
+        self.shared.controls.send(Control::Drop).ok();
+    }
+
+    /// Close all current connections with the given close code and reason, like obs-websocket
+    /// does when it shuts down or rejects a client.
+    pub fn close_connections(&self, code: WebSocketCloseCode, reason: impl Into<String>) {
+        self.shared
+            .controls
+            .send(Control::Close(code, reason.into()))
+            .ok();
+    }
+
+    /// All requests that were received so far, in the order of arrival. Requests within a batch
+    /// are listed separately.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.shared
+            .requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Forget about all requests that were received so far.
+    pub fn clear_requests(&self) {
+        self.shared
+            .requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+        self.shutdown.send(()).ok();
+    }
+}
+
+async fn accept(listener: TcpListener, shared: Arc<Shared>, shutdown: watch::Receiver<()>) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                warn!(error = ?e, "mock server failed accepting connection");
+                continue;
+            }
+        };
+
+        let shared = Arc::clone(&shared);
+        let shutdown = shutdown.clone();
+
+        tokio::spawn(async move {
+            if let Err(e) = Connection::accept(stream, shared, shutdown).await {
+                debug!(error = %e, "mock server connection failed");
+            }
+        });
+    }
+}
+
+/// A single connection of a client to the mock server.
+struct Connection {
+    socket: WebSocketStream<TcpStream>,
+    encoding: Encoding,
+    shared: Arc<Shared>,
+    subscriptions: EventSubscription,
+}
+
+type ConnectionResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+/// Whatever happened first, while a connection waits for messages, events, controls or the
+/// shutdown.
+enum Next {
+    Message(Option<Result<Message, tokio_tungstenite::tungstenite::Error>>),
+    Event(Result<Value, broadcast::error::RecvError>),
+    Control(Result<Control, broadcast::error::RecvError>),
+    Shutdown,
+}
+
+/// Wait for whichever of the two futures finishes first.
+async fn first<T>(a: impl Future<Output = T>, b: impl Future<Output = T>) -> T {
+    futures_util::pin_mut!(a, b);
+    future::select(a, b).await.factor_first().0
+}
+
+impl Connection {
+    async fn accept(
+        stream: TcpStream,
+        shared: Arc<Shared>,
+        mut shutdown: watch::Receiver<()>,
+    ) -> ConnectionResult {
+        let mut encoding = Encoding::Json;
+        // The error type is dictated by the `Callback` trait of tungstenite.
+        #[allow(clippy::result_large_err)]
+        let negotiate = |req: &Request, mut resp: Response| {
+            let protocol = req
+                .headers()
+                .get(SEC_WEBSOCKET_PROTOCOL)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| {
+                    value
+                        .split(',')
+                        .find_map(|protocol| Encoding::from_protocol(protocol.trim()))
+                });
+
+            if let Some(protocol) = protocol {
+                encoding = protocol;
+                resp.headers_mut().insert(
+                    SEC_WEBSOCKET_PROTOCOL,
+                    HeaderValue::from_static(protocol.protocol()),
+                );
+            }
+
+            Ok(resp)
+        };
+
+        let mut controls = shared.controls.subscribe();
+        let socket = tokio_tungstenite::accept_hdr_async(stream, negotiate).await?;
+        let mut conn = Self {
+            socket,
+            encoding,
+            shared,
+            subscriptions: EventSubscription::ALL,
+        };
+
+        if !conn.handshake().await? {
+            return Ok(());
+        }
+
+        let mut events = conn.shared.events.subscribe();
+
+        loop {
+            let next = first(
+                first(
+                    conn.socket.next().map(Next::Message),
+                    events.recv().map(Next::Event),
+                ),
+                first(
+                    controls.recv().map(Next::Control),
+                    shutdown.changed().map(|_| Next::Shutdown),
+                ),
+            )
+            .await;
+
+            match next {
+                Next::Message(Some(Ok(message))) if message.is_close() => {
+                    conn.socket.close(None).await.ok();
+                    break;
+                }
+                Next::Message(Some(Ok(message))) if message.is_ping() || message.is_pong() => {}
+                Next::Message(Some(Ok(message))) => conn.handle(message).await?,
+                Next::Message(Some(Err(e))) => return Err(e.into()),
+                Next::Message(None) => break,
+                Next::Event(Ok(event)) if conn.subscribed(&event) => conn.send(&event).await?,
+                Next::Event(Ok(_) | Err(broadcast::error::RecvError::Lagged(_))) => {}
+                Next::Event(Err(broadcast::error::RecvError::Closed)) => break,
+                Next::Control(Ok(Control::Drop)) => break,
+                Next::Control(Ok(Control::Close(code, reason))) => {
+                    conn.close(code, &reason).await;
+                    break;
+                }
+                Next::Control(Err(_)) => {}
+                Next::Shutdown => {
+                    conn.close_with(CloseCode::Away, "mock server shutting down")
+                        .await;
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Perform the handshake with the client, returning whether the client was identified.
+    async fn handshake(&mut self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let auth = self.shared.password.as_ref().map(|password| {
+            let challenge = random_string();
+            let salt = random_string();
+            let expected = create_auth_response(&challenge, &salt, password);
+            (challenge, salt, expected)
+        });
+
+        let mut hello = json!({
+            "obsWebSocketVersion": OBS_WEBSOCKET_VERSION,
+            "rpcVersion": RPC_VERSION,
+        });
+        if let Some((challenge, salt, _)) = &auth {
+            hello["authentication"] = json!({ "challenge": challenge, "salt": salt });
+        }
+        self.send(&json!({ "op": 0, "d": hello })).await?;
+
+        let identify = match self.socket.next().await {
+            // Clients may close the connection right away, after probing the server.
+            Some(Ok(message)) if message.is_close() => {
+                self.socket.close(None).await.ok();
+                return Ok(false);
+            }
+            Some(Ok(message)) => self.decode(message)?,
+            Some(Err(e)) => return Err(e.into()),
+            None => return Ok(false),
+        };
+
+        if identify["op"] != 1 {
+            self.close(
+                WebSocketCloseCode::NotIdentified,
+                "expected `Identify` message",
+            )
+            .await;
+            return Ok(false);
+        }
+
+        if let Some((_, _, expected)) = &auth {
+            if identify["d"]["authentication"].as_str() != Some(expected) {
+                self.close(
+                    WebSocketCloseCode::AuthenticationFailed,
+                    "authentication failed",
+                )
+                .await;
+                return Ok(false);
+            }
+        }
+
+        if identify["d"]["rpcVersion"] != RPC_VERSION {
+            self.close(
+                WebSocketCloseCode::UnsupportedRpcVersion,
+                "unsupported RPC version",
+            )
+            .await;
+            return Ok(false);
+        }
+
+        self.update_subscriptions(&identify["d"]);
+        self.send(&json!({ "op": 2, "d": { "negotiatedRpcVersion": RPC_VERSION } }))
+            .await?;
+
+        Ok(true)
+    }
+
+    /// Update the event subscriptions from an `Identify` or `Reidentify` message.
+    fn update_subscriptions(&mut self, data: &Value) {
+        if let Some(bits) = data["eventSubscriptions"].as_u64() {
+            self.subscriptions = EventSubscription::from_bits_truncate(bits as u32);
+        }
+    }
+
+    /// Whether the client subscribed to the given event message.
+    fn subscribed(&self, event: &Value) -> bool {
+        let intent = event["d"]["eventIntent"].as_u64().unwrap_or_default();
+        self.subscriptions
+            .intersects(EventSubscription::from_bits_truncate(intent as u32))
+    }
+
+    async fn handle(&mut self, message: Message) -> ConnectionResult {
+        let message = self.decode(message)?;
+        let data = &message["d"];
+
+        match message["op"].as_u64() {
+            // Reidentify
+            Some(3) => {
+                self.update_subscriptions(data);
+                self.send(&json!({ "op": 2, "d": { "negotiatedRpcVersion": RPC_VERSION } }))
+                    .await?;
+            }
+            // Request
+            Some(6) => {
+                let mut response = self.execute(data);
+                response["requestId"] = data["requestId"].clone();
+
+                let delay = data["requestType"].as_str().and_then(|request_type| {
+                    self.shared
+                        .delays
+                        .read()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .get(request_type)
+                        .copied()
+                });
+                if let Some(delay) = delay {
+                    tokio::time::sleep(delay).await;
+                }
+
+                self.send(&json!({ "op": 7, "d": response })).await?;
+            }
+            // RequestBatch
+            Some(8) => {
+                let halt_on_failure = data["haltOnFailure"].as_bool().unwrap_or(false);
+                let mut results = Vec::new();
+
+                for request in data["requests"].as_array().into_iter().flatten() {
+                    let mut response = self.execute(request);
+                    let failed = response["requestStatus"]["result"] == false;
+                    response["requestId"] = request["requestId"].clone();
+                    results.push(response);
+
+                    if failed && halt_on_failure {
+                        break;
+                    }
+                }
+
+                self.send(&json!({
+                    "op": 9,
+                    "d": { "requestId": data["requestId"], "results": results },
+                }))
+                .await?;
+            }
+            _ => {
+                self.close(WebSocketCloseCode::UnknownOpCode, "unknown op code")
+                    .await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Execute a single request and create the response for it, without the request ID.
+    fn execute(&self, request: &Value) -> Value {
+        let request_type = request["requestType"].as_str().unwrap_or_default();
+        let request_data = request.get("requestData").filter(|data| !data.is_null());
+
+        self.shared
+            .requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(MockRequest {
+                request_type: request_type.to_owned(),
+                request_data: request_data.cloned(),
+            });
+
+        let handler = self
+            .shared
+            .handlers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(request_type)
+            .cloned();
+
+        let result = match handler {
+            Some(handler) => Some(handler(request_data)),
+            None => self
+                .replay(request_type, request_data)
+                .or_else(|| self.simulate(request_type, request_data)),
+        };
+
+        let result = match result {
+            Some(result) => result,
+            None if request_type == "GetVersion" => Ok(self.version()),
+            None => Err(RequestError::new(
+                StatusCode::UnknownRequestType,
+                format!("unknown request type `{}`", request_type),
+            )),
+        };
+
+        let mut response = json!({ "requestType": request_type });
+        match result {
+            Ok(data) => {
+                response["requestStatus"] = json!({ "result": true, "code": StatusCode::Success });
+                if !data.is_null() {
+                    response["responseData"] = data;
+                }
+            }
+            Err(error) => {
+                response["requestStatus"] = json!({
+                    "result": false,
+                    "code": error.code,
+                    "comment": error.comment,
+                });
+            }
+        }
+
+        response
+    }
+
+    /// Answer the request from the recorded session, if there is one, and send out all events
+    /// that were recorded after it.
+    fn replay(
+        &self,
+        request_type: &str,
+        request_data: Option<&Value>,
+    ) -> Option<Result<Value, RequestError>> {
+        let mut replay = self
+            .shared
+            .replay
+            .as_ref()?
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let result = replay.handle(request_type, request_data);
+        for (event_type, data) in replay.take_events() {
+            self.shared.broadcast_event(&event_type, data);
+        }
+
+        result
+    }
+
+    /// Let the simulator handle the request, if there is one, and send out all resulting events.
+    fn simulate(
+        &self,
+        request_type: &str,
+        request_data: Option<&Value>,
+    ) -> Option<Result<Value, RequestError>> {
+        let mut simulator = self
+            .shared
+            .simulator
+            .as_ref()?
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let result = simulator.handle(request_type, request_data);
+        for (event_type, data) in simulator.take_events() {
+            self.shared.broadcast_event(event_type, data);
+        }
+
+        result
+    }
+
+    /// Default response for the `GetVersion` request.
+    fn version(&self) -> Value {
+        json!({
+            "obsVersion": OBS_VERSION,
+            "obsWebSocketVersion": OBS_WEBSOCKET_VERSION,
+            "rpcVersion": RPC_VERSION,
+            "availableRequests": self.available_requests(),
+            "supportedImageFormats": ["png", "jpg"],
+            "platform": "mock",
+            "platformDescription": "obws mock server",
+        })
+    }
+
+    /// List all request types that the server can answer, unless restricted explicitly.
+    fn available_requests(&self) -> Vec<String> {
+        if let Some(available_requests) = &self.shared.available_requests {
+            return available_requests.clone();
+        }
+
+        let simulated = match self.shared.simulator {
+            Some(_) => simulator::REQUEST_TYPES,
+            None => &[],
+        };
+
+        let mut available_requests = self
+            .shared
+            .handlers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .chain(
+                RequestCategory::ALL
+                    .iter()
+                    .flat_map(|category| category.request_types())
+                    .chain(simulated)
+                    .map(|&name| name.to_owned()),
+            )
+            .collect::<Vec<_>>();
+        if let Some(replay) = &self.shared.replay {
+            let replay = replay.lock().unwrap_or_else(PoisonError::into_inner);
+            available_requests.extend(replay.request_types().map(ToOwned::to_owned));
+        }
+        available_requests.sort();
+        available_requests.dedup();
+        available_requests
+    }
+
+    fn decode(&self, message: Message) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.encoding.decode(message).map_err(|e| match e {
+            crate::client::codec::DecodeError::IntoText(e) => e.into(),
+            crate::client::codec::DecodeError::Deserialize(e) => e.into(),
+        })
+    }
+
+    async fn send(&mut self, message: &Value) -> ConnectionResult {
+        let message = self.encoding.encode(message)?;
+        self.socket.send(message).await?;
+        Ok(())
+    }
+
+    async fn close(&mut self, code: WebSocketCloseCode, reason: &str) {
+        self.close_with(CloseCode::Library(code as u16), reason)
+            .await;
+    }
+
+    /// Send a close frame and wait for the client to confirm it, before the connection is
+    /// dropped, like obs-websocket does.
+    async fn close_with(&mut self, code: CloseCode, reason: &str) {
+        let frame = CloseFrame {
+            code,
+            reason: reason.to_owned().into(),
+        };
+
+        if self.socket.close(Some(frame)).await.is_ok() {
+            while let Some(Ok(_)) = self.socket.next().await {}
+        }
+    }
+}
+
+/// Category of an event, as used for the event subscriptions of clients.
+fn event_intent(event_type: &str) -> EventSubscription {
+    match event_type {
+        "InputVolumeMeters" => EventSubscription::INPUT_VOLUME_METERS,
+        "InputActiveStateChanged" => EventSubscription::INPUT_ACTIVE_STATE_CHANGED,
+        "InputShowStateChanged" => EventSubscription::INPUT_SHOW_STATE_CHANGED,
+        "SceneItemTransformChanged" => EventSubscription::SCENE_ITEM_TRANSFORM_CHANGED,
+        "SceneCollectionListChanged" | "ProfileListChanged" => EventSubscription::CONFIG,
+        "StreamStateChanged" | "RecordStateChanged" | "VirtualcamStateChanged" => {
+            EventSubscription::OUTPUTS
+        }
+        "VendorEvent" => EventSubscription::VENDORS,
+        "StudioModeStateChanged" => EventSubscription::UI,
+        "CurrentProgramSceneChanged" | "CurrentPreviewSceneChanged" => EventSubscription::SCENES,
+        name if name.starts_with("CurrentSceneCollection")
+            || name.starts_with("CurrentProfile") =>
+        {
+            EventSubscription::CONFIG
+        }
+        name if name.starts_with("SourceFilter") => EventSubscription::FILTERS,
+        name if name.starts_with("MediaInput") => EventSubscription::MEDIA_INPUTS,
+        name if name.starts_with("Input") => EventSubscription::INPUTS,
+        name if name.starts_with("ReplayBuffer") => EventSubscription::OUTPUTS,
+        name if name.starts_with("SceneItem") => EventSubscription::SCENE_ITEMS,
+        name if name.starts_with("SceneTransition")
+            || name.starts_with("CurrentSceneTransition") =>
+        {
+            EventSubscription::TRANSITIONS
+        }
+        name if name.starts_with("Scene") => EventSubscription::SCENES,
+        _ => EventSubscription::GENERAL,
+    }
+}
+
+/// Create a random alphanumeric string, used for the authentication challenge and salt.
+fn random_string() -> String {
+    std::iter::repeat_with(fastrand::alphanumeric)
+        .take(32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+
+    use super::*;
+    use crate::{client::HandshakeError, testing::Direction, Error};
+
+    #[tokio::test]
+    async fn handshake() {
+        let server = MockServer::start().await.unwrap();
+        let client = server.connect().await.unwrap();
+
+        let version = client.general().version().await.unwrap();
+        assert_eq!(1, version.rpc_version);
+        assert!(RequestCategory::ALL
+            .iter()
+            .flat_map(|category| category.request_types())
+            .all(|request_type| version.available_requests.iter().any(|r| r == request_type)));
+    }
+
+    #[tokio::test]
+    async fn authentication() {
+        let server = MockServer::builder()
+            .password("secret")
+            .start()
+            .await
+            .unwrap();
+        assert!(server.connect().await.is_ok());
+
+        for password in [Some("wrong"), None] {
+            let result = Client::connect("127.0.0.1", server.port(), password).await;
+            assert!(matches!(
+                result,
+                Err(Error::Handshake(HandshakeError::ConnectionClosed(Some(details))))
+                    if details.code == CloseCode::Library(4009)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn session() {
+        let server = MockServer::builder()
+            .password("secret")
+            .start()
+            .await
+            .unwrap();
+        let client = server.connect().await.unwrap();
+
+        let session = client.session();
+        assert_eq!(
+            OBS_WEBSOCKET_VERSION,
+            session.obs_web_socket_version.to_string()
+        );
+        assert_eq!(RPC_VERSION, session.rpc_version);
+        assert!(session.authentication_required);
+        assert_eq!(EventSubscription::ALL, session.event_subscriptions);
+        assert_eq!(vec!["png", "jpg"], session.version.supported_image_formats);
+
+        client.reidentify(EventSubscription::SCENES).await.unwrap();
+        assert_eq!(
+            EventSubscription::SCENES,
+            client.session().event_subscriptions
+        );
+
+        let requests = server.requests();
+        assert_eq!(1, requests.len());
+        assert_eq!("GetVersion", requests[0].request_type);
+    }
+
+    #[tokio::test]
+    async fn probe() {
+        let server = MockServer::start().await.unwrap();
+        let probe = Client::probe("127.0.0.1", server.port()).await.unwrap();
+        assert_eq!(
+            OBS_WEBSOCKET_VERSION,
+            probe.obs_web_socket_version.to_string()
+        );
+        assert_eq!(RPC_VERSION, probe.rpc_version);
+        assert!(!probe.authentication_required);
+
+        let server = MockServer::builder()
+            .password("secret")
+            .start()
+            .await
+            .unwrap();
+        let probe = Client::probe("127.0.0.1", server.port()).await.unwrap();
+        assert!(probe.authentication_required);
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn responses() {
+        let server = MockServer::start().await.unwrap();
+        server.respond(
+            "GetCurrentProgramScene",
+            json!({ "currentProgramSceneName": "Main" }),
+        );
+        server.on_request("SetCurrentProgramScene", |data| match data {
+            Some(data) if data["sceneName"] == "Main" => Ok(Value::Null),
+            _ => Err(RequestError::new(StatusCode::ResourceNotFound, "no scene")),
+        });
+        server.fail("StartRecord", StatusCode::OutputRunning);
+
+        let client = server.connect().await.unwrap();
+        let scenes = client.scenes();
+
+        assert_eq!("Main", scenes.current_program_scene().await.unwrap());
+        assert!(scenes.set_current_program_scene("Main").await.is_ok());
+        assert!(matches!(
+            scenes.set_current_program_scene("Other").await,
+            Err(Error::Api { code: StatusCode::ResourceNotFound, message: Some(message) })
+                if message == "no scene"
+        ));
+        assert!(matches!(
+            client.recording().start().await,
+            Err(Error::Api {
+                code: StatusCode::OutputRunning,
+                message: None
+            })
+        ));
+        assert!(matches!(
+            client.streaming().start().await,
+            Err(Error::Api {
+                code: StatusCode::UnknownRequestType,
+                ..
+            })
+        ));
+
+        let requests = server.requests();
+        assert_eq!("GetVersion", requests[0].request_type);
+        assert_eq!(
+            MockRequest {
+                request_type: "SetCurrentProgramScene".to_owned(),
+                request_data: Some(json!({ "sceneName": "Other" })),
+            },
+            requests[3]
+        );
+    }
+
+    #[tokio::test]
+    async fn simulator() {
+        let simulator = Simulator::new()
+            .scene("Gaming")
+            .input("Gaming", "Media", "ffmpeg_source")
+            .filter("Media", "Color", "color_filter_v2");
+        let server = MockServer::builder()
+            .simulator(simulator)
+            .start()
+            .await
+            .unwrap();
+        let client = server.connect().await.unwrap();
+
+        let version = client.general().version().await.unwrap();
+        assert!(version
+            .available_requests
+            .iter()
+            .any(|r| r == "SetCurrentProgramScene"));
+
+        let scenes = client.scenes();
+        scenes.set_current_program_scene("Gaming").await.unwrap();
+        assert_eq!("Gaming", scenes.current_program_scene().await.unwrap());
+        assert!(matches!(
+            scenes.create("Gaming").await,
+            Err(Error::Api {
+                code: StatusCode::ResourceAlreadyExists,
+                ..
+            })
+        ));
+        assert!(matches!(
+            scenes.set_current_program_scene("Missing").await,
+            Err(Error::Api {
+                code: StatusCode::ResourceNotFound,
+                ..
+            })
+        ));
+        assert!(matches!(
+            scenes.current_preview_scene().await,
+            Err(Error::Api {
+                code: StatusCode::StudioModeNotActive,
+                ..
+            })
+        ));
+
+        let inputs = client.inputs();
+        let item_id = inputs
+            .create(crate::requests::inputs::Create {
+                scene: "Scene",
+                input: "Color",
+                kind: "color_source_v3",
+                settings: Some(json!({ "width": 100, "height": 50 })),
+                enabled: Some(false),
+            })
+            .await
+            .unwrap();
+        assert_eq!(2, inputs.list(None).await.unwrap().len());
+        assert!(inputs.toggle_mute("Media").await.unwrap());
+
+        let scene_items = client.scene_items();
+        assert!(!scene_items.enabled("Scene", item_id).await.unwrap());
+        let transform = scene_items.transform("Scene", item_id).await.unwrap();
+        assert_eq!(100.0, transform.width);
+        assert_eq!(1, scene_items.list("Gaming").await.unwrap().len());
+
+        assert_eq!(1, client.filters().list("Media").await.unwrap().len());
+        assert!(client.media_inputs().status("Media").await.is_ok());
+        assert!(client.sources().active("Media").await.unwrap().active);
+
+        let recording = client.recording();
+        recording.start().await.unwrap();
+        assert!(matches!(
+            recording.start().await,
+            Err(Error::Api {
+                code: StatusCode::OutputRunning,
+                ..
+            })
+        ));
+        assert!(recording.toggle_pause().await.unwrap());
+        assert!(recording.status().await.unwrap().paused);
+        assert!(!recording.stop().await.unwrap().is_empty());
+        assert!(matches!(
+            recording.stop().await,
+            Err(Error::Api {
+                code: StatusCode::OutputNotRunning,
+                ..
+            })
+        ));
+
+        assert_eq!(4, client.outputs().list().await.unwrap().len());
+        assert!(client.streaming().status().await.is_ok());
+        assert!(client.general().stats().await.is_ok());
+        assert!(client.config().video_settings().await.is_ok());
+        assert_eq!("Untitled", client.profiles().current().await.unwrap());
+        assert!(client.scene_collections().list().await.is_ok());
+        assert!(client.transitions().current().await.is_ok());
+        assert_eq!(1, client.ui().list_monitors().await.unwrap().len());
+
+        client.ui().set_studio_mode_enabled(true).await.unwrap();
+        scenes.set_current_preview_scene("Scene").await.unwrap();
+        client.transitions().trigger().await.unwrap();
+        assert_eq!("Scene", scenes.current_program_scene().await.unwrap());
+        assert_eq!("Gaming", scenes.current_preview_scene().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        use crate::{client::ConnectConfig, testing::Recorder};
+
+        let path = std::env::temp_dir().join(format!("obws-session-{}.jsonl", random_string()));
+
+        let server = MockServer::builder()
+            .simulator(Simulator::new())
+            .start()
+            .await
+            .unwrap();
+        let config = ConnectConfig {
+            recorder: Some(Recorder::create(&path).unwrap()),
+            ..ConnectConfig::new("127.0.0.1", server.port(), None::<&str>)
+        };
+        let client = Client::connect_with_config(config).await.unwrap();
+        client.scenes().create("New").await.unwrap();
+        client
+            .scenes()
+            .set_current_program_scene("New")
+            .await
+            .unwrap();
+        assert!(client.scenes().create("New").await.is_err());
+        drop(client);
+
+        let session = RecordedSession::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(Direction::Received, session.frames()[0].direction);
+        assert_eq!(0, session.frames()[0].message["op"]);
+
+        let server = MockServer::builder().replay(session).start().await.unwrap();
+        let client = server.connect().await.unwrap();
+        let scenes = client.scenes();
+
+        scenes.create("New").await.unwrap();
+        scenes.set_current_program_scene("New").await.unwrap();
+        assert!(matches!(
+            scenes.create("New").await,
+            Err(Error::Api {
+                code: StatusCode::ResourceAlreadyExists,
+                ..
+            })
+        ));
+        assert!(matches!(
+            scenes.create("Other").await,
+            Err(Error::Api {
+                code: StatusCode::UnknownRequestType,
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "events")]
+    #[tokio::test]
+    async fn simulator_events() {
+        use std::time::Duration;
+
+        use futures_util::StreamExt;
+
+        use crate::{client::ConnectConfig, events::Event, requests::EventSubscription};
+
+        let server = MockServer::builder()
+            .simulator(Simulator::new())
+            .start()
+            .await
+            .unwrap();
+        let client = server.connect().await.unwrap();
+        let events = client.events().unwrap();
+        futures_util::pin_mut!(events);
+
+        let config = ConnectConfig {
+            event_subscriptions: Some(EventSubscription::NONE),
+            ..ConnectConfig::new("127.0.0.1", server.port(), None::<&str>)
+        };
+        let quiet = Client::connect_with_config(config).await.unwrap();
+        let quiet_events = quiet.events().unwrap();
+        futures_util::pin_mut!(quiet_events);
+
+        client.scenes().create("New").await.unwrap();
+
+        assert!(matches!(
+            events.next().await,
+            Some(Event::SceneCreated { name, is_group: false }) if name == "New"
+        ));
+        assert!(matches!(
+            events.next().await,
+            Some(Event::SceneListChanged { scenes }) if scenes.len() == 2
+        ));
+
+        let next = tokio::time::timeout(Duration::from_millis(50), quiet_events.next()).await;
+        assert!(next.is_err());
+    }
+
+    #[cfg(feature = "events")]
+    #[tokio::test]
+    async fn events() {
+        use futures_util::StreamExt;
+
+        use crate::events::Event;
+
+        let server = MockServer::start().await.unwrap();
+        let client = server.connect().await.unwrap();
+        let events = client.events().unwrap();
+        futures_util::pin_mut!(events);
+
+        server.push_event("CurrentProgramSceneChanged", json!({ "sceneName": "Main" }));
+
+        assert!(matches!(
+            events.next().await,
+            Some(Event::CurrentProgramSceneChanged { name }) if name == "Main"
+        ));
+    }
+}
@@ -0,0 +1,302 @@
+use serde_json::{json, Map, Value};
+
+use super::{
+    already_exists, apply_settings, check_range, not_found, outputs, Args, Profile, Response,
+    Simulator,
+};
+use crate::{responses::StatusCode, testing::RequestError};
+
+const REALM_GLOBAL: &str = "OBS_WEBSOCKET_DATA_REALM_GLOBAL";
+const REALM_PROFILE: &str = "OBS_WEBSOCKET_DATA_REALM_PROFILE";
+
+impl Simulator {
+    fn active_profile(&self) -> &Profile {
+        self.profiles
+            .iter()
+            .find(|p| p.name == self.current_profile)
+            .expect("current profile always exists")
+    }
+
+    fn active_profile_mut(&mut self) -> &mut Profile {
+        let current = &self.current_profile;
+        self.profiles
+            .iter_mut()
+            .find(|p| &p.name == current)
+            .expect("current profile always exists")
+    }
+
+    /// Get the persistent data slots of the realm, that a request is targeted at.
+    fn persistent_realm(
+        &mut self,
+        args: &Args<'_>,
+    ) -> Result<&mut Map<String, Value>, RequestError> {
+        match args.str("realm")? {
+            REALM_GLOBAL => Ok(&mut self.global_data),
+            REALM_PROFILE => Ok(&mut self.active_profile_mut().data),
+            realm => Err(RequestError::new(
+                StatusCode::InvalidRequestField,
+                format!("Your specified realm `{}` is invalid.", realm),
+            )),
+        }
+    }
+
+    pub(super) fn persistent_data(&mut self, args: &Args<'_>) -> Response {
+        let slot = args.str("slotName")?;
+        let data = self.persistent_realm(args)?;
+
+        Ok(json!({ "slotValue": data.get(slot) }))
+    }
+
+    pub(super) fn set_persistent_data(&mut self, args: &Args<'_>) -> Response {
+        let slot = args.str("slotName")?;
+        let value = args.value("slotValue")?;
+        let data = self.persistent_realm(args)?;
+
+        data.insert(slot.to_owned(), value.clone());
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn scene_collection_list(&self) -> Value {
+        json!({
+            "currentSceneCollectionName": self.current_scene_collection,
+            "sceneCollections": self.scene_collections,
+        })
+    }
+
+    fn switch_scene_collection(&mut self, name: String) {
+        if self.current_scene_collection == name {
+            return;
+        }
+
+        self.emit(
+            "CurrentSceneCollectionChanging",
+            json!({ "sceneCollectionName": self.current_scene_collection }),
+        );
+        self.emit(
+            "CurrentSceneCollectionChanged",
+            json!({ "sceneCollectionName": name }),
+        );
+        self.current_scene_collection = name;
+    }
+
+    pub(super) fn set_current_scene_collection(&mut self, args: &Args<'_>) -> Response {
+        let name = args.str("sceneCollectionName")?;
+        if !self.scene_collections.iter().any(|c| c == name) {
+            return Err(not_found("scene collection", name));
+        }
+
+        self.switch_scene_collection(name.to_owned());
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn create_scene_collection(&mut self, args: &Args<'_>) -> Response {
+        let name = args.str("sceneCollectionName")?;
+        if self.scene_collections.iter().any(|c| c == name) {
+            return Err(already_exists("scene collection", name));
+        }
+
+        self.scene_collections.push(name.to_owned());
+        self.emit(
+            "SceneCollectionListChanged",
+            json!({ "sceneCollections": self.scene_collections }),
+        );
+        self.switch_scene_collection(name.to_owned());
+
+        Ok(Value::Null)
+    }
+
+    fn profile_names(&self) -> Vec<&str> {
+        self.profiles.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    pub(super) fn profile_list(&self) -> Value {
+        json!({
+            "currentProfileName": self.current_profile,
+            "profiles": self.profile_names(),
+        })
+    }
+
+    fn switch_profile(&mut self, name: String) {
+        if self.current_profile == name {
+            return;
+        }
+
+        self.emit(
+            "CurrentProfileChanging",
+            json!({ "profileName": self.current_profile }),
+        );
+        self.emit("CurrentProfileChanged", json!({ "profileName": name }));
+        self.current_profile = name;
+    }
+
+    fn emit_profile_list_changed(&mut self) {
+        let profiles = json!({ "profiles": self.profile_names() });
+        self.emit("ProfileListChanged", profiles);
+    }
+
+    pub(super) fn set_current_profile(&mut self, args: &Args<'_>) -> Response {
+        let name = args.str("profileName")?;
+        if self.profiles.iter().all(|p| p.name != name) {
+            return Err(not_found("profile", name));
+        }
+
+        self.switch_profile(name.to_owned());
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn create_profile(&mut self, args: &Args<'_>) -> Response {
+        let name = args.str("profileName")?;
+        if self.profiles.iter().any(|p| p.name == name) {
+            return Err(already_exists("profile", name));
+        }
+
+        self.profiles.push(Profile::new(name.to_owned()));
+        self.emit_profile_list_changed();
+        self.switch_profile(name.to_owned());
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn remove_profile(&mut self, args: &Args<'_>) -> Response {
+        let name = args.str("profileName")?;
+        if self.profiles.iter().all(|p| p.name != name) {
+            return Err(not_found("profile", name));
+        }
+
+        let fallback = self
+            .profiles
+            .iter()
+            .find(|p| p.name != name)
+            .map(|p| p.name.clone())
+            .ok_or_else(|| {
+                RequestError::new(
+                    StatusCode::NotEnoughResources,
+                    "The last profile can't be removed.",
+                )
+            })?;
+
+        if self.current_profile == name {
+            self.switch_profile(fallback);
+        }
+
+        self.profiles.retain(|p| p.name != name);
+        self.emit_profile_list_changed();
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn profile_parameter(&self, args: &Args<'_>) -> Response {
+        let category = args.str("parameterCategory")?;
+        let name = args.str("parameterName")?;
+
+        let value = self
+            .active_profile()
+            .parameters
+            .iter()
+            .find(|(c, n, _)| c == category && n == name)
+            .map(|(_, _, value)| value);
+
+        Ok(json!({ "parameterValue": value, "defaultParameterValue": null }))
+    }
+
+    pub(super) fn set_profile_parameter(&mut self, args: &Args<'_>) -> Response {
+        let category = args.str("parameterCategory")?;
+        let name = args.str("parameterName")?;
+        let value = args.opt_str("parameterValue")?;
+
+        let parameters = &mut self.active_profile_mut().parameters;
+        parameters.retain(|(c, n, _)| c != category || n != name);
+        if let Some(value) = value {
+            parameters.push((category.to_owned(), name.to_owned(), value.to_owned()));
+        }
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn video_settings(&self) -> Value {
+        json!({
+            "fpsNumerator": self.video.fps_numerator,
+            "fpsDenominator": self.video.fps_denominator,
+            "baseWidth": self.video.base_width,
+            "baseHeight": self.video.base_height,
+            "outputWidth": self.video.output_width,
+            "outputHeight": self.video.output_height,
+        })
+    }
+
+    pub(super) fn set_video_settings(&mut self, args: &Args<'_>) -> Response {
+        if self.outputs_active() {
+            return Err(RequestError::new(
+                StatusCode::OutputRunning,
+                "Video settings cannot be changed while an output is active.",
+            ));
+        }
+
+        // Values always come in pairs, where both or none of them must be set.
+        let pair = |first: &str, second: &str, min, max| {
+            let values = (args.opt_i64(first)?, args.opt_i64(second)?);
+            match values {
+                (Some(a), Some(b)) => Ok(Some((
+                    check_range(first, a, min, max)? as u32,
+                    check_range(second, b, min, max)? as u32,
+                ))),
+                (None, None) => Ok(None),
+                (Some(_), None) | (None, Some(_)) => Err(RequestError::new(
+                    StatusCode::MissingRequestField,
+                    format!(
+                        "The fields `{}` and `{}` must be specified together.",
+                        first, second
+                    ),
+                )),
+            }
+        };
+
+        let fps = pair("fpsNumerator", "fpsDenominator", 1, i64::from(u32::MAX))?;
+        let base = pair("baseWidth", "baseHeight", 8, 4096)?;
+        let output = pair("outputWidth", "outputHeight", 8, 4096)?;
+
+        if let Some((numerator, denominator)) = fps {
+            self.video.fps_numerator = numerator;
+            self.video.fps_denominator = denominator;
+        }
+        if let Some((width, height)) = base {
+            self.video.base_width = width;
+            self.video.base_height = height;
+        }
+        if let Some((width, height)) = output {
+            self.video.output_width = width;
+            self.video.output_height = height;
+        }
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn stream_service_settings(&self) -> Value {
+        json!({
+            "streamServiceType": self.stream_service.0,
+            "streamServiceSettings": self.stream_service.1,
+        })
+    }
+
+    pub(super) fn set_stream_service_settings(&mut self, args: &Args<'_>) -> Response {
+        let kind = args.str("streamServiceType")?;
+        let settings = args.object("streamServiceSettings")?;
+
+        if self.output_running(outputs::STREAM) {
+            return Err(RequestError::new(
+                StatusCode::OutputRunning,
+                "You cannot change stream service settings while streaming.",
+            ));
+        }
+
+        // Settings of the same service type are merged, otherwise they're replaced.
+        let overlay = self.stream_service.0 == kind;
+        apply_settings(&mut self.stream_service.1, settings, overlay);
+        self.stream_service.0 = kind.to_owned();
+
+        Ok(Value::Null)
+    }
+}
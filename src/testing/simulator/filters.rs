@@ -0,0 +1,239 @@
+use serde_json::{json, Value};
+
+use super::{
+    already_exists, apply_settings, check_range, not_found, Args, Filter, Response, Simulator,
+};
+use crate::{responses::StatusCode, testing::RequestError};
+
+/// Kinds of filters that are available for creation.
+const FILTER_KINDS: &[&str] = &[
+    "async_delay_filter",
+    "chroma_key_filter_v2",
+    "color_filter",
+    "color_filter_v2",
+    "color_key_filter_v2",
+    "compressor_filter",
+    "crop_filter",
+    "gain_filter",
+    "gpu_delay",
+    "limiter_filter",
+    "mask_filter_v2",
+    "noise_gate_filter",
+    "noise_suppress_filter_v2",
+    "scale_filter",
+    "scroll_filter",
+    "sharpness_filter_v2",
+];
+
+fn check_kind(kind: &str) -> Result<(), RequestError> {
+    if FILTER_KINDS.contains(&kind) {
+        Ok(())
+    } else {
+        Err(RequestError::new(
+            StatusCode::InvalidFilterKind,
+            format!(
+                "Your specified filter kind `{}` is not supported by OBS.",
+                kind
+            ),
+        ))
+    }
+}
+
+fn filter_json(index: usize, filter: &Filter) -> Value {
+    json!({
+        "filterEnabled": filter.enabled,
+        "filterIndex": index,
+        "filterKind": filter.kind,
+        "filterName": filter.name,
+        "filterSettings": filter.settings,
+    })
+}
+
+impl Simulator {
+    fn filters(&self, source: &str) -> Result<&Vec<Filter>, RequestError> {
+        if let Some(scene) = self.scenes.iter().find(|s| s.name == source) {
+            return Ok(&scene.filters);
+        }
+
+        self.inputs
+            .iter()
+            .find(|i| i.name == source)
+            .map(|i| &i.filters)
+            .ok_or_else(|| not_found("source", source))
+    }
+
+    /// Get the filters of a scene or input.
+    pub(super) fn filters_mut(&mut self, source: &str) -> Result<&mut Vec<Filter>, RequestError> {
+        if let Some(scene) = self.scenes.iter_mut().find(|s| s.name == source) {
+            return Ok(&mut scene.filters);
+        }
+
+        self.inputs
+            .iter_mut()
+            .find(|i| i.name == source)
+            .map(|i| &mut i.filters)
+            .ok_or_else(|| not_found("source", source))
+    }
+
+    /// Get the source name and position of the filter, that a request is targeted at.
+    fn filter_position(&self, args: &Args<'_>) -> Result<(String, usize), RequestError> {
+        let source = args.str("sourceName")?;
+        let name = args.str("filterName")?;
+
+        let index = self
+            .filters(source)?
+            .iter()
+            .position(|f| f.name == name)
+            .ok_or_else(|| not_found("filter", name))?;
+
+        Ok((source.to_owned(), index))
+    }
+
+    pub(super) fn filter_list(&self, args: &Args<'_>) -> Response {
+        let filters = self
+            .filters(args.str("sourceName")?)?
+            .iter()
+            .enumerate()
+            .map(|(index, filter)| filter_json(index, filter))
+            .collect::<Vec<_>>();
+
+        Ok(json!({ "filters": filters }))
+    }
+
+    pub(super) fn filter_default_settings(&self, args: &Args<'_>) -> Response {
+        check_kind(args.str("filterKind")?)?;
+        Ok(json!({ "defaultFilterSettings": {} }))
+    }
+
+    pub(super) fn create_filter(&mut self, args: &Args<'_>) -> Response {
+        let source = args.str("sourceName")?;
+        let name = args.str("filterName")?;
+        let kind = args.str("filterKind")?;
+        let settings = args
+            .opt_object("filterSettings")?
+            .cloned()
+            .unwrap_or_default();
+
+        let filters = self.filters_mut(source)?;
+        if filters.iter().any(|f| f.name == name) {
+            return Err(already_exists("filter", name));
+        }
+        check_kind(kind)?;
+
+        filters.push(Filter {
+            name: name.to_owned(),
+            kind: kind.to_owned(),
+            enabled: true,
+            settings: settings.clone(),
+        });
+        let index = filters.len() - 1;
+
+        self.emit(
+            "SourceFilterCreated",
+            json!({
+                "sourceName": source,
+                "filterName": name,
+                "filterKind": kind,
+                "filterIndex": index,
+                "filterSettings": settings,
+                "defaultFilterSettings": {},
+            }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn remove_filter(&mut self, args: &Args<'_>) -> Response {
+        let (source, index) = self.filter_position(args)?;
+        let filter = self.filters_mut(&source)?.remove(index);
+
+        self.emit(
+            "SourceFilterRemoved",
+            json!({ "sourceName": source, "filterName": filter.name }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn set_filter_name(&mut self, args: &Args<'_>) -> Response {
+        let (source, index) = self.filter_position(args)?;
+        let new_name = args.str("newFilterName")?;
+
+        let filters = self.filters_mut(&source)?;
+        if filters.iter().any(|f| f.name == new_name) {
+            return Err(already_exists("filter", new_name));
+        }
+        let old_name = std::mem::replace(&mut filters[index].name, new_name.to_owned());
+
+        self.emit(
+            "SourceFilterNameChanged",
+            json!({ "sourceName": source, "oldFilterName": old_name, "filterName": new_name }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn source_filter(&self, args: &Args<'_>) -> Response {
+        let (source, index) = self.filter_position(args)?;
+        let filter = &self.filters(&source)?[index];
+
+        Ok(json!({
+            "filterEnabled": filter.enabled,
+            "filterIndex": index,
+            "filterKind": filter.kind,
+            "filterSettings": filter.settings,
+        }))
+    }
+
+    pub(super) fn set_filter_index(&mut self, args: &Args<'_>) -> Response {
+        let (source, index) = self.filter_position(args)?;
+        let new_index = args.i64("filterIndex")?;
+        let new_index = check_range("filterIndex", new_index, 0, i64::MAX)? as usize;
+
+        let filters = self.filters_mut(&source)?;
+        let filter = filters.remove(index);
+        filters.insert(new_index.min(filters.len()), filter);
+
+        let filters = filters
+            .iter()
+            .enumerate()
+            .map(|(index, filter)| filter_json(index, filter))
+            .collect::<Vec<_>>();
+        self.emit(
+            "SourceFilterListReindexed",
+            json!({ "sourceName": source, "filters": filters }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn set_filter_settings(&mut self, args: &Args<'_>) -> Response {
+        let (source, index) = self.filter_position(args)?;
+        let settings = args.object("filterSettings")?;
+        let overlay = args.opt_bool("overlay")?.unwrap_or(true);
+
+        apply_settings(
+            &mut self.filters_mut(&source)?[index].settings,
+            settings,
+            overlay,
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn set_filter_enabled(&mut self, args: &Args<'_>) -> Response {
+        let (source, index) = self.filter_position(args)?;
+        let enabled = args.bool("filterEnabled")?;
+
+        let filter = &mut self.filters_mut(&source)?[index];
+        filter.enabled = enabled;
+        let name = filter.name.clone();
+
+        self.emit(
+            "SourceFilterEnableStateChanged",
+            json!({ "sourceName": source, "filterName": name, "filterEnabled": enabled }),
+        );
+
+        Ok(Value::Null)
+    }
+}
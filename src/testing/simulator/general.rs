@@ -0,0 +1,105 @@
+use serde_json::{json, Value};
+
+use super::{outputs, Args, Response, Simulator};
+use crate::{responses::StatusCode, testing::RequestError};
+
+/// Names of the frontend hotkeys, that are always registered in OBS.
+const HOTKEYS: &[&str] = &[
+    "OBSBasic.StartStreaming",
+    "OBSBasic.StopStreaming",
+    "OBSBasic.ForceStopStreaming",
+    "OBSBasic.StartRecording",
+    "OBSBasic.StopRecording",
+    "OBSBasic.PauseRecording",
+    "OBSBasic.UnpauseRecording",
+    "OBSBasic.StartReplayBuffer",
+    "OBSBasic.StopReplayBuffer",
+    "OBSBasic.StartVirtualCam",
+    "OBSBasic.StopVirtualCam",
+    "OBSBasic.EnablePreview",
+    "OBSBasic.DisablePreview",
+    "OBSBasic.ShowContextBar",
+    "OBSBasic.HideContextBar",
+    "OBSBasic.TogglePreviewProgram",
+    "OBSBasic.Transition",
+    "OBSBasic.ResetStats",
+    "OBSBasic.Screenshot",
+    "OBSBasic.SelectedSourceScreenshot",
+    "ReplayBuffer.Save",
+];
+
+impl Simulator {
+    pub(super) fn stats(&self) -> Value {
+        let frames = self.started.elapsed().as_millis() as u64
+            * u64::from(self.video.fps_numerator)
+            / u64::from(self.video.fps_denominator)
+            / 1000;
+
+        json!({
+            "cpuUsage": 1.5,
+            "memoryUsage": 256.0,
+            "availableDiskSpace": 100_000.0,
+            "activeFps": f64::from(self.video.fps_numerator) / f64::from(self.video.fps_denominator),
+            "averageFrameRenderTime": 0.5,
+            "renderSkippedFrames": 0,
+            "renderTotalFrames": frames,
+            "outputSkippedFrames": 0,
+            "outputTotalFrames": frames,
+            "webSocketSessionIncomingMessages": 0,
+            "webSocketSessionOutgoingMessages": 0,
+        })
+    }
+
+    pub(super) fn broadcast_custom_event(&mut self, args: &Args<'_>) -> Response {
+        let data = args.object("eventData")?;
+        self.emit("CustomEvent", Value::Object(data.clone()));
+        Ok(Value::Null)
+    }
+
+    pub(super) fn call_vendor_request(&self, args: &Args<'_>) -> Response {
+        let vendor = args.str("vendorName")?;
+        args.str("requestType")?;
+
+        // No plugins are loaded in the simulator, so there are never any vendors.
+        Err(RequestError::new(
+            StatusCode::ResourceNotFound,
+            format!("No vendor was found by the name of `{}`.", vendor),
+        ))
+    }
+
+    pub(super) fn hotkey_list(&self) -> Value {
+        json!({ "hotkeys": HOTKEYS })
+    }
+
+    pub(super) fn trigger_hotkey_by_name(&mut self, args: &Args<'_>) -> Response {
+        let name = args.str("hotkeyName")?;
+
+        // Hotkeys silently do nothing if their action is not possible in the current state, so
+        // any errors are ignored here.
+        let _ = match name {
+            "OBSBasic.StartStreaming" => self.start_output(outputs::STREAM),
+            "OBSBasic.StopStreaming" | "OBSBasic.ForceStopStreaming" => {
+                self.stop_output(outputs::STREAM)
+            }
+            "OBSBasic.StartRecording" => self.start_output(outputs::RECORD),
+            "OBSBasic.StopRecording" => self.stop_output(outputs::RECORD),
+            "OBSBasic.PauseRecording" => self.pause_record(),
+            "OBSBasic.UnpauseRecording" => self.resume_record(),
+            "OBSBasic.StartReplayBuffer" => self.start_output(outputs::REPLAY_BUFFER),
+            "OBSBasic.StopReplayBuffer" => self.stop_output(outputs::REPLAY_BUFFER),
+            "OBSBasic.StartVirtualCam" => self.start_output(outputs::VIRTUAL_CAM),
+            "OBSBasic.StopVirtualCam" => self.stop_output(outputs::VIRTUAL_CAM),
+            "OBSBasic.Transition" => self.trigger_studio_mode_transition(),
+            "ReplayBuffer.Save" => self.save_replay_buffer(),
+            _ if HOTKEYS.contains(&name) => Ok(Value::Null),
+            _ => {
+                return Err(RequestError::new(
+                    StatusCode::ResourceNotFound,
+                    format!("No hotkey was found by the name of `{}`.", name),
+                ))
+            }
+        };
+
+        Ok(Value::Null)
+    }
+}
@@ -0,0 +1,570 @@
+use serde_json::{json, Map, Value};
+
+use super::{already_exists, apply_settings, check_range, Args, Input, Response, Simulator};
+use crate::{responses::StatusCode, testing::RequestError};
+
+/// Duration of the media, that is played by any media input.
+pub(super) const MEDIA_DURATION: i64 = 60_000;
+
+/// Kinds of inputs that are available for creation.
+const INPUT_KINDS: &[&str] = &[
+    "browser_source",
+    "color_source_v3",
+    "ffmpeg_source",
+    "image_source",
+    "monitor_capture",
+    "pulse_input_capture",
+    "pulse_output_capture",
+    "slideshow",
+    "text_ft2_source_v2",
+    "v4l2_input",
+    "vlc_source",
+    "window_capture",
+    "xshm_input",
+];
+
+/// Kinds of inputs, that only provide video, but no audio.
+const VIDEO_ONLY_KINDS: &[&str] = &[
+    "color_source_v3",
+    "image_source",
+    "monitor_capture",
+    "slideshow",
+    "text_ft2_source_v2",
+    "window_capture",
+    "xshm_input",
+];
+
+/// Possible values for the monitor type of an input.
+const MONITOR_TYPES: &[&str] = &[
+    "OBS_MONITORING_TYPE_NONE",
+    "OBS_MONITORING_TYPE_MONITOR_ONLY",
+    "OBS_MONITORING_TYPE_MONITOR_AND_OUTPUT",
+];
+
+/// Names of special audio inputs, that OBS creates for the global audio devices.
+const SPECIAL_INPUTS: &[(&str, &str)] = &[
+    ("desktop1", "Desktop Audio"),
+    ("desktop2", "Desktop Audio 2"),
+    ("mic1", "Mic/Aux"),
+    ("mic2", "Mic/Aux 2"),
+    ("mic3", "Mic/Aux 3"),
+    ("mic4", "Mic/Aux 4"),
+];
+
+/// Whether inputs of the given kind play back media files.
+pub(super) fn is_media_kind(kind: &str) -> bool {
+    kind == "ffmpeg_source" || kind == "vlc_source"
+}
+
+/// Remove the version suffix (like `_v2`) from an input kind.
+fn unversioned_kind(kind: &str) -> &str {
+    match kind.rsplit_once("_v") {
+        Some((base, version)) if version.bytes().all(|b| b.is_ascii_digit()) => base,
+        _ => kind,
+    }
+}
+
+fn check_kind(kind: &str) -> Result<(), RequestError> {
+    if INPUT_KINDS.contains(&kind) {
+        Ok(())
+    } else {
+        Err(RequestError::new(
+            StatusCode::InvalidInputKind,
+            format!(
+                "Your specified input kind `{}` is not supported by OBS.",
+                kind
+            ),
+        ))
+    }
+}
+
+/// Convert a linear volume multiplier into decibels.
+fn mul_to_db(mul: f64) -> f64 {
+    if mul > 0.0 {
+        20.0 * mul.log10()
+    } else {
+        -100.0
+    }
+}
+
+impl Simulator {
+    /// Get an input, that is capable of playing audio.
+    fn audio_input(&self, args: &Args<'_>) -> Result<&Input, RequestError> {
+        let input = self.find_input(args.str("inputName")?)?;
+        if VIDEO_ONLY_KINDS.contains(&input.kind.as_str()) {
+            return Err(RequestError::new(
+                StatusCode::InvalidResourceState,
+                "The specified input does not support audio.",
+            ));
+        }
+
+        Ok(input)
+    }
+
+    /// Modify an audio input, returning its name for events.
+    fn update_audio_input(
+        &mut self,
+        args: &Args<'_>,
+        update: impl FnOnce(&mut Input),
+    ) -> Result<String, RequestError> {
+        let name = self.audio_input(args)?.name.clone();
+        update(self.find_input_mut(&name)?);
+        Ok(name)
+    }
+
+    pub(super) fn input_list(&self, args: &Args<'_>) -> Response {
+        let kind = args.opt_str("inputKind")?;
+        let inputs = self
+            .inputs
+            .iter()
+            .filter(|input| kind.map_or(true, |kind| input.kind == kind))
+            .map(|input| {
+                json!({
+                    "inputName": input.name,
+                    "inputKind": input.kind,
+                    "unversionedInputKind": unversioned_kind(&input.kind),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(json!({ "inputs": inputs }))
+    }
+
+    pub(super) fn input_kind_list(&self, args: &Args<'_>) -> Response {
+        let unversioned = args.opt_bool("unversioned")?.unwrap_or_default();
+        let kinds = INPUT_KINDS
+            .iter()
+            .map(|kind| {
+                if unversioned {
+                    unversioned_kind(kind)
+                } else {
+                    kind
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Ok(json!({ "inputKinds": kinds }))
+    }
+
+    pub(super) fn special_inputs(&self) -> Value {
+        let specials = SPECIAL_INPUTS
+            .iter()
+            .map(|(key, name)| {
+                let name = self.inputs.iter().any(|i| i.name == *name).then(|| *name);
+                (key.to_string(), json!(name))
+            })
+            .collect::<Map<_, _>>();
+
+        Value::Object(specials)
+    }
+
+    pub(super) fn create_input(&mut self, args: &Args<'_>) -> Response {
+        let scene = self.find_scene(args.str("sceneName")?)?.name.clone();
+        let name = args.str("inputName")?;
+        let kind = args.str("inputKind")?;
+        let settings = args
+            .opt_object("inputSettings")?
+            .cloned()
+            .unwrap_or_default();
+        let enabled = args.opt_bool("sceneItemEnabled")?.unwrap_or(true);
+
+        if self.source_exists(name) {
+            return Err(already_exists("source", name));
+        }
+        check_kind(kind)?;
+
+        self.inputs.push(Input::new(
+            name.to_owned(),
+            kind.to_owned(),
+            settings.clone(),
+        ));
+        self.emit(
+            "InputCreated",
+            json!({
+                "inputName": name,
+                "inputKind": kind,
+                "unversionedInputKind": unversioned_kind(kind),
+                "inputSettings": settings,
+                "defaultInputSettings": {},
+            }),
+        );
+
+        let transform = self.default_transform(name);
+        let target = self.find_scene_mut(&scene)?;
+        let id = target.add_item(name.to_owned(), enabled, transform);
+        let index = target.items.len() - 1;
+        self.emit(
+            "SceneItemCreated",
+            json!({
+                "sceneName": scene,
+                "sourceName": name,
+                "sceneItemId": id,
+                "sceneItemIndex": index,
+            }),
+        );
+
+        Ok(json!({ "sceneItemId": id }))
+    }
+
+    pub(super) fn remove_input(&mut self, args: &Args<'_>) -> Response {
+        let name = self.find_input(args.str("inputName")?)?.name.clone();
+
+        self.remove_source_items(&name);
+        self.inputs.retain(|i| i.name != name);
+        self.emit("InputRemoved", json!({ "inputName": name }));
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn set_input_name(&mut self, args: &Args<'_>) -> Response {
+        let name = self.find_input(args.str("inputName")?)?.name.clone();
+        let new_name = args.str("newInputName")?;
+        if self.source_exists(new_name) {
+            return Err(already_exists("source", new_name));
+        }
+
+        self.find_input_mut(&name)?.name = new_name.to_owned();
+        self.rename_source_items(&name, new_name);
+        self.emit(
+            "InputNameChanged",
+            json!({ "oldInputName": name, "inputName": new_name }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn input_default_settings(&self, args: &Args<'_>) -> Response {
+        check_kind(args.str("inputKind")?)?;
+        Ok(json!({ "defaultInputSettings": {} }))
+    }
+
+    pub(super) fn input_settings(&self, args: &Args<'_>) -> Response {
+        let input = self.find_input(args.str("inputName")?)?;
+        Ok(json!({ "inputSettings": input.settings, "inputKind": input.kind }))
+    }
+
+    pub(super) fn set_input_settings(&mut self, args: &Args<'_>) -> Response {
+        let settings = args.object("inputSettings")?;
+        let overlay = args.opt_bool("overlay")?.unwrap_or(true);
+        let input = self.find_input_mut(args.str("inputName")?)?;
+
+        apply_settings(&mut input.settings, settings, overlay);
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn input_mute(&self, args: &Args<'_>) -> Response {
+        Ok(json!({ "inputMuted": self.audio_input(args)?.muted }))
+    }
+
+    pub(super) fn set_input_mute(&mut self, args: &Args<'_>) -> Response {
+        let muted = args.bool("inputMuted")?;
+        self.change_input_mute(args, |_| muted)?;
+        Ok(Value::Null)
+    }
+
+    pub(super) fn toggle_input_mute(&mut self, args: &Args<'_>) -> Response {
+        let muted = self.change_input_mute(args, |muted| !muted)?;
+        Ok(json!({ "inputMuted": muted }))
+    }
+
+    fn change_input_mute(
+        &mut self,
+        args: &Args<'_>,
+        change: impl FnOnce(bool) -> bool,
+    ) -> Result<bool, RequestError> {
+        let input = self.audio_input(args)?;
+        let (name, old) = (input.name.clone(), input.muted);
+        let muted = change(old);
+
+        if muted != old {
+            self.find_input_mut(&name)?.muted = muted;
+            self.emit(
+                "InputMuteStateChanged",
+                json!({ "inputName": name, "inputMuted": muted }),
+            );
+        }
+
+        Ok(muted)
+    }
+
+    pub(super) fn input_volume(&self, args: &Args<'_>) -> Response {
+        let mul = self.audio_input(args)?.volume_mul;
+        Ok(json!({ "inputVolumeMul": mul, "inputVolumeDb": mul_to_db(mul) }))
+    }
+
+    pub(super) fn set_input_volume(&mut self, args: &Args<'_>) -> Response {
+        let mul = match (
+            args.opt_f64("inputVolumeMul")?,
+            args.opt_f64("inputVolumeDb")?,
+        ) {
+            (Some(mul), None) => check_range("inputVolumeMul", mul, 0.0, 20.0)?,
+            (None, Some(db)) => 10_f64.powf(check_range("inputVolumeDb", db, -100.0, 26.0)? / 20.0),
+            (Some(_), Some(_)) => {
+                return Err(RequestError::new(
+                    StatusCode::TooManyRequestFields,
+                    "You may only specify one volume field.",
+                ))
+            }
+            (None, None) => {
+                return Err(RequestError::new(
+                    StatusCode::MissingRequestField,
+                    "You must specify one volume field.",
+                ))
+            }
+        };
+
+        let name = self.update_audio_input(args, |input| input.volume_mul = mul)?;
+        self.emit(
+            "InputVolumeChanged",
+            json!({ "inputName": name, "inputVolumeMul": mul, "inputVolumeDb": mul_to_db(mul) }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn input_audio_balance(&self, args: &Args<'_>) -> Response {
+        Ok(json!({ "inputAudioBalance": self.audio_input(args)?.audio_balance }))
+    }
+
+    pub(super) fn set_input_audio_balance(&mut self, args: &Args<'_>) -> Response {
+        let balance = args.f64("inputAudioBalance")?;
+        let balance = check_range("inputAudioBalance", balance, 0.0, 1.0)?;
+
+        let name = self.update_audio_input(args, |input| input.audio_balance = balance)?;
+        self.emit(
+            "InputAudioBalanceChanged",
+            json!({ "inputName": name, "inputAudioBalance": balance }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn input_audio_sync_offset(&self, args: &Args<'_>) -> Response {
+        Ok(json!({ "inputAudioSyncOffset": self.audio_input(args)?.audio_sync_offset }))
+    }
+
+    pub(super) fn set_input_audio_sync_offset(&mut self, args: &Args<'_>) -> Response {
+        let offset = args.i64("inputAudioSyncOffset")?;
+        let offset = check_range("inputAudioSyncOffset", offset, -950, 20000)?;
+
+        let name = self.update_audio_input(args, |input| input.audio_sync_offset = offset)?;
+        self.emit(
+            "InputAudioSyncOffsetChanged",
+            json!({ "inputName": name, "inputAudioSyncOffset": offset }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn input_audio_monitor_type(&self, args: &Args<'_>) -> Response {
+        Ok(json!({ "monitorType": self.audio_input(args)?.monitor_type }))
+    }
+
+    pub(super) fn set_input_audio_monitor_type(&mut self, args: &Args<'_>) -> Response {
+        let monitor_type = args.str("monitorType")?;
+        if !MONITOR_TYPES.contains(&monitor_type) {
+            return Err(RequestError::new(
+                StatusCode::InvalidRequestField,
+                "The field monitorType has an invalid value.",
+            ));
+        }
+
+        let name =
+            self.update_audio_input(args, |input| input.monitor_type = monitor_type.to_owned())?;
+        self.emit(
+            "InputAudioMonitorTypeChanged",
+            json!({ "inputName": name, "monitorType": monitor_type }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn input_audio_tracks(&self, args: &Args<'_>) -> Response {
+        let tracks = audio_tracks_json(&self.audio_input(args)?.audio_tracks);
+        Ok(json!({ "inputAudioTracks": tracks }))
+    }
+
+    pub(super) fn set_input_audio_tracks(&mut self, args: &Args<'_>) -> Response {
+        let changes = args.object("inputAudioTracks")?;
+        let mut tracks = self.audio_input(args)?.audio_tracks;
+
+        for (track, enabled) in changes {
+            let index = track
+                .parse::<usize>()
+                .ok()
+                .filter(|track| (1..=6).contains(track))
+                .ok_or_else(|| {
+                    RequestError::new(
+                        StatusCode::InvalidRequestField,
+                        format!("The audio track `{}` is invalid.", track),
+                    )
+                })?;
+            tracks[index - 1] = enabled.as_bool().ok_or_else(|| {
+                RequestError::new(
+                    StatusCode::InvalidRequestFieldType,
+                    format!("The value of audio track `{}` is invalid.", track),
+                )
+            })?;
+        }
+
+        let name = self.update_audio_input(args, |input| input.audio_tracks = tracks)?;
+        self.emit(
+            "InputAudioTracksChanged",
+            json!({ "inputName": name, "inputAudioTracks": audio_tracks_json(&tracks) }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn input_property_items(&self, args: &Args<'_>) -> Response {
+        self.find_input(args.str("inputName")?)?;
+        args.str("propertyName")?;
+
+        Ok(json!({ "propertyItems": [] }))
+    }
+
+    pub(super) fn press_input_properties_button(&self, args: &Args<'_>) -> Response {
+        self.find_input(args.str("inputName")?)?;
+        args.str("propertyName")?;
+
+        Ok(Value::Null)
+    }
+
+    /// Get a media input, returning its name.
+    fn media_input(&self, args: &Args<'_>) -> Result<String, RequestError> {
+        let input = self.find_input(args.str("inputName")?)?;
+        match input.media {
+            Some(_) => Ok(input.name.clone()),
+            None => Err(RequestError::new(
+                StatusCode::InvalidResourceType,
+                "The specified input is not a media input.",
+            )),
+        }
+    }
+
+    pub(super) fn media_input_status(&self, args: &Args<'_>) -> Response {
+        let name = self.media_input(args)?;
+        let media = self
+            .find_input(&name)?
+            .media
+            .as_ref()
+            .expect("input is a media input");
+        let running = matches!(
+            media.state,
+            "OBS_MEDIA_STATE_PLAYING" | "OBS_MEDIA_STATE_PAUSED"
+        );
+
+        Ok(json!({
+            "mediaState": media.state,
+            "mediaDuration": media.duration,
+            "mediaCursor": running.then(|| media.cursor),
+        }))
+    }
+
+    /// Move the cursor of a media input, that is playing or paused.
+    fn move_media_cursor(
+        &mut self,
+        args: &Args<'_>,
+        cursor: impl FnOnce(i64) -> i64,
+    ) -> Result<(), RequestError> {
+        let name = self.media_input(args)?;
+        let media = self
+            .find_input_mut(&name)?
+            .media
+            .as_mut()
+            .expect("input is a media input");
+
+        if !matches!(
+            media.state,
+            "OBS_MEDIA_STATE_PLAYING" | "OBS_MEDIA_STATE_PAUSED"
+        ) {
+            return Err(RequestError::new(
+                StatusCode::InvalidResourceState,
+                "The media input must be playing or paused in order to set the cursor position.",
+            ));
+        }
+
+        media.cursor = cursor(media.cursor).clamp(0, media.duration);
+        Ok(())
+    }
+
+    pub(super) fn set_media_input_cursor(&mut self, args: &Args<'_>) -> Response {
+        let cursor = args.i64("mediaCursor")?;
+        let cursor = check_range("mediaCursor", cursor, 0, i64::MAX)?;
+
+        self.move_media_cursor(args, |_| cursor)?;
+        Ok(Value::Null)
+    }
+
+    pub(super) fn offset_media_input_cursor(&mut self, args: &Args<'_>) -> Response {
+        let offset = args.i64("mediaCursorOffset")?;
+
+        self.move_media_cursor(args, |cursor| cursor + offset)?;
+        Ok(Value::Null)
+    }
+
+    pub(super) fn trigger_media_input_action(&mut self, args: &Args<'_>) -> Response {
+        let action = args.str("mediaAction")?;
+        let name = self.media_input(args)?;
+        let media = self
+            .find_input_mut(&name)?
+            .media
+            .as_mut()
+            .expect("input is a media input");
+        let stopped = !matches!(
+            media.state,
+            "OBS_MEDIA_STATE_PLAYING" | "OBS_MEDIA_STATE_PAUSED"
+        );
+
+        let started = match action {
+            "OBS_WEBSOCKET_MEDIA_INPUT_ACTION_NONE"
+            | "OBS_WEBSOCKET_MEDIA_INPUT_ACTION_NEXT"
+            | "OBS_WEBSOCKET_MEDIA_INPUT_ACTION_PREVIOUS" => false,
+            "OBS_WEBSOCKET_MEDIA_INPUT_ACTION_PLAY" => {
+                media.state = "OBS_MEDIA_STATE_PLAYING";
+                stopped
+            }
+            "OBS_WEBSOCKET_MEDIA_INPUT_ACTION_PAUSE" => {
+                media.state = "OBS_MEDIA_STATE_PAUSED";
+                false
+            }
+            "OBS_WEBSOCKET_MEDIA_INPUT_ACTION_STOP" => {
+                media.state = "OBS_MEDIA_STATE_STOPPED";
+                media.cursor = 0;
+                false
+            }
+            "OBS_WEBSOCKET_MEDIA_INPUT_ACTION_RESTART" => {
+                media.state = "OBS_MEDIA_STATE_PLAYING";
+                media.cursor = 0;
+                true
+            }
+            _ => {
+                return Err(RequestError::new(
+                    StatusCode::InvalidRequestField,
+                    "You have specified an invalid media input action.",
+                ))
+            }
+        };
+
+        self.emit(
+            "MediaInputActionTriggered",
+            json!({ "inputName": name, "mediaAction": action }),
+        );
+        if started {
+            self.emit("MediaInputPlaybackStarted", json!({ "inputName": name }));
+        }
+
+        Ok(Value::Null)
+    }
+}
+
+fn audio_tracks_json(tracks: &[bool; 6]) -> Value {
+    let tracks = tracks
+        .iter()
+        .enumerate()
+        .map(|(index, enabled)| ((index + 1).to_string(), Value::Bool(*enabled)))
+        .collect::<Map<_, _>>();
+
+    Value::Object(tracks)
+}
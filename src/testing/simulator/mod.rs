@@ -0,0 +1,1053 @@
+//! A simulated OBS instance, that keeps track of its state and handles requests like the real
+//! obs-websocket plugin would.
+
+use std::time::Instant;
+
+use serde_json::{json, Map, Value};
+
+use super::RequestError;
+use crate::responses::StatusCode;
+
+mod config;
+mod filters;
+mod general;
+mod inputs;
+mod outputs;
+mod scene_items;
+mod scenes;
+mod transitions;
+mod ui;
+
+/// All request types that are handled by the [`Simulator`].
+pub(super) const REQUEST_TYPES: &[&str] = &[
+    // Config
+    "GetPersistentData",
+    "SetPersistentData",
+    "GetSceneCollectionList",
+    "SetCurrentSceneCollection",
+    "CreateSceneCollection",
+    "GetProfileList",
+    "SetCurrentProfile",
+    "CreateProfile",
+    "RemoveProfile",
+    "GetProfileParameter",
+    "SetProfileParameter",
+    "GetVideoSettings",
+    "SetVideoSettings",
+    "GetStreamServiceSettings",
+    "SetStreamServiceSettings",
+    "GetRecordDirectory",
+    // Filters
+    "GetSourceFilterList",
+    "GetSourceFilterDefaultSettings",
+    "CreateSourceFilter",
+    "RemoveSourceFilter",
+    "SetSourceFilterName",
+    "GetSourceFilter",
+    "SetSourceFilterIndex",
+    "SetSourceFilterSettings",
+    "SetSourceFilterEnabled",
+    // General
+    "GetStats",
+    "BroadcastCustomEvent",
+    "CallVendorRequest",
+    "GetHotkeyList",
+    "TriggerHotkeyByName",
+    "TriggerHotkeyByKeySequence",
+    // Inputs
+    "GetInputList",
+    "GetInputKindList",
+    "GetSpecialInputs",
+    "CreateInput",
+    "RemoveInput",
+    "SetInputName",
+    "GetInputDefaultSettings",
+    "GetInputSettings",
+    "SetInputSettings",
+    "GetInputMute",
+    "SetInputMute",
+    "ToggleInputMute",
+    "GetInputVolume",
+    "SetInputVolume",
+    "GetInputAudioBalance",
+    "SetInputAudioBalance",
+    "GetInputAudioSyncOffset",
+    "SetInputAudioSyncOffset",
+    "GetInputAudioMonitorType",
+    "SetInputAudioMonitorType",
+    "GetInputAudioTracks",
+    "SetInputAudioTracks",
+    "GetInputPropertiesListPropertyItems",
+    "PressInputPropertiesButton",
+    // Media inputs
+    "GetMediaInputStatus",
+    "SetMediaInputCursor",
+    "OffsetMediaInputCursor",
+    "TriggerMediaInputAction",
+    // Outputs
+    "GetVirtualCamStatus",
+    "ToggleVirtualCam",
+    "StartVirtualCam",
+    "StopVirtualCam",
+    "GetReplayBufferStatus",
+    "ToggleReplayBuffer",
+    "StartReplayBuffer",
+    "StopReplayBuffer",
+    "SaveReplayBuffer",
+    "GetLastReplayBufferReplay",
+    "GetOutputList",
+    "GetOutputStatus",
+    "ToggleOutput",
+    "StartOutput",
+    "StopOutput",
+    "GetOutputSettings",
+    "SetOutputSettings",
+    // Record
+    "GetRecordStatus",
+    "ToggleRecord",
+    "StartRecord",
+    "StopRecord",
+    "ToggleRecordPause",
+    "PauseRecord",
+    "ResumeRecord",
+    // Scene items
+    "GetSceneItemList",
+    "GetGroupSceneItemList",
+    "GetSceneItemId",
+    "CreateSceneItem",
+    "RemoveSceneItem",
+    "DuplicateSceneItem",
+    "GetSceneItemTransform",
+    "SetSceneItemTransform",
+    "GetSceneItemEnabled",
+    "SetSceneItemEnabled",
+    "GetSceneItemLocked",
+    "SetSceneItemLocked",
+    "GetSceneItemIndex",
+    "SetSceneItemIndex",
+    "GetSceneItemBlendMode",
+    "SetSceneItemBlendMode",
+    "GetSceneItemPrivateSettings",
+    "SetSceneItemPrivateSettings",
+    // Scenes
+    "GetSceneList",
+    "GetGroupList",
+    "GetCurrentProgramScene",
+    "SetCurrentProgramScene",
+    "GetCurrentPreviewScene",
+    "SetCurrentPreviewScene",
+    "CreateScene",
+    "RemoveScene",
+    "SetSceneName",
+    "GetSceneSceneTransitionOverride",
+    "SetSceneSceneTransitionOverride",
+    // Sources
+    "GetSourceActive",
+    "GetSourceScreenshot",
+    "SaveSourceScreenshot",
+    // Stream
+    "GetStreamStatus",
+    "ToggleStream",
+    "StartStream",
+    "StopStream",
+    "SendStreamCaption",
+    // Transitions
+    "GetTransitionKindList",
+    "GetSceneTransitionList",
+    "GetCurrentSceneTransition",
+    "SetCurrentSceneTransition",
+    "SetCurrentSceneTransitionDuration",
+    "SetCurrentSceneTransitionSettings",
+    "GetCurrentSceneTransitionCursor",
+    "TriggerStudioModeTransition",
+    "SetTBarPosition",
+    // UI
+    "GetStudioModeEnabled",
+    "SetStudioModeEnabled",
+    "OpenInputPropertiesDialog",
+    "OpenInputFiltersDialog",
+    "OpenInputInteractDialog",
+    "GetMonitorList",
+    "OpenVideoMixProjector",
+    "OpenSourceProjector",
+];
+
+/// Result of handling a single request, containing the response data on success.
+type Response = Result<Value, RequestError>;
+
+/// Simulated state of an OBS instance, that backs a [`MockServer`](super::MockServer).
+///
+/// It keeps track of scenes, scene items, inputs, filters, transitions, outputs, profiles and
+/// scene collections, and handles all requests of this crate that don't need a real video
+/// pipeline. Requests are validated like obs-websocket does, failing with the same status codes
+/// (for example [`StatusCode::ResourceNotFound`] or [`StatusCode::OutputRunning`]), and every
+/// change of the state emits the matching events to all connected clients.
+///
+/// A new simulator contains a single scene named `Scene`, the `Cut` and `Fade` transitions and
+/// an `Untitled` profile and scene collection. Further content is added with the builder
+/// methods:
+///
+/// ```
+/// use obws::testing::Simulator;
+///
+/// let simulator = Simulator::new()
+///     .scene("Gaming")
+///     .input("Gaming", "Capture", "game_capture")
+///     .filter("Capture", "Color", "color_filter_v2")
+///     .profile("Streaming");
+/// ```
+///
+/// **Note:** Scene collections are only tracked by name. Switching between them keeps the
+/// scenes and inputs as they are.
+#[derive(Debug)]
+pub struct Simulator {
+    scenes: Vec<Scene>,
+    inputs: Vec<Input>,
+    program_scene: String,
+    /// Current preview scene, which is only set while studio mode is enabled.
+    preview_scene: Option<String>,
+    transitions: Vec<Transition>,
+    current_transition: String,
+    transition_duration: i64,
+    outputs: Vec<Output>,
+    last_replay: Option<String>,
+    profiles: Vec<Profile>,
+    current_profile: String,
+    scene_collections: Vec<String>,
+    current_scene_collection: String,
+    global_data: Map<String, Value>,
+    video: VideoSettings,
+    stream_service: (String, Map<String, Value>),
+    record_directory: String,
+    started: Instant,
+    /// Events that were emitted while handling the current request.
+    events: Vec<(&'static str, Value)>,
+}
+
+/// A scene or group, which contain a list of scene items.
+#[derive(Debug)]
+struct Scene {
+    name: String,
+    is_group: bool,
+    items: Vec<SceneItem>,
+    next_item_id: i64,
+    filters: Vec<Filter>,
+    transition_override: Option<String>,
+    transition_override_duration: Option<i64>,
+}
+
+/// Instance of a source within a scene.
+#[derive(Clone, Debug)]
+struct SceneItem {
+    id: i64,
+    source: String,
+    enabled: bool,
+    locked: bool,
+    transform: Map<String, Value>,
+    blend_mode: String,
+    private_settings: Value,
+}
+
+#[derive(Debug)]
+struct Input {
+    name: String,
+    kind: String,
+    settings: Map<String, Value>,
+    muted: bool,
+    volume_mul: f64,
+    audio_balance: f64,
+    audio_sync_offset: i64,
+    monitor_type: String,
+    audio_tracks: [bool; 6],
+    filters: Vec<Filter>,
+    media: Option<Media>,
+}
+
+/// Playback state of a media input.
+#[derive(Debug)]
+struct Media {
+    state: &'static str,
+    cursor: i64,
+    duration: i64,
+}
+
+#[derive(Debug)]
+struct Filter {
+    name: String,
+    kind: String,
+    enabled: bool,
+    settings: Map<String, Value>,
+}
+
+#[derive(Debug)]
+struct Transition {
+    name: String,
+    kind: String,
+    fixed: bool,
+    configurable: bool,
+    settings: Map<String, Value>,
+}
+
+#[derive(Debug)]
+struct Output {
+    name: String,
+    kind: String,
+    active: bool,
+    paused: bool,
+    started: Option<Instant>,
+    settings: Map<String, Value>,
+}
+
+#[derive(Debug)]
+struct Profile {
+    name: String,
+    parameters: Vec<(String, String, String)>,
+    data: Map<String, Value>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct VideoSettings {
+    fps_numerator: u32,
+    fps_denominator: u32,
+    base_width: u32,
+    base_height: u32,
+    output_width: u32,
+    output_height: u32,
+}
+
+impl Default for Simulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Simulator {
+    /// Create a simulator with the same content, that a fresh installation of OBS has.
+    pub fn new() -> Self {
+        Self {
+            scenes: vec![Scene::new("Scene".to_owned(), false)],
+            inputs: Vec::new(),
+            program_scene: "Scene".to_owned(),
+            preview_scene: None,
+            transitions: vec![
+                Transition::new("Cut", "cut_transition", true),
+                Transition::new("Fade", "fade_transition", false),
+            ],
+            current_transition: "Fade".to_owned(),
+            transition_duration: 300,
+            outputs: vec![
+                Output::new(outputs::STREAM, "rtmp_output"),
+                Output::new(outputs::RECORD, "ffmpeg_muxer"),
+                Output::new(outputs::REPLAY_BUFFER, "replay_buffer"),
+                Output::new(outputs::VIRTUAL_CAM, "virtualcam_output"),
+            ],
+            last_replay: None,
+            profiles: vec![Profile::new("Untitled".to_owned())],
+            current_profile: "Untitled".to_owned(),
+            scene_collections: vec!["Untitled".to_owned()],
+            current_scene_collection: "Untitled".to_owned(),
+            global_data: Map::new(),
+            video: VideoSettings {
+                fps_numerator: 60,
+                fps_denominator: 1,
+                base_width: 1920,
+                base_height: 1080,
+                output_width: 1920,
+                output_height: 1080,
+            },
+            stream_service: (
+                "rtmp_common".to_owned(),
+                [("server", "auto"), ("service", "Twitch")]
+                    .into_iter()
+                    .map(|(k, v)| (k.to_owned(), Value::from(v)))
+                    .collect(),
+            ),
+            record_directory: "/home/obs/Videos".to_owned(),
+            started: Instant::now(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Add an empty scene.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a source with the same name already exists.
+    pub fn scene(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.expect_unique(&name);
+        self.scenes.push(Scene::new(name, false));
+        self
+    }
+
+    /// Add an empty group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a source with the same name already exists.
+    pub fn group(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.expect_unique(&name);
+        self.scenes.push(Scene::new(name, true));
+        self
+    }
+
+    /// Add an input of the given kind, together with a scene item for it in the given scene or
+    /// group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a source with the same name already exists, or the scene doesn't exist.
+    pub fn input(
+        mut self,
+        scene: impl AsRef<str>,
+        name: impl Into<String>,
+        kind: impl Into<String>,
+    ) -> Self {
+        let name = name.into();
+        self.expect_unique(&name);
+        self.inputs
+            .push(Input::new(name.clone(), kind.into(), Map::new()));
+
+        let transform = self.default_transform(&name);
+        self.scenes
+            .iter_mut()
+            .find(|s| s.name == scene.as_ref())
+            .unwrap_or_else(|| panic!("scene `{}` doesn't exist", scene.as_ref()))
+            .add_item(name, true, transform);
+        self
+    }
+
+    /// Add a filter of the given kind to a scene or input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source doesn't exist, or already has a filter with the same name.
+    pub fn filter(
+        mut self,
+        source: impl AsRef<str>,
+        name: impl Into<String>,
+        kind: impl Into<String>,
+    ) -> Self {
+        let name = name.into();
+        let filters = self
+            .filters_mut(source.as_ref())
+            .unwrap_or_else(|_| panic!("source `{}` doesn't exist", source.as_ref()));
+
+        assert!(
+            filters.iter().all(|f| f.name != name),
+            "filter `{}` already exists",
+            name
+        );
+        filters.push(Filter {
+            name,
+            kind: kind.into(),
+            enabled: true,
+            settings: Map::new(),
+        });
+        self
+    }
+
+    /// Add another scene transition of the given kind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transition already exists.
+    pub fn transition(mut self, name: impl Into<String>, kind: impl AsRef<str>) -> Self {
+        let name = name.into();
+        assert!(
+            self.transitions.iter().all(|t| t.name != name),
+            "transition `{}` already exists",
+            name
+        );
+        self.transitions.push(Transition::new(
+            &name,
+            kind.as_ref(),
+            kind.as_ref() == "cut_transition",
+        ));
+        self
+    }
+
+    /// Add another profile.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the profile already exists.
+    pub fn profile(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        assert!(
+            self.profiles.iter().all(|p| p.name != name),
+            "profile `{}` already exists",
+            name
+        );
+        self.profiles.push(Profile::new(name));
+        self
+    }
+
+    /// Add another scene collection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scene collection already exists.
+    pub fn scene_collection(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        assert!(
+            !self.scene_collections.contains(&name),
+            "scene collection `{}` already exists",
+            name
+        );
+        self.scene_collections.push(name);
+        self
+    }
+
+    fn expect_unique(&self, name: &str) {
+        assert!(
+            !self.source_exists(name),
+            "source `{}` already exists",
+            name
+        );
+    }
+
+    /// Handle a request, returning [`None`] if the request type is not simulated.
+    pub(super) fn handle(&mut self, request_type: &str, data: Option<&Value>) -> Option<Response> {
+        let args = Args(data);
+
+        Some(match request_type {
+            // Config
+            "GetPersistentData" => self.persistent_data(&args),
+            "SetPersistentData" => self.set_persistent_data(&args),
+            "GetSceneCollectionList" => Ok(self.scene_collection_list()),
+            "SetCurrentSceneCollection" => self.set_current_scene_collection(&args),
+            "CreateSceneCollection" => self.create_scene_collection(&args),
+            "GetProfileList" => Ok(self.profile_list()),
+            "SetCurrentProfile" => self.set_current_profile(&args),
+            "CreateProfile" => self.create_profile(&args),
+            "RemoveProfile" => self.remove_profile(&args),
+            "GetProfileParameter" => self.profile_parameter(&args),
+            "SetProfileParameter" => self.set_profile_parameter(&args),
+            "GetVideoSettings" => Ok(self.video_settings()),
+            "SetVideoSettings" => self.set_video_settings(&args),
+            "GetStreamServiceSettings" => Ok(self.stream_service_settings()),
+            "SetStreamServiceSettings" => self.set_stream_service_settings(&args),
+            "GetRecordDirectory" => Ok(json!({ "recordDirectory": self.record_directory })),
+            // Filters
+            "GetSourceFilterList" => self.filter_list(&args),
+            "GetSourceFilterDefaultSettings" => self.filter_default_settings(&args),
+            "CreateSourceFilter" => self.create_filter(&args),
+            "RemoveSourceFilter" => self.remove_filter(&args),
+            "SetSourceFilterName" => self.set_filter_name(&args),
+            "GetSourceFilter" => self.source_filter(&args),
+            "SetSourceFilterIndex" => self.set_filter_index(&args),
+            "SetSourceFilterSettings" => self.set_filter_settings(&args),
+            "SetSourceFilterEnabled" => self.set_filter_enabled(&args),
+            // General
+            "GetStats" => Ok(self.stats()),
+            "BroadcastCustomEvent" => self.broadcast_custom_event(&args),
+            "CallVendorRequest" => self.call_vendor_request(&args),
+            "GetHotkeyList" => Ok(self.hotkey_list()),
+            "TriggerHotkeyByName" => self.trigger_hotkey_by_name(&args),
+            "TriggerHotkeyByKeySequence" => Ok(Value::Null),
+            // Inputs
+            "GetInputList" => self.input_list(&args),
+            "GetInputKindList" => self.input_kind_list(&args),
+            "GetSpecialInputs" => Ok(self.special_inputs()),
+            "CreateInput" => self.create_input(&args),
+            "RemoveInput" => self.remove_input(&args),
+            "SetInputName" => self.set_input_name(&args),
+            "GetInputDefaultSettings" => self.input_default_settings(&args),
+            "GetInputSettings" => self.input_settings(&args),
+            "SetInputSettings" => self.set_input_settings(&args),
+            "GetInputMute" => self.input_mute(&args),
+            "SetInputMute" => self.set_input_mute(&args),
+            "ToggleInputMute" => self.toggle_input_mute(&args),
+            "GetInputVolume" => self.input_volume(&args),
+            "SetInputVolume" => self.set_input_volume(&args),
+            "GetInputAudioBalance" => self.input_audio_balance(&args),
+            "SetInputAudioBalance" => self.set_input_audio_balance(&args),
+            "GetInputAudioSyncOffset" => self.input_audio_sync_offset(&args),
+            "SetInputAudioSyncOffset" => self.set_input_audio_sync_offset(&args),
+            "GetInputAudioMonitorType" => self.input_audio_monitor_type(&args),
+            "SetInputAudioMonitorType" => self.set_input_audio_monitor_type(&args),
+            "GetInputAudioTracks" => self.input_audio_tracks(&args),
+            "SetInputAudioTracks" => self.set_input_audio_tracks(&args),
+            "GetInputPropertiesListPropertyItems" => self.input_property_items(&args),
+            "PressInputPropertiesButton" => self.press_input_properties_button(&args),
+            // Media inputs
+            "GetMediaInputStatus" => self.media_input_status(&args),
+            "SetMediaInputCursor" => self.set_media_input_cursor(&args),
+            "OffsetMediaInputCursor" => self.offset_media_input_cursor(&args),
+            "TriggerMediaInputAction" => self.trigger_media_input_action(&args),
+            // Outputs
+            "GetVirtualCamStatus" => self.output_active(outputs::VIRTUAL_CAM),
+            "ToggleVirtualCam" => self.toggle_output(outputs::VIRTUAL_CAM),
+            "StartVirtualCam" => self.start_output(outputs::VIRTUAL_CAM),
+            "StopVirtualCam" => self.stop_output(outputs::VIRTUAL_CAM),
+            "GetReplayBufferStatus" => self.output_active(outputs::REPLAY_BUFFER),
+            "ToggleReplayBuffer" => self.toggle_output(outputs::REPLAY_BUFFER),
+            "StartReplayBuffer" => self.start_output(outputs::REPLAY_BUFFER),
+            "StopReplayBuffer" => self.stop_output(outputs::REPLAY_BUFFER),
+            "SaveReplayBuffer" => self.save_replay_buffer(),
+            "GetLastReplayBufferReplay" => self.last_replay_buffer_replay(),
+            "GetOutputList" => Ok(self.output_list()),
+            "GetOutputStatus" => self.generic_output_status(&args),
+            "ToggleOutput" => self.generic_output(&args, Self::toggle_output),
+            "StartOutput" => self.generic_output(&args, Self::start_output),
+            "StopOutput" => self.generic_output(&args, Self::stop_output),
+            "GetOutputSettings" => self.output_settings(&args),
+            "SetOutputSettings" => self.set_output_settings(&args),
+            // Record
+            "GetRecordStatus" => Ok(self.record_status()),
+            "ToggleRecord" => self.toggle_output(outputs::RECORD),
+            "StartRecord" => self.start_output(outputs::RECORD),
+            "StopRecord" => self.stop_output(outputs::RECORD),
+            "ToggleRecordPause" => self.toggle_record_pause(),
+            "PauseRecord" => self.pause_record(),
+            "ResumeRecord" => self.resume_record(),
+            // Scene items
+            "GetSceneItemList" => self.scene_item_list(&args, false),
+            "GetGroupSceneItemList" => self.scene_item_list(&args, true),
+            "GetSceneItemId" => self.scene_item_id(&args),
+            "CreateSceneItem" => self.create_scene_item(&args),
+            "RemoveSceneItem" => self.remove_scene_item(&args),
+            "DuplicateSceneItem" => self.duplicate_scene_item(&args),
+            "GetSceneItemTransform" => self.scene_item_transform(&args),
+            "SetSceneItemTransform" => self.set_scene_item_transform(&args),
+            "GetSceneItemEnabled" => self.scene_item_enabled(&args),
+            "SetSceneItemEnabled" => self.set_scene_item_enabled(&args),
+            "GetSceneItemLocked" => self.scene_item_locked(&args),
+            "SetSceneItemLocked" => self.set_scene_item_locked(&args),
+            "GetSceneItemIndex" => self.scene_item_index(&args),
+            "SetSceneItemIndex" => self.set_scene_item_index(&args),
+            "GetSceneItemBlendMode" => self.scene_item_blend_mode(&args),
+            "SetSceneItemBlendMode" => self.set_scene_item_blend_mode(&args),
+            "GetSceneItemPrivateSettings" => self.scene_item_private_settings(&args),
+            "SetSceneItemPrivateSettings" => self.set_scene_item_private_settings(&args),
+            // Scenes
+            "GetSceneList" => Ok(self.scene_list()),
+            "GetGroupList" => Ok(self.group_list()),
+            "GetCurrentProgramScene" => {
+                Ok(json!({ "currentProgramSceneName": self.program_scene }))
+            }
+            "SetCurrentProgramScene" => self.set_current_program_scene(&args),
+            "GetCurrentPreviewScene" => self.current_preview_scene(),
+            "SetCurrentPreviewScene" => self.set_current_preview_scene(&args),
+            "CreateScene" => self.create_scene(&args),
+            "RemoveScene" => self.remove_scene(&args),
+            "SetSceneName" => self.set_scene_name(&args),
+            "GetSceneSceneTransitionOverride" => self.scene_transition_override(&args),
+            "SetSceneSceneTransitionOverride" => self.set_scene_transition_override(&args),
+            // Sources
+            "GetSourceActive" => self.source_active(&args),
+            "GetSourceScreenshot" => self.source_screenshot(&args),
+            "SaveSourceScreenshot" => self.save_source_screenshot(&args),
+            // Stream
+            "GetStreamStatus" => Ok(self.stream_status()),
+            "ToggleStream" => self.toggle_output(outputs::STREAM),
+            "StartStream" => self.start_output(outputs::STREAM),
+            "StopStream" => self.stop_output(outputs::STREAM),
+            "SendStreamCaption" => self.send_stream_caption(&args),
+            // Transitions
+            "GetTransitionKindList" => Ok(self.transition_kind_list()),
+            "GetSceneTransitionList" => Ok(self.scene_transition_list()),
+            "GetCurrentSceneTransition" => Ok(self.current_scene_transition()),
+            "SetCurrentSceneTransition" => self.set_current_scene_transition(&args),
+            "SetCurrentSceneTransitionDuration" => {
+                self.set_current_scene_transition_duration(&args)
+            }
+            "SetCurrentSceneTransitionSettings" => {
+                self.set_current_scene_transition_settings(&args)
+            }
+            "GetCurrentSceneTransitionCursor" => Ok(json!({ "transitionCursor": 1.0 })),
+            "TriggerStudioModeTransition" => self.trigger_studio_mode_transition(),
+            "SetTBarPosition" => self.set_tbar_position(&args),
+            // UI
+            "GetStudioModeEnabled" => {
+                Ok(json!({ "studioModeEnabled": self.preview_scene.is_some() }))
+            }
+            "SetStudioModeEnabled" => self.set_studio_mode_enabled(&args),
+            "OpenInputPropertiesDialog" | "OpenInputFiltersDialog" | "OpenInputInteractDialog" => {
+                self.open_input_dialog(&args)
+            }
+            "GetMonitorList" => Ok(self.monitor_list()),
+            "OpenVideoMixProjector" => self.open_video_mix_projector(&args),
+            "OpenSourceProjector" => self.open_source_projector(&args),
+            _ => return None,
+        })
+    }
+
+    /// Take all events, that were emitted while handling the last request.
+    pub(super) fn take_events(&mut self) -> Vec<(&'static str, Value)> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Record an event, to be sent once the current request is handled.
+    fn emit(&mut self, event_type: &'static str, data: Value) {
+        self.events.push((event_type, data));
+    }
+
+    /// Whether a scene, group or input with the given name exists.
+    fn source_exists(&self, name: &str) -> bool {
+        self.scenes.iter().any(|s| s.name == name) || self.inputs.iter().any(|i| i.name == name)
+    }
+
+    fn find_scene(&self, name: &str) -> Result<&Scene, RequestError> {
+        self.scenes
+            .iter()
+            .find(|s| s.name == name)
+            .ok_or_else(|| not_found("scene", name))
+    }
+
+    fn find_scene_mut(&mut self, name: &str) -> Result<&mut Scene, RequestError> {
+        self.scenes
+            .iter_mut()
+            .find(|s| s.name == name)
+            .ok_or_else(|| not_found("scene", name))
+    }
+
+    fn find_input(&self, name: &str) -> Result<&Input, RequestError> {
+        self.inputs
+            .iter()
+            .find(|i| i.name == name)
+            .ok_or_else(|| not_found("input", name))
+    }
+
+    fn find_input_mut(&mut self, name: &str) -> Result<&mut Input, RequestError> {
+        self.inputs
+            .iter_mut()
+            .find(|i| i.name == name)
+            .ok_or_else(|| not_found("input", name))
+    }
+
+    /// Base size of a source, as it would be rendered without any scaling.
+    fn source_size(&self, name: &str) -> (f64, f64) {
+        if self.scenes.iter().any(|s| s.name == name) {
+            return (
+                f64::from(self.video.base_width),
+                f64::from(self.video.base_height),
+            );
+        }
+
+        self.inputs
+            .iter()
+            .find(|i| i.name == name)
+            .map(|input| {
+                let dimension = |key| input.settings.get(key).and_then(Value::as_f64);
+                (
+                    dimension("width").unwrap_or_default(),
+                    dimension("height").unwrap_or_default(),
+                )
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Scene {
+    fn new(name: String, is_group: bool) -> Self {
+        Self {
+            name,
+            is_group,
+            items: Vec::new(),
+            next_item_id: 1,
+            filters: Vec::new(),
+            transition_override: None,
+            transition_override_duration: None,
+        }
+    }
+
+    /// Add a new scene item on top of all others, returning its ID.
+    fn add_item(&mut self, source: String, enabled: bool, transform: Map<String, Value>) -> i64 {
+        let id = self.next_item_id;
+        self.next_item_id += 1;
+        self.items.push(SceneItem {
+            id,
+            source,
+            enabled,
+            locked: false,
+            transform,
+            blend_mode: "OBS_BLEND_NORMAL".to_owned(),
+            private_settings: json!({}),
+        });
+        id
+    }
+}
+
+impl Input {
+    fn new(name: String, kind: String, settings: Map<String, Value>) -> Self {
+        let media = inputs::is_media_kind(&kind).then(|| Media {
+            state: "OBS_MEDIA_STATE_PLAYING",
+            cursor: 0,
+            duration: inputs::MEDIA_DURATION,
+        });
+
+        Self {
+            name,
+            kind,
+            settings,
+            muted: false,
+            volume_mul: 1.0,
+            audio_balance: 0.5,
+            audio_sync_offset: 0,
+            monitor_type: "OBS_MONITORING_TYPE_NONE".to_owned(),
+            audio_tracks: [true; 6],
+            filters: Vec::new(),
+            media,
+        }
+    }
+}
+
+impl Transition {
+    fn new(name: &str, kind: &str, fixed: bool) -> Self {
+        Self {
+            name: name.to_owned(),
+            kind: kind.to_owned(),
+            fixed,
+            configurable: !fixed,
+            settings: Map::new(),
+        }
+    }
+}
+
+impl Output {
+    fn new(name: &str, kind: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            kind: kind.to_owned(),
+            active: false,
+            paused: false,
+            started: None,
+            settings: Map::new(),
+        }
+    }
+}
+
+impl Profile {
+    fn new(name: String) -> Self {
+        Self {
+            parameters: vec![("General".to_owned(), "Name".to_owned(), name.clone())],
+            name,
+            data: Map::new(),
+        }
+    }
+}
+
+/// Accessors for the fields of the request data, that fail with the same status codes as
+/// obs-websocket does.
+struct Args<'a>(Option<&'a Value>);
+
+impl<'a> Args<'a> {
+    fn get(&self, name: &str) -> Result<Option<&'a Value>, RequestError> {
+        match self.0 {
+            Some(data) => Ok(data.get(name).filter(|value| !value.is_null())),
+            None => Err(RequestError::new(
+                StatusCode::MissingRequestData,
+                "Your request data is missing or invalid (non-object)",
+            )),
+        }
+    }
+
+    fn opt<T>(
+        &self,
+        name: &str,
+        convert: impl FnOnce(&'a Value) -> Option<T>,
+    ) -> Result<Option<T>, RequestError> {
+        match self.get(name) {
+            Ok(Some(value)) => convert(value).map(Some).ok_or_else(|| {
+                RequestError::new(
+                    StatusCode::InvalidRequestFieldType,
+                    format!("The field value of `{}` is invalid.", name),
+                )
+            }),
+            Ok(None) => Ok(None),
+            Err(_) => Ok(None),
+        }
+    }
+
+    fn required<T>(
+        &self,
+        name: &str,
+        convert: impl FnOnce(&'a Value) -> Option<T>,
+    ) -> Result<T, RequestError> {
+        self.get(name)?;
+        self.opt(name, convert)?.ok_or_else(|| {
+            RequestError::new(
+                StatusCode::MissingRequestField,
+                format!("Your request is missing the `{}` field.", name),
+            )
+        })
+    }
+
+    fn str(&self, name: &str) -> Result<&'a str, RequestError> {
+        let value = self.required(name, Value::as_str)?;
+        if value.is_empty() {
+            return Err(RequestError::new(
+                StatusCode::RequestFieldEmpty,
+                format!("The field `{}` must not be empty.", name),
+            ));
+        }
+        Ok(value)
+    }
+
+    fn opt_str(&self, name: &str) -> Result<Option<&'a str>, RequestError> {
+        self.opt(name, Value::as_str)
+    }
+
+    fn bool(&self, name: &str) -> Result<bool, RequestError> {
+        self.required(name, Value::as_bool)
+    }
+
+    fn opt_bool(&self, name: &str) -> Result<Option<bool>, RequestError> {
+        self.opt(name, Value::as_bool)
+    }
+
+    fn i64(&self, name: &str) -> Result<i64, RequestError> {
+        self.required(name, Value::as_i64)
+    }
+
+    fn opt_i64(&self, name: &str) -> Result<Option<i64>, RequestError> {
+        self.opt(name, Value::as_i64)
+    }
+
+    fn f64(&self, name: &str) -> Result<f64, RequestError> {
+        self.required(name, Value::as_f64)
+    }
+
+    fn opt_f64(&self, name: &str) -> Result<Option<f64>, RequestError> {
+        self.opt(name, Value::as_f64)
+    }
+
+    fn object(&self, name: &str) -> Result<&'a Map<String, Value>, RequestError> {
+        self.required(name, Value::as_object)
+    }
+
+    fn opt_object(&self, name: &str) -> Result<Option<&'a Map<String, Value>>, RequestError> {
+        self.opt(name, Value::as_object)
+    }
+
+    fn value(&self, name: &str) -> Result<&'a Value, RequestError> {
+        self.required(name, Some)
+    }
+}
+
+/// Ensure that a numeric field is within the given range.
+fn check_range<T>(name: &str, value: T, min: T, max: T) -> Result<T, RequestError>
+where
+    T: PartialOrd + std::fmt::Display,
+{
+    if value < min || value > max {
+        return Err(RequestError::new(
+            StatusCode::RequestFieldOutOfRange,
+            format!(
+                "The field value of `{}` is out of range (minimum {}, maximum {}).",
+                name, min, max
+            ),
+        ));
+    }
+
+    Ok(value)
+}
+
+fn not_found(kind: &str, name: &str) -> RequestError {
+    RequestError::new(
+        StatusCode::ResourceNotFound,
+        format!("No {} was found by the name of `{}`.", kind, name),
+    )
+}
+
+fn already_exists(kind: &str, name: &str) -> RequestError {
+    RequestError::new(
+        StatusCode::ResourceAlreadyExists,
+        format!("A {} already exists by that name: `{}`.", kind, name),
+    )
+}
+
+/// Apply new settings on top of existing ones, or replace them completely if `overlay` is
+/// disabled.
+fn apply_settings(settings: &mut Map<String, Value>, new: &Map<String, Value>, overlay: bool) {
+    if !overlay {
+        settings.clear();
+    }
+
+    settings.extend(new.iter().map(|(k, v)| (k.clone(), v.clone())));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_all_request_types() {
+        let mut simulator = Simulator::new();
+        for request_type in REQUEST_TYPES {
+            assert!(
+                simulator.handle(request_type, None).is_some(),
+                "{} not handled",
+                request_type
+            );
+        }
+
+        assert!(simulator.handle("Sleep", None).is_none());
+    }
+
+    #[test]
+    fn validates_fields() {
+        let mut simulator = Simulator::new();
+        let code = |result: Option<Response>| result.unwrap().unwrap_err().code;
+
+        assert_eq!(
+            StatusCode::MissingRequestData,
+            code(simulator.handle("CreateScene", None))
+        );
+        assert_eq!(
+            StatusCode::MissingRequestField,
+            code(simulator.handle("CreateScene", Some(&json!({}))))
+        );
+        assert_eq!(
+            StatusCode::InvalidRequestFieldType,
+            code(simulator.handle("CreateScene", Some(&json!({ "sceneName": 1 }))))
+        );
+        assert_eq!(
+            StatusCode::RequestFieldEmpty,
+            code(simulator.handle("CreateScene", Some(&json!({ "sceneName": "" }))))
+        );
+        assert_eq!(
+            StatusCode::ResourceAlreadyExists,
+            code(simulator.handle("CreateScene", Some(&json!({ "sceneName": "Scene" }))))
+        );
+    }
+
+    #[test]
+    fn emits_events() {
+        let mut simulator = Simulator::new().scene("Other");
+        let data = json!({ "sceneName": "Other" });
+
+        simulator
+            .handle("SetCurrentProgramScene", Some(&data))
+            .unwrap()
+            .unwrap();
+        let events = simulator
+            .take_events()
+            .into_iter()
+            .map(|(event_type, _)| event_type)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "SceneTransitionStarted",
+                "CurrentProgramSceneChanged",
+                "SceneTransitionVideoEnded",
+                "SceneTransitionEnded",
+            ],
+            events
+        );
+
+        // Failed requests don't change anything.
+        let result = simulator.handle("StopStream", None).unwrap();
+        assert_eq!(StatusCode::OutputNotRunning, result.unwrap_err().code);
+        assert!(simulator.take_events().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "source `Scene` already exists")]
+    fn builder_rejects_duplicates() {
+        Simulator::new().input("Scene", "Scene", "color_source_v3");
+    }
+}
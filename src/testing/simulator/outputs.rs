@@ -0,0 +1,321 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use super::{apply_settings, not_found, Args, Output, Response, Simulator};
+use crate::{responses::StatusCode, testing::RequestError};
+
+/// Name of the stream output.
+pub(super) const STREAM: &str = "simple_stream";
+/// Name of the record output.
+pub(super) const RECORD: &str = "simple_file_output";
+/// Name of the replay buffer output.
+pub(super) const REPLAY_BUFFER: &str = "Replay Buffer";
+/// Name of the virtual camera output.
+pub(super) const VIRTUAL_CAM: &str = "virtualcam_output";
+
+/// Simulated bitrate of all outputs, in bytes per millisecond (about 6 Mbit/s).
+const BYTES_PER_MILLI: u64 = 750;
+
+fn output_not_running() -> RequestError {
+    RequestError::new(StatusCode::OutputNotRunning, "The output is not running.")
+}
+
+/// Format a duration as timecode, like `01:02:03.456`.
+fn timecode(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+        duration.subsec_millis()
+    )
+}
+
+impl Output {
+    fn duration(&self) -> Duration {
+        self.started.map(|s| s.elapsed()).unwrap_or_default()
+    }
+}
+
+impl Simulator {
+    fn output(&self, name: &str) -> Result<&Output, RequestError> {
+        self.outputs
+            .iter()
+            .find(|o| o.name == name)
+            .ok_or_else(|| not_found("output", name))
+    }
+
+    fn output_mut(&mut self, name: &str) -> Result<&mut Output, RequestError> {
+        self.outputs
+            .iter_mut()
+            .find(|o| o.name == name)
+            .ok_or_else(|| not_found("output", name))
+    }
+
+    /// Whether any output is currently active.
+    pub(super) fn outputs_active(&self) -> bool {
+        self.outputs.iter().any(|o| o.active)
+    }
+
+    /// Whether the given output exists and is currently active.
+    pub(super) fn output_running(&self, name: &str) -> bool {
+        self.output(name).map_or(false, |o| o.active)
+    }
+
+    /// Emit the state change event of the given output.
+    fn emit_output_state(&mut self, name: &str, active: bool, state: &str, path: Option<&str>) {
+        let event_type = match name {
+            STREAM => "StreamStateChanged",
+            RECORD => "RecordStateChanged",
+            REPLAY_BUFFER => "ReplayBufferStateChanged",
+            VIRTUAL_CAM => "VirtualcamStateChanged",
+            _ => return,
+        };
+
+        let mut data = json!({ "outputActive": active, "outputState": state });
+        if name == RECORD {
+            data["outputPath"] = json!(path);
+        }
+
+        self.emit(event_type, data);
+    }
+
+    pub(super) fn output_active(&self, name: &str) -> Response {
+        Ok(json!({ "outputActive": self.output(name)?.active }))
+    }
+
+    pub(super) fn start_output(&mut self, name: &str) -> Response {
+        let output = self.output_mut(name)?;
+        if output.active {
+            return Err(RequestError::new(
+                StatusCode::OutputRunning,
+                "The output is already running.",
+            ));
+        }
+
+        output.active = true;
+        output.paused = false;
+        output.started = Some(std::time::Instant::now());
+
+        self.emit_output_state(name, false, "OBS_WEBSOCKET_OUTPUT_STARTING", None);
+        self.emit_output_state(name, true, "OBS_WEBSOCKET_OUTPUT_STARTED", None);
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn stop_output(&mut self, name: &str) -> Response {
+        let output = self.output_mut(name)?;
+        if !output.active {
+            return Err(output_not_running());
+        }
+
+        output.active = false;
+        output.paused = false;
+        output.started = None;
+
+        let path = (name == RECORD).then(|| {
+            let secs = self.started.elapsed().as_secs();
+            format!("{}/recording-{}.mkv", self.record_directory, secs)
+        });
+
+        self.emit_output_state(name, true, "OBS_WEBSOCKET_OUTPUT_STOPPING", None);
+        self.emit_output_state(name, false, "OBS_WEBSOCKET_OUTPUT_STOPPED", path.as_deref());
+
+        Ok(match path {
+            Some(path) => json!({ "outputPath": path }),
+            None => Value::Null,
+        })
+    }
+
+    pub(super) fn toggle_output(&mut self, name: &str) -> Response {
+        let active = self.output(name)?.active;
+        if active {
+            self.stop_output(name)?;
+        } else {
+            self.start_output(name)?;
+        }
+
+        Ok(json!({ "outputActive": !active }))
+    }
+
+    /// Run one of the output actions on the output, that is given in the request.
+    pub(super) fn generic_output(
+        &mut self,
+        args: &Args<'_>,
+        action: fn(&mut Self, &str) -> Response,
+    ) -> Response {
+        let name = self.output(args.str("outputName")?)?.name.clone();
+        action(self, &name).map(|response| match response {
+            // Only the generic toggle returns data.
+            Value::Object(data) if data.contains_key("outputActive") => Value::Object(data),
+            _ => Value::Null,
+        })
+    }
+
+    pub(super) fn output_list(&self) -> Value {
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| {
+                let encoded = output.name != VIRTUAL_CAM;
+                json!({
+                    "outputName": output.name,
+                    "outputKind": output.kind,
+                    "outputWidth": self.video.output_width,
+                    "outputHeight": self.video.output_height,
+                    "outputActive": output.active,
+                    "outputFlags": {
+                        "OBS_OUTPUT_AUDIO": true,
+                        "OBS_OUTPUT_VIDEO": true,
+                        "OBS_OUTPUT_ENCODED": encoded,
+                        "OBS_OUTPUT_MULTI_TRACK": encoded,
+                        "OBS_OUTPUT_SERVICE": output.name == STREAM,
+                    },
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({ "outputs": outputs })
+    }
+
+    fn output_status(&self, output: &Output) -> Value {
+        let duration = output.duration();
+        let millis = duration.as_millis() as u64;
+
+        json!({
+            "outputActive": output.active,
+            "outputReconnecting": false,
+            "outputTimecode": timecode(duration),
+            "outputDuration": millis,
+            "outputCongestion": 0.0,
+            "outputBytes": millis * BYTES_PER_MILLI,
+            "outputSkippedFrames": 0,
+            "outputTotalFrames": millis * u64::from(self.video.fps_numerator)
+                / u64::from(self.video.fps_denominator)
+                / 1000,
+        })
+    }
+
+    pub(super) fn generic_output_status(&self, args: &Args<'_>) -> Response {
+        let output = self.output(args.str("outputName")?)?;
+        Ok(self.output_status(output))
+    }
+
+    pub(super) fn stream_status(&self) -> Value {
+        let output = self.output(STREAM).expect("stream output always exists");
+        self.output_status(output)
+    }
+
+    pub(super) fn output_settings(&self, args: &Args<'_>) -> Response {
+        let output = self.output(args.str("outputName")?)?;
+        Ok(json!({ "outputSettings": output.settings }))
+    }
+
+    pub(super) fn set_output_settings(&mut self, args: &Args<'_>) -> Response {
+        let settings = args.object("outputSettings")?;
+        let output = self.output_mut(args.str("outputName")?)?;
+
+        apply_settings(&mut output.settings, settings, true);
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn send_stream_caption(&mut self, args: &Args<'_>) -> Response {
+        args.required("captionText", Value::as_str)?;
+        if !self.output(STREAM)?.active {
+            return Err(output_not_running());
+        }
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn record_status(&self) -> Value {
+        let output = self.output(RECORD).expect("record output always exists");
+        let duration = output.duration();
+        let millis = duration.as_millis() as u64;
+
+        json!({
+            "outputActive": output.active,
+            "outputPaused": output.paused,
+            "outputTimecode": timecode(duration),
+            "outputDuration": millis,
+            "outputBytes": millis * BYTES_PER_MILLI,
+        })
+    }
+
+    pub(super) fn pause_record(&mut self) -> Response {
+        let output = self.output_mut(RECORD)?;
+        if !output.active {
+            return Err(output_not_running());
+        }
+        if output.paused {
+            return Err(RequestError::new(
+                StatusCode::OutputPaused,
+                "The record output is already paused.",
+            ));
+        }
+
+        output.paused = true;
+        self.emit_output_state(RECORD, true, "OBS_WEBSOCKET_OUTPUT_PAUSED", None);
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn resume_record(&mut self) -> Response {
+        let output = self.output_mut(RECORD)?;
+        if !output.active {
+            return Err(output_not_running());
+        }
+        if !output.paused {
+            return Err(RequestError::new(
+                StatusCode::OutputNotPaused,
+                "The record output is not paused.",
+            ));
+        }
+
+        output.paused = false;
+        self.emit_output_state(RECORD, true, "OBS_WEBSOCKET_OUTPUT_RESUMED", None);
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn toggle_record_pause(&mut self) -> Response {
+        let paused = self.output(RECORD)?.paused;
+        if paused {
+            self.resume_record()?;
+        } else {
+            self.pause_record()?;
+        }
+
+        Ok(json!({ "outputPaused": !paused }))
+    }
+
+    pub(super) fn save_replay_buffer(&mut self) -> Response {
+        if !self.output(REPLAY_BUFFER)?.active {
+            return Err(output_not_running());
+        }
+
+        let secs = self.started.elapsed().as_secs();
+        let path = format!("{}/replay-{}.mkv", self.record_directory, secs);
+        self.last_replay = Some(path.clone());
+        self.emit("ReplayBufferSaved", json!({ "savedReplayPath": path }));
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn last_replay_buffer_replay(&self) -> Response {
+        if !self.output(REPLAY_BUFFER)?.active {
+            return Err(output_not_running());
+        }
+
+        match &self.last_replay {
+            Some(path) => Ok(json!({ "savedReplayPath": path })),
+            None => Err(RequestError::new(
+                StatusCode::ResourceNotFound,
+                "No replay has been saved yet.",
+            )),
+        }
+    }
+}
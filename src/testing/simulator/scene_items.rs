@@ -0,0 +1,472 @@
+use serde_json::{json, Map, Value};
+
+use super::{check_range, not_found, Args, Response, Scene, SceneItem, Simulator};
+use crate::{responses::StatusCode, testing::RequestError};
+
+/// Possible values for the bounds type of a scene item transform.
+const BOUNDS_TYPES: &[&str] = &[
+    "OBS_BOUNDS_NONE",
+    "OBS_BOUNDS_STRETCH",
+    "OBS_BOUNDS_SCALE_INNER",
+    "OBS_BOUNDS_SCALE_OUTER",
+    "OBS_BOUNDS_SCALE_TO_WIDTH",
+    "OBS_BOUNDS_SCALE_TO_HEIGHT",
+    "OBS_BOUNDS_MAX_ONLY",
+];
+
+/// Possible values for the blend mode of a scene item.
+const BLEND_MODES: &[&str] = &[
+    "OBS_BLEND_NORMAL",
+    "OBS_BLEND_ADDITIVE",
+    "OBS_BLEND_SUBTRACT",
+    "OBS_BLEND_SCREEN",
+    "OBS_BLEND_MULTIPLY",
+    "OBS_BLEND_LIGHTEN",
+    "OBS_BLEND_DARKEN",
+];
+
+/// Fields of a transform, that can be changed with floating point numbers.
+const TRANSFORM_FLOATS: &[&str] = &[
+    "positionX",
+    "positionY",
+    "rotation",
+    "scaleX",
+    "scaleY",
+    "boundsWidth",
+    "boundsHeight",
+];
+
+/// Fields of a transform, that can be changed with positive integers.
+const TRANSFORM_INTEGERS: &[&str] = &[
+    "alignment",
+    "boundsAlignment",
+    "cropLeft",
+    "cropRight",
+    "cropTop",
+    "cropBottom",
+];
+
+impl Simulator {
+    /// Transform of a newly created scene item for the given source.
+    pub(super) fn default_transform(&self, source: &str) -> Map<String, Value> {
+        let (width, height) = self.source_size(source);
+
+        match json!({
+            "sourceWidth": width,
+            "sourceHeight": height,
+            "positionX": 0.0,
+            "positionY": 0.0,
+            "rotation": 0.0,
+            "scaleX": 1.0,
+            "scaleY": 1.0,
+            "width": width,
+            "height": height,
+            "alignment": 5,
+            "boundsType": "OBS_BOUNDS_NONE",
+            "boundsAlignment": 0,
+            "boundsWidth": 0.0,
+            "boundsHeight": 0.0,
+            "cropLeft": 0,
+            "cropRight": 0,
+            "cropTop": 0,
+            "cropBottom": 0,
+        }) {
+            Value::Object(transform) => transform,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Remove all scene items, that show the given source, from all scenes.
+    pub(super) fn remove_source_items(&mut self, source: &str) {
+        let mut removed = Vec::new();
+
+        for scene in &mut self.scenes {
+            for item in scene.items.iter().filter(|item| item.source == source) {
+                removed.push(json!({
+                    "sceneName": scene.name,
+                    "sourceName": source,
+                    "sceneItemId": item.id,
+                }));
+            }
+            scene.items.retain(|item| item.source != source);
+        }
+
+        for event in removed {
+            self.emit("SceneItemRemoved", event);
+        }
+    }
+
+    /// Update all scene items, that show a source, which was renamed.
+    pub(super) fn rename_source_items(&mut self, old_name: &str, new_name: &str) {
+        for scene in &mut self.scenes {
+            for item in scene
+                .items
+                .iter_mut()
+                .filter(|item| item.source == old_name)
+            {
+                item.source = new_name.to_owned();
+            }
+        }
+    }
+
+    /// Get a scene (or group) to modify its scene items.
+    fn item_scene(&self, args: &Args<'_>) -> Result<&Scene, RequestError> {
+        self.find_scene(args.str("sceneName")?)
+    }
+
+    /// Get the scene and scene item, that a request is targeted at.
+    fn scene_item(&self, args: &Args<'_>) -> Result<(&Scene, &SceneItem), RequestError> {
+        let scene = self.item_scene(args)?;
+        let id = args.i64("sceneItemId")?;
+
+        let item = scene
+            .items
+            .iter()
+            .find(|item| item.id == id)
+            .ok_or_else(|| {
+                RequestError::new(
+                    StatusCode::ResourceNotFound,
+                    format!(
+                        "No scene items were found in scene `{}` with the ID `{}`.",
+                        scene.name, id
+                    ),
+                )
+            })?;
+
+        Ok((scene, item))
+    }
+
+    /// Modify a scene item, returning the scene name and item ID for events.
+    fn update_scene_item(
+        &mut self,
+        args: &Args<'_>,
+        update: impl FnOnce(&mut SceneItem),
+    ) -> Result<(String, i64), RequestError> {
+        let (scene, item) = self.scene_item(args)?;
+        let (scene, id) = (scene.name.clone(), item.id);
+
+        let item = self
+            .find_scene_mut(&scene)?
+            .items
+            .iter_mut()
+            .find(|item| item.id == id)
+            .ok_or_else(|| not_found("scene item", &id.to_string()))?;
+        update(item);
+
+        Ok((scene, id))
+    }
+
+    fn scene_item_json(&self, index: usize, item: &SceneItem) -> Value {
+        let input = self.inputs.iter().find(|i| i.name == item.source);
+        let scene = self.scenes.iter().find(|s| s.name == item.source);
+
+        json!({
+            "sceneItemId": item.id,
+            "sceneItemIndex": index,
+            "sourceName": item.source,
+            "sourceType": if input.is_some() {
+                "OBS_SOURCE_TYPE_INPUT"
+            } else {
+                "OBS_SOURCE_TYPE_SCENE"
+            },
+            "inputKind": input.map(|i| &i.kind),
+            "isGroup": scene.map(|s| s.is_group),
+            "sceneItemEnabled": item.enabled,
+            "sceneItemLocked": item.locked,
+            "sceneItemTransform": item.transform,
+            "sceneItemBlendMode": item.blend_mode,
+        })
+    }
+
+    pub(super) fn scene_item_list(&self, args: &Args<'_>, group: bool) -> Response {
+        let name = args.str("sceneName")?;
+        let scene = self
+            .find_scene(name)
+            .ok()
+            .filter(|s| s.is_group == group)
+            .ok_or_else(|| not_found(if group { "group" } else { "scene" }, name))?;
+
+        let items = scene
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| self.scene_item_json(index, item))
+            .collect::<Vec<_>>();
+
+        Ok(json!({ "sceneItems": items }))
+    }
+
+    pub(super) fn scene_item_id(&self, args: &Args<'_>) -> Response {
+        let scene = self.item_scene(args)?;
+        let source = args.str("sourceName")?;
+        let offset = args.opt_i64("searchOffset")?.unwrap_or_default();
+        check_range("searchOffset", offset, -1, i64::MAX)?;
+
+        let mut matches = scene.items.iter().filter(|item| item.source == source);
+        let item = if offset < 0 {
+            matches.next_back()
+        } else {
+            matches.nth(offset as usize)
+        };
+
+        item.map(|item| json!({ "sceneItemId": item.id }))
+            .ok_or_else(|| {
+                RequestError::new(
+                    StatusCode::ResourceNotFound,
+                    format!(
+                        "No scene items were found in scene `{}` with the source `{}`.",
+                        scene.name, source
+                    ),
+                )
+            })
+    }
+
+    pub(super) fn create_scene_item(&mut self, args: &Args<'_>) -> Response {
+        let scene = self.item_scene(args)?.name.clone();
+        let source = args.str("sourceName")?;
+        let enabled = args.opt_bool("sceneItemEnabled")?.unwrap_or(true);
+
+        if !self.source_exists(source) {
+            return Err(not_found("source", source));
+        }
+        if scene == source {
+            return Err(RequestError::new(
+                StatusCode::CannotAct,
+                "You cannot create scene item of a scene within itself.",
+            ));
+        }
+
+        let transform = self.default_transform(source);
+        let target = self.find_scene_mut(&scene)?;
+        let id = target.add_item(source.to_owned(), enabled, transform);
+        let index = target.items.len() - 1;
+
+        self.emit(
+            "SceneItemCreated",
+            json!({
+                "sceneName": scene,
+                "sourceName": source,
+                "sceneItemId": id,
+                "sceneItemIndex": index,
+            }),
+        );
+
+        Ok(json!({ "sceneItemId": id }))
+    }
+
+    pub(super) fn remove_scene_item(&mut self, args: &Args<'_>) -> Response {
+        let (scene, item) = self.scene_item(args)?;
+        let (scene, item) = (scene.name.clone(), item.clone());
+
+        self.find_scene_mut(&scene)?
+            .items
+            .retain(|i| i.id != item.id);
+        self.emit(
+            "SceneItemRemoved",
+            json!({
+                "sceneName": scene,
+                "sourceName": item.source,
+                "sceneItemId": item.id,
+            }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn duplicate_scene_item(&mut self, args: &Args<'_>) -> Response {
+        let (scene, item) = self.scene_item(args)?;
+        let item = item.clone();
+        let destination = match args.opt_str("destinationSceneName")? {
+            Some(destination) => self.find_scene(destination)?.name.clone(),
+            None => scene.name.clone(),
+        };
+
+        if destination == item.source {
+            return Err(RequestError::new(
+                StatusCode::CannotAct,
+                "You cannot duplicate a scene item of a scene into itself.",
+            ));
+        }
+
+        let target = self.find_scene_mut(&destination)?;
+        let id = target.add_item(item.source.clone(), item.enabled, item.transform);
+        let new = target.items.last_mut().expect("item was just added");
+        new.locked = item.locked;
+        new.blend_mode = item.blend_mode;
+        new.private_settings = item.private_settings;
+        let index = target.items.len() - 1;
+
+        self.emit(
+            "SceneItemCreated",
+            json!({
+                "sceneName": destination,
+                "sourceName": item.source,
+                "sceneItemId": id,
+                "sceneItemIndex": index,
+            }),
+        );
+
+        Ok(json!({ "sceneItemId": id }))
+    }
+
+    pub(super) fn scene_item_transform(&self, args: &Args<'_>) -> Response {
+        let (_, item) = self.scene_item(args)?;
+        Ok(json!({ "sceneItemTransform": item.transform }))
+    }
+
+    pub(super) fn set_scene_item_transform(&mut self, args: &Args<'_>) -> Response {
+        let changes = args.object("sceneItemTransform")?;
+        let invalid = |name: &str| {
+            RequestError::new(
+                StatusCode::InvalidRequestFieldType,
+                format!("The field value of `{}` is invalid.", name),
+            )
+        };
+
+        let mut updates = Map::new();
+        for (name, value) in changes {
+            let valid = if TRANSFORM_FLOATS.contains(&name.as_str()) {
+                value.is_number()
+            } else if TRANSFORM_INTEGERS.contains(&name.as_str()) {
+                value.is_u64()
+            } else if name == "boundsType" {
+                value.as_str().map_or(false, |v| BOUNDS_TYPES.contains(&v))
+            } else {
+                continue;
+            };
+
+            if !valid {
+                return Err(invalid(name));
+            }
+            updates.insert(name.clone(), value.clone());
+        }
+
+        let (scene, id) = self.update_scene_item(args, |item| {
+            item.transform.extend(updates);
+
+            let get = |name| item.transform.get(name).and_then(Value::as_f64);
+            let width = get("sourceWidth").unwrap_or_default() * get("scaleX").unwrap_or(1.0);
+            let height = get("sourceHeight").unwrap_or_default() * get("scaleY").unwrap_or(1.0);
+            item.transform.insert("width".to_owned(), json!(width));
+            item.transform.insert("height".to_owned(), json!(height));
+        })?;
+
+        let transform = self.scene_item(args)?.1.transform.clone();
+        self.emit(
+            "SceneItemTransformChanged",
+            json!({
+                "sceneName": scene,
+                "sceneItemId": id,
+                "sceneItemTransform": transform,
+            }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn scene_item_enabled(&self, args: &Args<'_>) -> Response {
+        let (_, item) = self.scene_item(args)?;
+        Ok(json!({ "sceneItemEnabled": item.enabled }))
+    }
+
+    pub(super) fn set_scene_item_enabled(&mut self, args: &Args<'_>) -> Response {
+        let enabled = args.bool("sceneItemEnabled")?;
+        let (scene, id) = self.update_scene_item(args, |item| item.enabled = enabled)?;
+
+        self.emit(
+            "SceneItemEnableStateChanged",
+            json!({ "sceneName": scene, "sceneItemId": id, "sceneItemEnabled": enabled }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn scene_item_locked(&self, args: &Args<'_>) -> Response {
+        let (_, item) = self.scene_item(args)?;
+        Ok(json!({ "sceneItemLocked": item.locked }))
+    }
+
+    pub(super) fn set_scene_item_locked(&mut self, args: &Args<'_>) -> Response {
+        let locked = args.bool("sceneItemLocked")?;
+        let (scene, id) = self.update_scene_item(args, |item| item.locked = locked)?;
+
+        self.emit(
+            "SceneItemLockStateChanged",
+            json!({ "sceneName": scene, "sceneItemId": id, "sceneItemLocked": locked }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn scene_item_index(&self, args: &Args<'_>) -> Response {
+        let (scene, item) = self.scene_item(args)?;
+        let index = scene.items.iter().position(|i| i.id == item.id);
+
+        Ok(json!({ "sceneItemIndex": index }))
+    }
+
+    pub(super) fn set_scene_item_index(&mut self, args: &Args<'_>) -> Response {
+        let (scene, item) = self.scene_item(args)?;
+        let (name, id) = (scene.name.clone(), item.id);
+        let index = args.i64("sceneItemIndex")?;
+        let index = check_range("sceneItemIndex", index, 0, i64::MAX)? as usize;
+
+        let scene = self.find_scene_mut(&name)?;
+        let current = scene
+            .items
+            .iter()
+            .position(|i| i.id == id)
+            .expect("item was just found");
+        let item = scene.items.remove(current);
+        scene.items.insert(index.min(scene.items.len()), item);
+
+        let items = scene
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| json!({ "sceneItemId": item.id, "sceneItemIndex": index }))
+            .collect::<Vec<_>>();
+        self.emit(
+            "SceneItemListReindexed",
+            json!({ "sceneName": name, "sceneItems": items }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn scene_item_blend_mode(&self, args: &Args<'_>) -> Response {
+        let (_, item) = self.scene_item(args)?;
+        Ok(json!({ "sceneItemBlendMode": item.blend_mode }))
+    }
+
+    pub(super) fn set_scene_item_blend_mode(&mut self, args: &Args<'_>) -> Response {
+        let mode = args.str("sceneItemBlendMode")?;
+        if !BLEND_MODES.contains(&mode) {
+            return Err(RequestError::new(
+                StatusCode::InvalidRequestField,
+                "The field sceneItemBlendMode has an invalid value.",
+            ));
+        }
+
+        self.update_scene_item(args, |item| item.blend_mode = mode.to_owned())?;
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn scene_item_private_settings(&self, args: &Args<'_>) -> Response {
+        let (_, item) = self.scene_item(args)?;
+        Ok(json!({ "sceneItemSettings": item.private_settings }))
+    }
+
+    pub(super) fn set_scene_item_private_settings(&mut self, args: &Args<'_>) -> Response {
+        let settings = args.object("sceneItemSettings")?;
+
+        self.update_scene_item(args, |item| {
+            if let Value::Object(current) = &mut item.private_settings {
+                super::apply_settings(current, settings, true);
+            }
+        })?;
+
+        Ok(Value::Null)
+    }
+}
@@ -0,0 +1,330 @@
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use super::{already_exists, check_range, not_found, Args, Response, Scene, Simulator};
+use crate::{responses::StatusCode, testing::RequestError};
+
+/// A 1x1 pixel, transparent PNG image, used as screenshot of any source.
+const SCREENSHOT_PNG: &str =
+    "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mNkYAAAAAYAAjCB0C8AAAAASUVORK5CYII=";
+
+/// Image formats, that screenshots can be taken in.
+const IMAGE_FORMATS: &[&str] = &["bmp", "jpeg", "jpg", "png"];
+
+impl Simulator {
+    pub(super) fn scene_list(&self) -> Value {
+        let scenes = self
+            .scenes
+            .iter()
+            .filter(|s| !s.is_group)
+            .enumerate()
+            .map(|(index, scene)| json!({ "sceneName": scene.name, "sceneIndex": index }))
+            .collect::<Vec<_>>();
+
+        json!({
+            "currentProgramSceneName": self.program_scene,
+            "currentPreviewSceneName": self.preview_scene,
+            "scenes": scenes,
+        })
+    }
+
+    pub(super) fn group_list(&self) -> Value {
+        let groups = self
+            .scenes
+            .iter()
+            .filter(|s| s.is_group)
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+
+        json!({ "groups": groups })
+    }
+
+    /// Get a scene, that is not a group.
+    fn plain_scene(&self, name: &str) -> Result<&Scene, RequestError> {
+        self.find_scene(name)
+            .ok()
+            .filter(|s| !s.is_group)
+            .ok_or_else(|| not_found("scene", name))
+    }
+
+    pub(super) fn set_current_program_scene(&mut self, args: &Args<'_>) -> Response {
+        let name = self.plain_scene(args.str("sceneName")?)?.name.clone();
+        self.switch_program_scene(name);
+        Ok(Value::Null)
+    }
+
+    /// Make the given scene the program scene, with a transition if it's not already active.
+    pub(super) fn switch_program_scene(&mut self, name: String) {
+        if self.program_scene == name {
+            return;
+        }
+
+        let transition = self
+            .find_scene(&name)
+            .ok()
+            .and_then(|s| s.transition_override.clone())
+            .unwrap_or_else(|| self.current_transition.clone());
+
+        self.emit(
+            "SceneTransitionStarted",
+            json!({ "transitionName": transition }),
+        );
+        self.emit("CurrentProgramSceneChanged", json!({ "sceneName": name }));
+        self.program_scene = name;
+        self.emit(
+            "SceneTransitionVideoEnded",
+            json!({ "transitionName": transition }),
+        );
+        self.emit(
+            "SceneTransitionEnded",
+            json!({ "transitionName": transition }),
+        );
+    }
+
+    pub(super) fn require_studio_mode(&self) -> Result<(), RequestError> {
+        match self.preview_scene {
+            Some(_) => Ok(()),
+            None => Err(RequestError::new(
+                StatusCode::StudioModeNotActive,
+                "Studio mode is not active.",
+            )),
+        }
+    }
+
+    pub(super) fn current_preview_scene(&self) -> Response {
+        self.require_studio_mode()?;
+        Ok(json!({ "currentPreviewSceneName": self.preview_scene }))
+    }
+
+    pub(super) fn set_current_preview_scene(&mut self, args: &Args<'_>) -> Response {
+        self.require_studio_mode()?;
+        let name = self.plain_scene(args.str("sceneName")?)?.name.clone();
+
+        if self.preview_scene.as_deref() != Some(&name) {
+            self.emit("CurrentPreviewSceneChanged", json!({ "sceneName": name }));
+            self.preview_scene = Some(name);
+        }
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn create_scene(&mut self, args: &Args<'_>) -> Response {
+        let name = args.str("sceneName")?;
+        if self.source_exists(name) {
+            return Err(already_exists("source", name));
+        }
+
+        self.scenes.push(Scene::new(name.to_owned(), false));
+        self.emit(
+            "SceneCreated",
+            json!({ "sceneName": name, "isGroup": false }),
+        );
+        self.emit_scene_list_changed();
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn remove_scene(&mut self, args: &Args<'_>) -> Response {
+        let name = self.plain_scene(args.str("sceneName")?)?.name.clone();
+
+        let fallback = self
+            .scenes
+            .iter()
+            .find(|s| !s.is_group && s.name != name)
+            .map(|s| s.name.clone())
+            .ok_or_else(|| {
+                RequestError::new(
+                    StatusCode::NotEnoughResources,
+                    "The last scene of a scene collection can't be removed.",
+                )
+            })?;
+
+        if self.program_scene == name {
+            self.switch_program_scene(fallback.clone());
+        }
+        if self.preview_scene.as_deref() == Some(&name) {
+            self.emit(
+                "CurrentPreviewSceneChanged",
+                json!({ "sceneName": fallback }),
+            );
+            self.preview_scene = Some(fallback);
+        }
+
+        self.remove_source_items(&name);
+        self.scenes.retain(|s| s.name != name);
+        self.emit(
+            "SceneRemoved",
+            json!({ "sceneName": name, "isGroup": false }),
+        );
+        self.emit_scene_list_changed();
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn set_scene_name(&mut self, args: &Args<'_>) -> Response {
+        let name = self.find_scene(args.str("sceneName")?)?.name.clone();
+        let new_name = args.str("newSceneName")?;
+        if self.source_exists(new_name) {
+            return Err(already_exists("source", new_name));
+        }
+
+        self.find_scene_mut(&name)?.name = new_name.to_owned();
+        self.rename_source_items(&name, new_name);
+
+        if self.program_scene == name {
+            self.program_scene = new_name.to_owned();
+        }
+        if self.preview_scene.as_deref() == Some(&name) {
+            self.preview_scene = Some(new_name.to_owned());
+        }
+
+        self.emit(
+            "SceneNameChanged",
+            json!({ "oldSceneName": name, "sceneName": new_name }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn scene_transition_override(&self, args: &Args<'_>) -> Response {
+        let scene = self.plain_scene(args.str("sceneName")?)?;
+
+        Ok(json!({
+            "transitionName": scene.transition_override,
+            "transitionDuration": scene.transition_override_duration,
+        }))
+    }
+
+    pub(super) fn set_scene_transition_override(&mut self, args: &Args<'_>) -> Response {
+        let name = self.plain_scene(args.str("sceneName")?)?.name.clone();
+        let transition = args.opt_str("transitionName")?;
+        let duration = args
+            .opt_i64("transitionDuration")?
+            .map(|duration| check_range("transitionDuration", duration, 50, 20000))
+            .transpose()?;
+
+        if let Some(transition) = transition {
+            if self.transitions.iter().all(|t| t.name != transition) {
+                return Err(not_found("scene transition", transition));
+            }
+        }
+
+        let scene = self.find_scene_mut(&name)?;
+        if transition.is_some() {
+            scene.transition_override = transition.map(ToOwned::to_owned);
+        }
+        if duration.is_some() {
+            scene.transition_override_duration = duration;
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn emit_scene_list_changed(&mut self) {
+        let scenes = match self.scene_list() {
+            Value::Object(mut list) => list.remove("scenes").unwrap_or_default(),
+            _ => Value::Null,
+        };
+
+        self.emit("SceneListChanged", json!({ "scenes": scenes }));
+    }
+
+    /// Names of all sources, that are visible through the given scene, including nested scenes
+    /// and groups.
+    fn visible_sources(&self, scene: &str, sources: &mut Vec<String>) {
+        let scene = match self.find_scene(scene) {
+            Ok(scene) => scene,
+            Err(_) => return,
+        };
+
+        for item in scene.items.iter().filter(|item| item.enabled) {
+            if !sources.contains(&item.source) {
+                sources.push(item.source.clone());
+                self.visible_sources(&item.source, sources);
+            }
+        }
+    }
+
+    pub(super) fn source_active(&self, args: &Args<'_>) -> Response {
+        let name = args.str("sourceName")?;
+        if !self.source_exists(name) {
+            return Err(not_found("source", name));
+        }
+
+        let mut program = vec![self.program_scene.clone()];
+        self.visible_sources(&self.program_scene, &mut program);
+
+        let mut preview = program.clone();
+        if let Some(scene) = &self.preview_scene {
+            preview.push(scene.clone());
+            self.visible_sources(scene, &mut preview);
+        }
+
+        Ok(json!({
+            "videoActive": program.iter().any(|s| s == name),
+            "videoShowing": preview.iter().any(|s| s == name),
+        }))
+    }
+
+    /// Validate the common arguments for taking screenshots of sources.
+    fn screenshot_format<'a>(&self, args: &Args<'a>) -> Result<&'a str, RequestError> {
+        let name = args.str("sourceName")?;
+        if !self.source_exists(name) {
+            return Err(not_found("source", name));
+        }
+
+        let format = args.str("imageFormat")?;
+        if !IMAGE_FORMATS.contains(&format) {
+            return Err(RequestError::new(
+                StatusCode::InvalidRequestField,
+                format!("Your specified image format `{}` is invalid.", format),
+            ));
+        }
+
+        for dimension in ["imageWidth", "imageHeight"] {
+            if let Some(value) = args.opt_i64(dimension)? {
+                check_range(dimension, value, 8, 4096)?;
+            }
+        }
+        if let Some(quality) = args.opt_i64("imageCompressionQuality")? {
+            check_range("imageCompressionQuality", quality, -1, 100)?;
+        }
+
+        Ok(format)
+    }
+
+    pub(super) fn source_screenshot(&self, args: &Args<'_>) -> Response {
+        let format = self.screenshot_format(args)?;
+
+        Ok(json!({
+            "imageData": format!("data:image/{};base64,{}", format, SCREENSHOT_PNG),
+        }))
+    }
+
+    pub(super) fn save_source_screenshot(&self, args: &Args<'_>) -> Response {
+        self.screenshot_format(args)?;
+        let path = Path::new(args.str("imageFilePath")?);
+
+        let invalid_path = || {
+            RequestError::new(
+                StatusCode::InvalidRequestField,
+                "The directory for your file path does not exist.",
+            )
+        };
+
+        if !path.is_absolute() || !path.parent().map_or(false, Path::is_dir) {
+            return Err(invalid_path());
+        }
+
+        let data = base64::decode(SCREENSHOT_PNG).expect("screenshot must be valid base64");
+        std::fs::write(path, data).map_err(|e| {
+            RequestError::new(
+                StatusCode::RequestProcessingFailed,
+                format!("Failed to save screenshot: {}", e),
+            )
+        })?;
+
+        Ok(Value::Null)
+    }
+}
@@ -0,0 +1,153 @@
+use serde_json::{json, Value};
+
+use super::{apply_settings, check_range, not_found, Args, Response, Simulator, Transition};
+use crate::{responses::StatusCode, testing::RequestError};
+
+/// Kinds of transitions, that are available in OBS.
+const TRANSITION_KINDS: &[&str] = &[
+    "cut_transition",
+    "fade_transition",
+    "fade_to_color_transition",
+    "luma_wipe_transition",
+    "obs_stinger_transition",
+    "slide_transition",
+    "swipe_transition",
+];
+
+fn not_configurable(what: &str) -> RequestError {
+    RequestError::new(
+        StatusCode::ResourceNotConfigurable,
+        format!("The current scene transition does not support {}.", what),
+    )
+}
+
+impl Simulator {
+    fn current_transition(&self) -> &Transition {
+        self.transitions
+            .iter()
+            .find(|t| t.name == self.current_transition)
+            .expect("current transition always exists")
+    }
+
+    pub(super) fn transition_kind_list(&self) -> Value {
+        json!({ "transitionKinds": TRANSITION_KINDS })
+    }
+
+    pub(super) fn scene_transition_list(&self) -> Value {
+        let current = self.current_transition();
+        let transitions = self
+            .transitions
+            .iter()
+            .map(|t| {
+                json!({
+                    "transitionName": t.name,
+                    "transitionKind": t.kind,
+                    "transitionFixed": t.fixed,
+                    "transitionConfigurable": t.configurable,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "currentSceneTransitionName": current.name,
+            "currentSceneTransitionKind": current.kind,
+            "transitions": transitions,
+        })
+    }
+
+    pub(super) fn current_scene_transition(&self) -> Value {
+        let current = self.current_transition();
+
+        json!({
+            "transitionName": current.name,
+            "transitionKind": current.kind,
+            "transitionFixed": current.fixed,
+            "transitionDuration": (!current.fixed).then(|| self.transition_duration),
+            "transitionConfigurable": current.configurable,
+            "transitionSettings": current.configurable.then(|| &current.settings),
+        })
+    }
+
+    pub(super) fn set_current_scene_transition(&mut self, args: &Args<'_>) -> Response {
+        let name = args.str("transitionName")?;
+        if self.transitions.iter().all(|t| t.name != name) {
+            return Err(not_found("scene transition", name));
+        }
+
+        if self.current_transition != name {
+            self.current_transition = name.to_owned();
+            self.emit(
+                "CurrentSceneTransitionChanged",
+                json!({ "transitionName": name }),
+            );
+        }
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn set_current_scene_transition_duration(&mut self, args: &Args<'_>) -> Response {
+        let duration = args.i64("transitionDuration")?;
+        let duration = check_range("transitionDuration", duration, 50, 20000)?;
+        if self.current_transition().fixed {
+            return Err(not_configurable("a custom duration"));
+        }
+
+        self.transition_duration = duration;
+        self.emit(
+            "CurrentSceneTransitionDurationChanged",
+            json!({ "transitionDuration": duration }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn set_current_scene_transition_settings(&mut self, args: &Args<'_>) -> Response {
+        let settings = args.object("transitionSettings")?;
+        let overlay = args.opt_bool("overlay")?.unwrap_or(true);
+        if !self.current_transition().configurable {
+            return Err(not_configurable("custom settings"));
+        }
+
+        let current = self.current_transition.clone();
+        let transition = self
+            .transitions
+            .iter_mut()
+            .find(|t| t.name == current)
+            .expect("current transition always exists");
+        apply_settings(&mut transition.settings, settings, overlay);
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn trigger_studio_mode_transition(&mut self) -> Response {
+        self.require_studio_mode()?;
+
+        // Like OBS does by default, the old program scene becomes the new preview scene.
+        let preview = self.preview_scene.clone().unwrap_or_default();
+        let program = self.program_scene.clone();
+        self.switch_program_scene(preview);
+
+        if self.preview_scene.as_ref() != Some(&program) {
+            self.emit(
+                "CurrentPreviewSceneChanged",
+                json!({ "sceneName": program }),
+            );
+            self.preview_scene = Some(program);
+        }
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn set_tbar_position(&mut self, args: &Args<'_>) -> Response {
+        self.require_studio_mode()?;
+        let position = check_range("position", args.f64("position")?, 0.0, 1.0)?;
+        let release = args.opt_bool("release")?.unwrap_or(true);
+
+        // Releasing the T-Bar at its end finishes the transition.
+        if release && position >= 1.0 {
+            self.trigger_studio_mode_transition()?;
+        }
+
+        Ok(Value::Null)
+    }
+}
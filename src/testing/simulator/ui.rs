@@ -0,0 +1,87 @@
+use serde_json::{json, Value};
+
+use super::{check_range, not_found, Args, Response, Simulator};
+use crate::{responses::StatusCode, testing::RequestError};
+
+/// Video mixes, that projectors can be opened for.
+const VIDEO_MIX_TYPES: &[&str] = &[
+    "OBS_WEBSOCKET_VIDEO_MIX_TYPE_PREVIEW",
+    "OBS_WEBSOCKET_VIDEO_MIX_TYPE_PROGRAM",
+    "OBS_WEBSOCKET_VIDEO_MIX_TYPE_MULTIVIEW",
+];
+
+impl Simulator {
+    pub(super) fn set_studio_mode_enabled(&mut self, args: &Args<'_>) -> Response {
+        let enabled = args.bool("studioModeEnabled")?;
+        if enabled == self.preview_scene.is_some() {
+            return Ok(Value::Null);
+        }
+
+        self.preview_scene = enabled.then(|| self.program_scene.clone());
+        self.emit(
+            "StudioModeStateChanged",
+            json!({ "studioModeEnabled": enabled }),
+        );
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn open_input_dialog(&self, args: &Args<'_>) -> Response {
+        self.find_input(args.str("inputName")?)?;
+        Ok(Value::Null)
+    }
+
+    pub(super) fn monitor_list(&self) -> Value {
+        json!({
+            "monitors": [{
+                "monitorName": format!(
+                    "Generic Monitor({}x{}@0,0)",
+                    self.video.base_width, self.video.base_height
+                ),
+                "monitorIndex": 0,
+                "monitorWidth": self.video.base_width,
+                "monitorHeight": self.video.base_height,
+                "monitorPositionX": 0,
+                "monitorPositionY": 0,
+            }],
+        })
+    }
+
+    /// Validate the location of a new projector.
+    fn projector_location(&self, args: &Args<'_>) -> Result<(), RequestError> {
+        if let Some(index) = args.opt_i64("monitorIndex")? {
+            check_range("monitorIndex", index, -1, 0)?;
+            if args.opt_str("projectorGeometry")?.is_some() {
+                return Err(RequestError::new(
+                    StatusCode::TooManyRequestFields,
+                    "`monitorIndex` and `projectorGeometry` are mutually exclusive.",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn open_video_mix_projector(&self, args: &Args<'_>) -> Response {
+        let mix_type = args.str("videoMixType")?;
+        if !VIDEO_MIX_TYPES.contains(&mix_type) {
+            return Err(RequestError::new(
+                StatusCode::InvalidRequestField,
+                format!("Your specified video mix type `{}` is invalid.", mix_type),
+            ));
+        }
+        self.projector_location(args)?;
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn open_source_projector(&self, args: &Args<'_>) -> Response {
+        let name = args.str("sourceName")?;
+        if !self.source_exists(name) {
+            return Err(not_found("source", name));
+        }
+        self.projector_location(args)?;
+
+        Ok(Value::Null)
+    }
+}
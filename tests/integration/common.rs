@@ -1,7 +1,8 @@
-use std::{env, sync::Once};
+use std::sync::Once;
 
 use anyhow::{ensure, Result};
 use obws::{
+    client::ConnectConfig,
     responses::{filters::SourceFilter, inputs::Input, scenes::Scene},
     Client,
 };
@@ -34,14 +35,47 @@ pub async fn new_client() -> Result<Client> {
         tracing_subscriber::fmt::init();
     });
 
-    let host = env::var("OBS_HOST").unwrap_or_else(|_| "localhost".to_owned());
-    let port = env::var("OBS_PORT")
-        .map(|p| p.parse())
-        .unwrap_or(Ok(4455))?;
-    let client = Client::connect(host, port, env::var("OBS_PASSWORD").ok()).await?;
+    #[cfg(feature = "testing")]
+    if std::env::var_os("OBWS_TEST_MOCK").is_some() {
+        return mock_client().await;
+    }
+
+    let client = Client::connect_with_config(ConnectConfig::from_env()?).await?;
+
+    ensure_obs_setup(&client).await?;
+
+    Ok(client)
+}
+
+/// Connect to a mock server instead of a real OBS instance, backed by a simulator that contains
+/// the same setup as described in the `README.md`.
+#[cfg(feature = "testing")]
+async fn mock_client() -> Result<Client> {
+    use obws::testing::{MockServer, Simulator};
+
+    let simulator = Simulator::new()
+        .scene(TEST_SCENE)
+        .scene(TEST_SCENE_2)
+        .group(TEST_GROUP)
+        .input(TEST_SCENE, TEST_TEXT, INPUT_KIND_TEXT_FT2)
+        .input(TEST_SCENE, TEST_TEXT_2, INPUT_KIND_TEXT_FT2)
+        .input(TEST_SCENE, TEST_BROWSER, INPUT_KIND_BROWSER)
+        .input(TEST_SCENE, TEST_MEDIA, INPUT_KIND_VLC)
+        .filter(TEST_TEXT, TEST_FILTER, FILTER_COLOR)
+        .transition(TEST_TRANSITION, "fade_transition")
+        .transition("OBWS-TEST-Transition2", "slide_transition")
+        .profile(TEST_PROFILE)
+        .scene_collection(TEST_PROFILE);
+
+    let server = MockServer::builder().simulator(simulator).start().await?;
+    let client = server.connect().await?;
 
     ensure_obs_setup(&client).await?;
 
+    // The server shuts down once dropped, but must stay around for the whole test. Its tasks end
+    // together with the runtime of the test.
+    std::mem::forget(server);
+
     Ok(client)
 }
 
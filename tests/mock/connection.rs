@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use obws::{client::ConnectionState, testing::MockServer, Error};
+
+#[tokio::test]
+async fn disconnect() {
+    let server = MockServer::start().await.unwrap();
+    let mut client = server.connect().await.unwrap();
+
+    tokio::time::timeout(Duration::from_secs(1), client.disconnect())
+        .await
+        .unwrap();
+    assert!(client.state().borrow().is_closed());
+    assert!(matches!(
+        client.general().version().await,
+        Err(Error::Send(_))
+    ));
+}
+
+#[cfg(feature = "events")]
+#[tokio::test]
+async fn reconnect() {
+    use futures_util::StreamExt;
+    use obws::{
+        client::{Backoff, ConnectConfig, ReconnectPolicy},
+        events::Event,
+        requests::EventSubscription,
+        Client,
+    };
+    use serde_json::json;
+
+    let server = MockServer::start().await.unwrap();
+    let mut config = ConnectConfig::new("127.0.0.1", server.port(), None::<&str>);
+    config.reconnect = Some(ReconnectPolicy {
+        max_attempts: Some(5),
+        backoff: Backoff {
+            initial_delay: Duration::from_millis(10),
+            ..Backoff::default()
+        },
+    });
+    let client = Client::connect_with_config(config).await.unwrap();
+    client.reidentify(EventSubscription::SCENES).await.unwrap();
+    let events = client.events().unwrap();
+    futures_util::pin_mut!(events);
+    let mut state = client.state();
+    server.clear_requests();
+
+    server.drop_connections();
+
+    assert!(matches!(events.next().await, Some(Event::ServerStopped)));
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while *state.borrow_and_update() != ConnectionState::Identified {
+            state.changed().await.unwrap();
+        }
+    })
+    .await
+    .unwrap();
+
+    // The new connection identified with the latest event subscriptions, and fetched the
+    // version again.
+    assert_eq!(
+        EventSubscription::SCENES,
+        client.session().event_subscriptions
+    );
+    let requests = server.requests();
+    assert_eq!(1, requests.len());
+    assert_eq!("GetVersion", requests[0].request_type);
+
+    client.general().version().await.unwrap();
+
+    server.push_event("RecordStateChanged", json!({ "outputActive": true }));
+    server.push_event("CurrentProgramSceneChanged", json!({ "sceneName": "Main" }));
+    assert!(matches!(
+        events.next().await,
+        Some(Event::CurrentProgramSceneChanged { name }) if name == "Main"
+    ));
+}
+
+#[tokio::test]
+async fn closed_by_server() {
+    use obws::responses::WebSocketCloseCode;
+
+    let server = MockServer::start().await.unwrap();
+    let client = server.connect().await.unwrap();
+    let mut state = client.state();
+    assert_eq!(ConnectionState::Identified, *state.borrow_and_update());
+
+    let recorder = tokio::spawn(async move {
+        let mut states = Vec::new();
+        while state.changed().await.is_ok() {
+            let current = state.borrow_and_update().clone();
+            let closed = current.is_closed();
+            states.push(current);
+            if closed {
+                break;
+            }
+        }
+        states
+    });
+
+    server.close_connections(WebSocketCloseCode::SessionInvalidated, "kicked");
+
+    let closed = tokio::time::timeout(Duration::from_secs(1), client.closed())
+        .await
+        .unwrap();
+    let expected = ConnectionState::Closed {
+        code: Some(WebSocketCloseCode::SessionInvalidated),
+        reason: Some("kicked".to_owned()),
+    };
+    assert_eq!(expected, closed);
+    assert_eq!(
+        vec![ConnectionState::Closing, expected],
+        recorder.await.unwrap()
+    );
+}
@@ -0,0 +1,63 @@
+use futures_util::StreamExt;
+use obws::{
+    client::ConnectConfig,
+    events::{Event, OutputEvent},
+    requests::EventSubscription,
+    testing::MockServer,
+    Client,
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn filtered_events() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.connect().await.unwrap();
+    let events = client.events_filtered(EventSubscription::OUTPUTS).unwrap();
+    futures_util::pin_mut!(events);
+
+    server.push_event("CurrentProgramSceneChanged", json!({ "sceneName": "Main" }));
+    server.push_event(
+        "RecordStateChanged",
+        json!({
+            "outputActive": true,
+            "outputState": "OBS_WEBSOCKET_OUTPUT_STARTED",
+            "outputPath": "/tmp/recording.mkv",
+        }),
+    );
+
+    let event = events.next().await.unwrap();
+    assert_eq!(EventSubscription::OUTPUTS, event.category());
+    assert!(matches!(
+        OutputEvent::try_from(event),
+        Ok(OutputEvent::RecordStateChanged { active: true, .. })
+    ));
+}
+
+#[tokio::test]
+async fn lagging_events() {
+    let server = MockServer::start().await.unwrap();
+    let mut config = ConnectConfig::new("127.0.0.1", server.port(), None::<&str>);
+    config.broadcast_capacity = Some(1);
+    let client = Client::connect_with_config(config).await.unwrap();
+    let events = client.events().unwrap();
+    futures_util::pin_mut!(events);
+    let unbounded = client.events_unbounded().unwrap();
+    futures_util::pin_mut!(unbounded);
+
+    for name in ["A", "B", "C"] {
+        server.push_event("CurrentProgramSceneChanged", json!({ "sceneName": name }));
+    }
+
+    for expected in ["A", "B", "C"] {
+        assert!(matches!(
+            unbounded.next().await,
+            Some(Event::CurrentProgramSceneChanged { name }) if name == expected
+        ));
+    }
+
+    assert!(matches!(events.next().await, Some(Event::Lagged(2))));
+    assert!(matches!(
+        events.next().await,
+        Some(Event::CurrentProgramSceneChanged { name }) if name == "C"
+    ));
+}
@@ -0,0 +1,4 @@
+mod connection;
+#[cfg(feature = "events")]
+mod events;
+mod requests;
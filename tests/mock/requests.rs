@@ -0,0 +1,325 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use obws::{
+    client::{
+        Backoff, ConnectConfig, InterceptedRequest, LatencyHistogram, RequestCategory,
+        RequestInterceptor, ResponseOutcome, RetryPolicy, TracingInterceptor,
+    },
+    requests::{
+        inputs::Volume,
+        scene_items::{Position, Scale, SceneItemTransform, SetTransform},
+    },
+    responses::StatusCode,
+    testing::{MockServer, RequestError, Simulator},
+    Client, Error,
+};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn interceptors() {
+    /// Records all calls and vetoes requests of the given type.
+    struct Log {
+        name: &'static str,
+        veto: &'static str,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl RequestInterceptor for Log {
+        fn before_send(&self, request: &InterceptedRequest<'_>) -> Result<(), String> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("{} before {}", self.name, request.request_type));
+            if request.request_type == self.veto {
+                return Err("not allowed".to_owned());
+            }
+            Ok(())
+        }
+
+        fn after_response(
+            &self,
+            request: &InterceptedRequest<'_>,
+            outcome: &ResponseOutcome<'_>,
+            _latency: Duration,
+        ) {
+            let result = match outcome {
+                ResponseOutcome::Response { status, .. } => format!("{:?}", status.code),
+                ResponseOutcome::Failed(_) => "failed".to_owned(),
+            };
+            self.calls.lock().unwrap().push(format!(
+                "{} after {} {}",
+                self.name, request.request_type, result
+            ));
+        }
+    }
+
+    let server = MockServer::start().await.unwrap();
+    server.fail("StartRecord", StatusCode::OutputRunning);
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let histogram = Arc::new(LatencyHistogram::new());
+    let mut config = ConnectConfig::new("127.0.0.1", server.port(), None::<&str>);
+    config.interceptors = vec![
+        Arc::new(TracingInterceptor::new()),
+        histogram.clone(),
+        Arc::new(Log {
+            name: "outer",
+            veto: "",
+            calls: Arc::clone(&calls),
+        }),
+        Arc::new(Log {
+            name: "inner",
+            veto: "StartStream",
+            calls: Arc::clone(&calls),
+        }),
+    ];
+    let client = Client::connect_with_config(config).await.unwrap();
+    server.clear_requests();
+
+    assert!(client.recording().start().await.is_err());
+    assert!(matches!(
+        client.streaming().start().await,
+        Err(Error::Vetoed { request_type, reason })
+            if request_type == "StartStream" && reason == "not allowed"
+    ));
+    assert_eq!(
+        vec![
+            "outer before StartRecord",
+            "inner before StartRecord",
+            "inner after StartRecord OutputRunning",
+            "outer after StartRecord OutputRunning",
+            "outer before StartStream",
+            "inner before StartStream",
+            "outer after StartStream failed",
+        ],
+        *calls.lock().unwrap()
+    );
+    let requests = server.requests();
+    assert_eq!(1, requests.len());
+    assert_eq!("StartRecord", requests[0].request_type);
+
+    let counts = histogram.snapshot();
+    assert_eq!(1, counts["StartRecord"].iter().sum::<u64>());
+    assert!(!counts.contains_key("StartStream"));
+}
+
+#[tokio::test]
+async fn coalesce_setters() {
+    let server = MockServer::start().await.unwrap();
+    server.respond("SetInputVolume", Value::Null);
+
+    let mut config = ConnectConfig::new("127.0.0.1", server.port(), None::<&str>);
+    config.coalesce_setters = true;
+    let client = Client::connect_with_config(config).await.unwrap();
+    server.clear_requests();
+
+    let inputs = client.inputs();
+    let results = futures_util::future::join_all(
+        (0..10_u8).map(|i| inputs.set_volume("Mic", Volume::Db(f32::from(i)))),
+    )
+    .await;
+    assert!(results.iter().all(Result::is_ok));
+
+    let volumes = server
+        .requests()
+        .into_iter()
+        .map(|request| request.request_data.unwrap()["inputVolumeDb"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![json!(0.0), json!(9.0)], volumes);
+
+    // Raw requests can't tell whether the response data is needed, so they report it instead.
+    server.clear_requests();
+    let results = futures_util::future::join_all((0..3_u8).map(|i| {
+        client.raw_request_as::<()>(
+            "SetInputVolume",
+            json!({ "inputName": "Mic", "inputVolumeDb": i }),
+        )
+    }))
+    .await;
+    assert!(results[0].is_ok());
+    assert!(matches!(
+        &results[1],
+        Err(Error::Superseded { request_type }) if request_type == "SetInputVolume"
+    ));
+    assert!(results[2].is_ok());
+    assert_eq!(2, server.requests().len());
+}
+
+#[tokio::test]
+async fn coalesce_partial_transforms() {
+    let server = MockServer::builder()
+        .simulator(Simulator::new().input("Scene", "Text", "text_ft2_source_v2"))
+        .start()
+        .await
+        .unwrap();
+    let mut config = ConnectConfig::new("127.0.0.1", server.port(), None::<&str>);
+    config.coalesce_setters = true;
+    let client = Client::connect_with_config(config).await.unwrap();
+    server.clear_requests();
+
+    let scene_items = client.scene_items();
+    let item_id = scene_items.list("Scene").await.unwrap()[0].id;
+    let transforms = [
+        SceneItemTransform {
+            rotation: Some(45.0),
+            ..SceneItemTransform::default()
+        },
+        SceneItemTransform {
+            position: Some(Position {
+                x: Some(100.0),
+                y: None,
+            }),
+            ..SceneItemTransform::default()
+        },
+        SceneItemTransform {
+            scale: Some(Scale {
+                x: Some(2.0),
+                y: None,
+            }),
+            ..SceneItemTransform::default()
+        },
+    ];
+    let results = futures_util::future::join_all(transforms.into_iter().map(|transform| {
+        scene_items.set_transform(SetTransform {
+            scene: "Scene",
+            item_id,
+            transform,
+        })
+    }))
+    .await;
+    assert!(results.iter().all(Result::is_ok));
+
+    // Each call only updates some fields, so none of them may be skipped.
+    let transform = scene_items.transform("Scene", item_id).await.unwrap();
+    assert_eq!(45.0, transform.rotation);
+    assert_eq!(100.0, transform.position_x);
+    assert_eq!(2.0, transform.scale_x);
+    assert_eq!(5, server.requests().len());
+}
+
+#[tokio::test]
+async fn retry() {
+    let server = MockServer::start().await.unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    server.on_request("GetCurrentProgramScene", move |_| {
+        match counter.fetch_add(1, Ordering::SeqCst) {
+            0 | 1 => Err(RequestError::new(
+                StatusCode::RequestProcessingFailed,
+                "busy",
+            )),
+            _ => Ok(json!({ "currentProgramSceneName": "Main" })),
+        }
+    });
+    server.fail("CreateScene", StatusCode::ResourceActionFailed);
+    server.fail("SetCurrentProgramScene", StatusCode::ResourceNotFound);
+
+    let mut config = ConnectConfig::new("127.0.0.1", server.port(), None::<&str>);
+    config.retry = Some(RetryPolicy {
+        backoff: Backoff {
+            initial_delay: Duration::from_millis(1),
+            ..Backoff::default()
+        },
+        ..RetryPolicy::default()
+    });
+    let client = Client::connect_with_config(config).await.unwrap();
+    server.clear_requests();
+
+    let scenes = client.scenes();
+    assert_eq!("Main", scenes.current_program_scene().await.unwrap());
+    assert_eq!(3, server.requests().len());
+
+    // Requests that aren't idempotent or failed permanently are not retried.
+    server.clear_requests();
+    let error = scenes.create("New").await.unwrap_err();
+    assert!(error.is_retryable());
+    let error = scenes.set_current_program_scene("Other").await.unwrap_err();
+    assert!(!error.is_retryable());
+    assert_eq!(Some(StatusCode::ResourceNotFound), error.status_code());
+    assert_eq!(2, server.requests().len());
+}
+
+#[tokio::test]
+async fn raw_requests() {
+    let server = MockServer::start().await.unwrap();
+    server.on_request("GetFutureThing", |data| match data {
+        Some(data) => Ok(json!({ "thing": data["name"] })),
+        None => Ok(json!({ "thing": "none" })),
+    });
+    let client = server.connect().await.unwrap();
+    server.clear_requests();
+
+    let response = client
+        .raw_request("GetFutureThing", json!({ "name": "a" }))
+        .await
+        .unwrap();
+    assert_eq!(json!({ "thing": "a" }), response);
+
+    let thing = client
+        .raw_request_as::<HashMap<String, String>>("GetFutureThing", ())
+        .await
+        .unwrap();
+    assert_eq!("none", thing["thing"]);
+
+    assert!(matches!(
+        client.raw_request("GetFutureThing", [1, 2]).await,
+        Err(Error::InvalidCustomData)
+    ));
+    assert!(matches!(
+        client.raw_request("DoFutureThing", ()).await,
+        Err(Error::Unsupported { request_type }) if request_type == "DoFutureThing"
+    ));
+    assert!(matches!(
+        client
+            .raw_request("Sleep", json!({ "sleepMillis": 10 }))
+            .await,
+        Err(Error::SleepOutsideBatch)
+    ));
+
+    let mut batch = client.batch();
+    let first = batch.add(client.raw_request("GetFutureThing", json!({ "name": "b" })));
+    let sleep = batch.add(client.raw_request("Sleep", json!({ "sleepMillis": 10 })));
+    let second = batch.add(client.raw_request_as::<Value>("GetFutureThing", ()));
+    let mut results = batch.send().await.unwrap();
+    assert_eq!("b", results.take(first).unwrap()["thing"]);
+    assert!(results.take(sleep).is_ok());
+    assert_eq!("none", results.take(second).unwrap()["thing"]);
+
+    let requests = server.requests();
+    assert_eq!(5, requests.len());
+    assert_eq!(Some(json!({ "name": "a" })), requests[0].request_data);
+    assert_eq!(None, requests[1].request_data);
+}
+
+#[tokio::test]
+async fn capabilities() {
+    let server = MockServer::builder()
+        .available_requests(["GetVersion", "GetSceneList", "GetSceneItemSource"])
+        .start()
+        .await
+        .unwrap();
+    server.respond("StartStream", Value::Null);
+    let client = server.connect().await.unwrap();
+    server.clear_requests();
+
+    assert!(client.supports("GetSceneList"));
+    assert!(!client.supports("StartStream"));
+    assert!(matches!(
+        client.streaming().start().await,
+        Err(Error::Unsupported { request_type }) if request_type == "StartStream"
+    ));
+    assert!(server.requests().is_empty());
+
+    let capabilities = client.capabilities();
+    assert_eq!(vec!["GetSceneItemSource"], capabilities.uncovered);
+    let scenes = capabilities.category(RequestCategory::Scenes);
+    assert_eq!(vec!["GetSceneList"], scenes.supported);
+    assert!(!scenes.is_complete());
+}